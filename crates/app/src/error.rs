@@ -0,0 +1,85 @@
+//! Unified application error type
+//!
+//! Application code bridging `core` and `telemetry` can return a single
+//! `AppResult<T>` and use `?` on any of the crates' error types. Each variant
+//! wraps its source transparently, so the original message is preserved.
+
+use rms_core::{ComponentError, SchedulerError};
+use telemetry::resilience::ResilienceError;
+use telemetry::streaming::StreamingError;
+use telemetry::{ConfigError, TransportError};
+use thiserror::Error;
+
+/// Error type covering every error the application's crates can return
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error(transparent)]
+    Component(#[from] ComponentError),
+    #[error(transparent)]
+    Scheduler(#[from] SchedulerError),
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    #[error(transparent)]
+    Streaming(#[from] StreamingError),
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Resilience(#[from] ResilienceError),
+}
+
+/// Result type using `AppError`
+pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Propagate `error` through `?` and return the resulting `AppError`
+    fn propagate<E>(error: E) -> AppError
+    where
+        AppError: From<E>,
+    {
+        fn fail<E>(error: E) -> AppResult<()>
+        where
+            AppError: From<E>,
+        {
+            Err(error)?
+        }
+        fail(error).unwrap_err()
+    }
+
+    #[test]
+    fn test_conversions_preserve_messages() {
+        let cases = [
+            (
+                propagate(ComponentError::new("sensor offline")),
+                "Component Error: sensor offline",
+            ),
+            (
+                propagate(SchedulerError::InvalidFrequency),
+                "Invalid frequency specified",
+            ),
+            (
+                propagate(TransportError::Closed),
+                "Transport closed or channel error",
+            ),
+            (propagate(StreamingError::ChannelClosed), "Channel closed"),
+            (
+                propagate(ConfigError::Invalid("batch_size".to_string())),
+                "Invalid configuration: batch_size",
+            ),
+            (
+                propagate(ResilienceError::BufferFull),
+                "Buffer full, dropped packet",
+            ),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+        }
+        assert!(matches!(
+            propagate(TransportError::Closed),
+            AppError::Transport(_)
+        ));
+    }
+}
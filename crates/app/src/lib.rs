@@ -33,10 +33,12 @@
 //! }
 //! ```
 
+pub mod error;
 pub mod simulation_api;
 
 // Re-export commonly used types for convenience
+pub use error::{AppError, AppResult};
 pub use simulation_api::{
-    ActuatorCommand, ComponentStats, SensorData, SimulationConfig, SimulationEngine,
-    TelemetrySnapshot,
+    ActuatorCommand, ComponentStats, ObservableValue, SensorData, SimulationConfig,
+    SimulationEngine, SnapshotDiff, TelemetrySnapshot, ValidationIssue, ValidationReport,
 };
@@ -18,8 +18,12 @@ async fn async_main() {
     let logger = Arc::new(Logger::new(LogLevel::Info));
     logger.log(LogLevel::Info, "Application starting...");
 
-    // Create component manager wrapped for shared async access
-    let manager = Arc::new(Mutex::new(ComponentManager::new()));
+    // Create component manager wrapped for shared async access. Components
+    // that ignore cancellation are abandoned 5s after Ctrl-C so shutdown
+    // cannot block forever on the manager lock.
+    let manager = Arc::new(Mutex::new(
+        ComponentManager::new().with_run_grace(std::time::Duration::from_secs(5)),
+    ));
 
     // Register components
     let sensor = Box::new(TemperatureSensor::new("sensor-001", "Temperature Sensor"));
@@ -55,8 +59,13 @@ async fn async_main() {
     println!("\n--- Health Check Phase ---");
     {
         let mgr = manager.lock().await;
-        if let Err(e) = mgr.health_check_all().await {
-            logger.log(LogLevel::Error, &format!("Health check failed: {}", e));
+        if let Err(failures) = mgr.health_check_all().await {
+            for (id, e) in failures {
+                logger.log(
+                    LogLevel::Error,
+                    &format!("Health check failed for {}: {}", id, e),
+                );
+            }
         } else {
             logger.log(LogLevel::Info, "All components healthy");
         }
@@ -98,7 +107,8 @@ async fn async_main() {
 
     let run_handle = tokio::spawn(async move {
         let mut mgr = manager_run.lock().await;
-        let res = mgr.run_all(shutdown_child).await;
+        // The motor runs until cancelled, so components must run side by side
+        let res = mgr.run_all_concurrent(shutdown_child).await;
         if let Err(e) = &res {
             logger_run.log(LogLevel::Error, &format!("Execution failed: {}", e));
         }
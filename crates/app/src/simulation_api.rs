@@ -48,11 +48,12 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{watch, Mutex, RwLock};
 
 // Re-export commonly used types
 pub use rms_core::{ComponentError, ComponentResult};
-pub use telemetry::{SystemHealth, TelemetryPacket};
+use telemetry::Unit;
+pub use telemetry::{HealthStatus, SystemHealth, TelemetryPacket};
 
 /// Configuration for the simulation engine
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -137,6 +138,89 @@ impl SensorData {
     }
 }
 
+/// Convert engine data into a telemetry reading payload, attaching units
+///
+/// Values are narrowed from f64 to f32 except GPS latitude/longitude, so
+/// temperatures, pressures, altitudes, accelerations and numeric values lose
+/// precision beyond about seven significant digits. GPS accuracy is unknown
+/// and reported as 0.0. Numeric values become unitless analog readings, and
+/// strings and booleans become digital readings (a string is carried as the
+/// label of a `true` state).
+impl From<SensorData> for telemetry::SensorData {
+    fn from(data: SensorData) -> Self {
+        match data {
+            SensorData::Temperature(celsius) => Self::Temperature {
+                value: celsius as f32,
+                unit: Unit::Celsius.symbol().to_string(),
+            },
+            SensorData::Pressure(hpa) => Self::Pressure {
+                value: hpa as f32,
+                unit: Unit::Hectopascal.symbol().to_string(),
+            },
+            SensorData::GpsPosition(latitude, longitude, altitude) => Self::Gps {
+                latitude,
+                longitude,
+                altitude: altitude as f32,
+                accuracy: 0.0,
+            },
+            SensorData::Acceleration(x, y, z) => Self::Accelerometer {
+                x: x as f32,
+                y: y as f32,
+                z: z as f32,
+                unit: Unit::MetersPerSecondSquared.symbol().to_string(),
+            },
+            SensorData::Numeric(value) => Self::Analog {
+                value: value as f32,
+                unit: String::new(),
+            },
+            SensorData::String(label) => Self::Digital { state: true, label },
+            SensorData::Bool(state) => Self::Digital {
+                state,
+                label: String::new(),
+            },
+        }
+    }
+}
+
+/// Convert a telemetry payload back into engine data where nothing is lost
+///
+/// Only payloads in the engine's own units map back: Celsius temperatures,
+/// hPa pressures, m/s² accelerations, unitless analog values and unlabelled
+/// digital states. GPS readings map back only when the accuracy is 0.0 (i.e.
+/// unknown). Anything else is returned unchanged as the error.
+impl TryFrom<telemetry::SensorData> for SensorData {
+    type Error = telemetry::SensorData;
+
+    fn try_from(data: telemetry::SensorData) -> Result<Self, Self::Error> {
+        use telemetry::SensorData as Telemetry;
+
+        match data {
+            Telemetry::Temperature { value, ref unit } if unit == Unit::Celsius.symbol() => {
+                Ok(Self::Temperature(value as f64))
+            }
+            Telemetry::Pressure { value, ref unit } if unit == Unit::Hectopascal.symbol() => {
+                Ok(Self::Pressure(value as f64))
+            }
+            Telemetry::Gps {
+                latitude,
+                longitude,
+                altitude,
+                accuracy: 0.0,
+            } => Ok(Self::GpsPosition(latitude, longitude, altitude as f64)),
+            Telemetry::Accelerometer { x, y, z, ref unit }
+                if unit == Unit::MetersPerSecondSquared.symbol() =>
+            {
+                Ok(Self::Acceleration(x as f64, y as f64, z as f64))
+            }
+            Telemetry::Analog { value, ref unit } if unit.is_empty() => {
+                Ok(Self::Numeric(value as f64))
+            }
+            Telemetry::Digital { state, ref label } if label.is_empty() => Ok(Self::Bool(state)),
+            other => Err(other),
+        }
+    }
+}
+
 /// Actuator command types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ActuatorCommand {
@@ -150,6 +234,37 @@ pub enum ActuatorCommand {
     Custom(String),
 }
 
+/// A value that notifies subscribers whenever it is set
+///
+/// Subscribers get a `watch::Receiver` and can `changed().await` instead of
+/// polling the current value in a loop.
+pub struct ObservableValue<T> {
+    tx: watch::Sender<T>,
+}
+
+impl<T: Clone> ObservableValue<T> {
+    /// Create an observable holding `value`
+    pub fn new(value: T) -> Self {
+        let (tx, _rx) = watch::channel(value);
+        Self { tx }
+    }
+
+    /// Get a copy of the current value
+    pub fn get(&self) -> T {
+        self.tx.borrow().clone()
+    }
+
+    /// Replace the value and notify all subscribers
+    pub fn set(&self, value: T) {
+        self.tx.send_replace(value);
+    }
+
+    /// Subscribe to changes of this value
+    pub fn subscribe(&self) -> watch::Receiver<T> {
+        self.tx.subscribe()
+    }
+}
+
 /// Statistics about a component
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentStats {
@@ -173,6 +288,98 @@ pub struct TelemetrySnapshot {
     pub timestamp: String,
 }
 
+impl TelemetrySnapshot {
+    /// Report what changed since `previous`
+    ///
+    /// A component counts as changed when its name, iteration count or
+    /// error count differs; `last_update` alone is ignored since it is
+    /// refreshed on every collection. Component ids are sorted.
+    pub fn diff(&self, previous: &TelemetrySnapshot) -> SnapshotDiff {
+        let mut diff = SnapshotDiff::default();
+        for (id, stats) in &self.component_stats {
+            match previous.component_stats.get(id) {
+                None => diff.added.push(id.clone()),
+                Some(old)
+                    if old.name != stats.name
+                        || old.iterations != stats.iterations
+                        || old.errors != stats.errors =>
+                {
+                    diff.changed.push(id.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = previous
+            .component_stats
+            .keys()
+            .filter(|id| !self.component_stats.contains_key(*id))
+            .cloned()
+            .collect();
+        diff.added.sort();
+        diff.changed.sort();
+        diff.removed.sort();
+
+        let before = previous.health.as_ref().map(|h| h.status);
+        let after = self.health.as_ref().map(|h| h.status);
+        if before != after {
+            diff.health_transition = Some((before, after));
+        }
+        diff
+    }
+}
+
+/// Changes between two telemetry snapshots, see [`TelemetrySnapshot::diff`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    /// Components present in both snapshots whose stats changed
+    pub changed: Vec<String>,
+    /// Components only present in the newer snapshot
+    pub added: Vec<String>,
+    /// Components only present in the older snapshot
+    pub removed: Vec<String>,
+    /// System health status before and after, if it changed
+    pub health_transition: Option<(Option<HealthStatus>, Option<HealthStatus>)>,
+}
+
+impl SnapshotDiff {
+    /// Check whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.health_transition.is_none()
+    }
+}
+
+/// A potential setup mistake found by `SimulationEngine::dry_run`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationIssue {
+    /// No sensors are registered
+    NoSensors,
+    /// No actuators are registered
+    NoActuators,
+    /// A sensor has never had data injected
+    SensorNeverInjected(String),
+    /// An actuator has never received a command
+    ActuatorWithoutCommand(String),
+    /// A sensor samples faster than the control loop and is capped to its rate
+    SensorRateAboveLoop { sensor_id: String, hz: u32 },
+}
+
+/// Result of validating a simulation setup without running it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// Issues found, sorted by component id within each kind
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Check whether no issues were found
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
 /// Main simulation engine interface
 pub struct SimulationEngine {
     config: SimulationConfig,
@@ -186,14 +393,35 @@ struct SensorInfo {
     #[allow(dead_code)]
     id: String,
     name: String,
-    latest_data: Option<SensorData>,
+    latest_data: ObservableValue<Option<SensorData>>,
+    /// Sampling rate in Hz (`None` samples on every iteration)
+    sample_rate_hz: Option<u32>,
+    /// Number of iterations in which this sensor was sampled
+    samples: u64,
+}
+
+impl SensorInfo {
+    /// Check whether this sensor's phase aligns with the given iteration
+    ///
+    /// A sensor sampling at `hz` under an engine running at `control_loop_hz`
+    /// is sampled every `control_loop_hz / hz` iterations, starting with the
+    /// first one. Rates at or above the loop rate sample on every iteration.
+    fn is_due(&self, iteration: u64, control_loop_hz: u32) -> bool {
+        match self.sample_rate_hz {
+            None => true,
+            Some(hz) => {
+                let period = (control_loop_hz / hz).max(1) as u64;
+                (iteration - 1).is_multiple_of(period)
+            }
+        }
+    }
 }
 
 struct ActuatorInfo {
     #[allow(dead_code)]
     id: String,
     name: String,
-    last_command: Option<ActuatorCommand>,
+    last_command: ObservableValue<Option<ActuatorCommand>>,
 }
 
 impl SimulationEngine {
@@ -209,17 +437,50 @@ impl SimulationEngine {
     }
 
     /// Register a sensor with the simulation
+    ///
+    /// The sensor is sampled on every control loop iteration.
     pub async fn register_sensor(&self, id: &str, sensor_type: &str) -> ComponentResult<()> {
+        self.insert_sensor(id, sensor_type, None).await;
+        Ok(())
+    }
+
+    /// Register a sensor that samples at its own rate
+    ///
+    /// The sensor is only sampled on iterations whose phase aligns with
+    /// `hz` given the engine's `control_loop_hz`, which models multi-rate
+    /// systems (e.g. GPS at 1Hz alongside an IMU at 100Hz).
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or an error if `hz` is zero
+    pub async fn register_sensor_with_rate(
+        &self,
+        id: &str,
+        sensor_type: &str,
+        hz: u32,
+    ) -> ComponentResult<()> {
+        if hz == 0 {
+            return Err(ComponentError::new(format!(
+                "Sensor {} sampling rate must be greater than 0 Hz",
+                id
+            )));
+        }
+        self.insert_sensor(id, sensor_type, Some(hz)).await;
+        Ok(())
+    }
+
+    async fn insert_sensor(&self, id: &str, sensor_type: &str, sample_rate_hz: Option<u32>) {
         let mut sensors = self.sensors.write().await;
         sensors.insert(
             id.to_string(),
             SensorInfo {
                 id: id.to_string(),
                 name: sensor_type.to_string(),
-                latest_data: None,
+                latest_data: ObservableValue::new(None),
+                sample_rate_hz,
+                samples: 0,
             },
         );
-        Ok(())
     }
 
     /// Register an actuator with the simulation
@@ -230,7 +491,7 @@ impl SimulationEngine {
             ActuatorInfo {
                 id: id.to_string(),
                 name: actuator_type.to_string(),
-                last_command: None,
+                last_command: ObservableValue::new(None),
             },
         );
         Ok(())
@@ -243,6 +504,72 @@ impl SimulationEngine {
         Ok(())
     }
 
+    /// Validate the simulation setup without running it
+    ///
+    /// Checks that sensors and actuators are registered, that every sensor
+    /// has had data injected, that every actuator has a command, and that no
+    /// sensor samples faster than the control loop.
+    ///
+    /// # Returns
+    ///
+    /// A report of potential issues, or an error if the configuration is
+    /// invalid (zero control loop rate or timeout) and the engine cannot run
+    pub async fn dry_run(&self) -> ComponentResult<ValidationReport> {
+        if self.config.control_loop_hz == 0 {
+            return Err(ComponentError::new(
+                "Control loop rate must be greater than 0 Hz",
+            ));
+        }
+        if self.config.timeout_secs == 0 {
+            return Err(ComponentError::new(
+                "Operation timeout must be greater than 0 seconds",
+            ));
+        }
+
+        let mut report = ValidationReport::default();
+        let sensors = self.sensors.read().await;
+        let actuators = self.actuators.read().await;
+
+        if sensors.is_empty() {
+            report.issues.push(ValidationIssue::NoSensors);
+        }
+        if actuators.is_empty() {
+            report.issues.push(ValidationIssue::NoActuators);
+        }
+
+        let mut sensor_ids: Vec<&String> = sensors.keys().collect();
+        sensor_ids.sort();
+        for id in &sensor_ids {
+            if sensors[*id].latest_data.get().is_none() {
+                report
+                    .issues
+                    .push(ValidationIssue::SensorNeverInjected((*id).clone()));
+            }
+        }
+        for id in &sensor_ids {
+            if let Some(hz) = sensors[*id].sample_rate_hz {
+                if hz > self.config.control_loop_hz {
+                    report.issues.push(ValidationIssue::SensorRateAboveLoop {
+                        sensor_id: (*id).clone(),
+                        hz,
+                    });
+                }
+            }
+        }
+
+        let mut actuator_ids: Vec<&String> = actuators.keys().collect();
+        actuator_ids.sort();
+        for id in actuator_ids {
+            if actuators[id].last_command.get().is_none() {
+                report
+                    .issues
+                    .push(ValidationIssue::ActuatorWithoutCommand(id.clone()));
+            }
+        }
+
+        Ok(report)
+    }
+
     /// Get current health status of the system
     pub async fn get_health_status(&self) -> ComponentResult<Option<SystemHealth>> {
         let health = self.health_status.lock().await;
@@ -264,9 +591,9 @@ impl SimulationEngine {
         sensor_id: &str,
         data: SensorData,
     ) -> ComponentResult<()> {
-        let mut sensors = self.sensors.write().await;
-        if let Some(sensor) = sensors.get_mut(sensor_id) {
-            sensor.latest_data = Some(data);
+        let sensors = self.sensors.read().await;
+        if let Some(sensor) = sensors.get(sensor_id) {
+            sensor.latest_data.set(Some(data));
             Ok(())
         } else {
             Err(ComponentError::new(format!(
@@ -291,9 +618,9 @@ impl SimulationEngine {
         actuator_id: &str,
         command: ActuatorCommand,
     ) -> ComponentResult<()> {
-        let mut actuators = self.actuators.write().await;
-        if let Some(actuator) = actuators.get_mut(actuator_id) {
-            actuator.last_command = Some(command);
+        let actuators = self.actuators.read().await;
+        if let Some(actuator) = actuators.get(actuator_id) {
+            actuator.last_command.set(Some(command));
             Ok(())
         } else {
             Err(ComponentError::new(format!(
@@ -306,15 +633,22 @@ impl SimulationEngine {
     /// Execute a single control loop iteration
     ///
     /// This processes all sensor inputs, executes control logic, and updates actuators.
+    /// Only sensors whose sampling phase aligns with this iteration are sampled.
     pub async fn execute_iteration(&self) -> ComponentResult<()> {
         let mut count = self.iteration_count.lock().await;
         *count += 1;
 
-        // In a real implementation, this would:
-        // 1. Read all sensor data
-        // 2. Execute control logic
-        // 3. Update actuators
-        // 4. Collect diagnostics
+        let mut sensors = self.sensors.write().await;
+        for sensor in sensors.values_mut() {
+            if sensor.is_due(*count, self.config.control_loop_hz) {
+                sensor.samples += 1;
+            }
+        }
+
+        // In a real implementation, this would also:
+        // 1. Execute control logic
+        // 2. Update actuators
+        // 3. Collect diagnostics
 
         Ok(())
     }
@@ -334,7 +668,7 @@ impl SimulationEngine {
                 ComponentStats {
                     id: id.clone(),
                     name: sensor.name.clone(),
-                    iterations: *iteration,
+                    iterations: sensor.samples,
                     errors: 0,
                     last_update: chrono::Utc::now().to_rfc3339(),
                 },
@@ -371,7 +705,20 @@ impl SimulationEngine {
     pub async fn get_sensor_data(&self, sensor_id: &str) -> ComponentResult<Option<SensorData>> {
         let sensors = self.sensors.read().await;
         if let Some(sensor) = sensors.get(sensor_id) {
-            Ok(sensor.latest_data.clone())
+            Ok(sensor.latest_data.get())
+        } else {
+            Err(ComponentError::new(format!(
+                "Sensor {} not found",
+                sensor_id
+            )))
+        }
+    }
+
+    /// Get the number of times a sensor has been sampled
+    pub async fn get_sample_count(&self, sensor_id: &str) -> ComponentResult<u64> {
+        let sensors = self.sensors.read().await;
+        if let Some(sensor) = sensors.get(sensor_id) {
+            Ok(sensor.samples)
         } else {
             Err(ComponentError::new(format!(
                 "Sensor {} not found",
@@ -387,7 +734,39 @@ impl SimulationEngine {
     ) -> ComponentResult<Option<ActuatorCommand>> {
         let actuators = self.actuators.read().await;
         if let Some(actuator) = actuators.get(actuator_id) {
-            Ok(actuator.last_command.clone())
+            Ok(actuator.last_command.get())
+        } else {
+            Err(ComponentError::new(format!(
+                "Actuator {} not found",
+                actuator_id
+            )))
+        }
+    }
+
+    /// Subscribe to data injected into a sensor
+    pub async fn watch_sensor(
+        &self,
+        sensor_id: &str,
+    ) -> ComponentResult<watch::Receiver<Option<SensorData>>> {
+        let sensors = self.sensors.read().await;
+        if let Some(sensor) = sensors.get(sensor_id) {
+            Ok(sensor.latest_data.subscribe())
+        } else {
+            Err(ComponentError::new(format!(
+                "Sensor {} not found",
+                sensor_id
+            )))
+        }
+    }
+
+    /// Subscribe to commands sent to an actuator
+    pub async fn watch_actuator(
+        &self,
+        actuator_id: &str,
+    ) -> ComponentResult<watch::Receiver<Option<ActuatorCommand>>> {
+        let actuators = self.actuators.read().await;
+        if let Some(actuator) = actuators.get(actuator_id) {
+            Ok(actuator.last_command.subscribe())
         } else {
             Err(ComponentError::new(format!(
                 "Actuator {} not found",
@@ -445,6 +824,75 @@ impl SimulationEngine {
 mod tests {
     use super::*;
 
+    fn stats(id: &str, iterations: u64, errors: u64) -> ComponentStats {
+        ComponentStats {
+            id: id.to_string(),
+            name: id.to_string(),
+            iterations,
+            errors,
+            last_update: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    fn snapshot(sequence: u64, components: Vec<ComponentStats>) -> TelemetrySnapshot {
+        TelemetrySnapshot {
+            health: None,
+            component_stats: components.into_iter().map(|c| (c.id.clone(), c)).collect(),
+            sequence,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_diff_reports_changes_and_new_components() {
+        let previous = snapshot(1, vec![stats("temp-001", 5, 0), stats("motor-001", 5, 0)]);
+        let current = snapshot(
+            2,
+            vec![
+                stats("temp-001", 5, 1),
+                stats("motor-001", 5, 0),
+                stats("baro-001", 0, 0),
+            ],
+        );
+
+        let diff = current.diff(&previous);
+        assert_eq!(diff.changed, vec!["temp-001".to_string()]);
+        assert_eq!(diff.added, vec!["baro-001".to_string()]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.health_transition, None);
+        assert!(current.diff(&current).is_empty());
+    }
+
+    #[test]
+    fn test_sensor_data_converts_to_telemetry_with_units() {
+        let converted: telemetry::SensorData = SensorData::temperature(21.5).into();
+        match converted {
+            telemetry::SensorData::Temperature { value, ref unit } => {
+                assert_eq!(value, 21.5);
+                assert_eq!(unit, "°C");
+            }
+            ref other => panic!("unexpected variant {:?}", other),
+        }
+        assert!(matches!(
+            SensorData::try_from(converted),
+            Ok(SensorData::Temperature(c)) if c == 21.5
+        ));
+
+        let gps: telemetry::SensorData = SensorData::gps_position(37.7749, -122.4194, 50.0).into();
+        assert!(matches!(
+            SensorData::try_from(gps),
+            Ok(SensorData::GpsPosition(lat, lon, alt))
+                if lat == 37.7749 && lon == -122.4194 && alt == 50.0
+        ));
+
+        // Strings survive as a labelled digital reading but cannot come back
+        let text: telemetry::SensorData = SensorData::string("armed".to_string()).into();
+        assert!(matches!(
+            SensorData::try_from(text),
+            Err(telemetry::SensorData::Digital { state: true, ref label }) if label == "armed"
+        ));
+    }
+
     #[tokio::test]
     async fn test_engine_creation() {
         let config = SimulationConfig::default();
@@ -517,4 +965,128 @@ mod tests {
         let sensors = engine.list_sensors().await.unwrap();
         assert_eq!(sensors.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_multi_rate_sampling() {
+        let config = SimulationConfig {
+            control_loop_hz: 10,
+            ..Default::default()
+        };
+        let engine = SimulationEngine::new(config).await.unwrap();
+        engine
+            .register_sensor_with_rate("gps-001", "GpsSensor", 1)
+            .await
+            .unwrap();
+        engine
+            .register_sensor_with_rate("imu-001", "ImuSensor", 10)
+            .await
+            .unwrap();
+
+        for _ in 0..10 {
+            engine.execute_iteration().await.unwrap();
+        }
+
+        assert_eq!(engine.get_sample_count("imu-001").await.unwrap(), 10);
+        assert_eq!(engine.get_sample_count("gps-001").await.unwrap(), 1);
+
+        let telemetry = engine.collect_telemetry().await.unwrap();
+        assert_eq!(telemetry.component_stats["gps-001"].iterations, 1);
+    }
+
+    #[tokio::test]
+    async fn test_watch_actuator_observes_command() {
+        let engine = SimulationEngine::new(SimulationConfig::default())
+            .await
+            .unwrap();
+        engine
+            .register_actuator("motor-001", "MotorActuator")
+            .await
+            .unwrap();
+
+        let mut watcher = engine.watch_actuator("motor-001").await.unwrap();
+        assert!(watcher.borrow().is_none());
+
+        engine
+            .send_actuator_command("motor-001", ActuatorCommand::MotorSpeed(0.75))
+            .await
+            .unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), watcher.changed())
+            .await
+            .expect("watcher was not notified")
+            .unwrap();
+        assert!(matches!(
+            *watcher.borrow(),
+            Some(ActuatorCommand::MotorSpeed(speed)) if speed == 0.75
+        ));
+
+        assert!(engine.watch_actuator("nonexistent").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_flags_incomplete_setup() {
+        let config = SimulationConfig {
+            control_loop_hz: 10,
+            ..Default::default()
+        };
+        let engine = SimulationEngine::new(config).await.unwrap();
+        engine
+            .register_sensor("temp-001", "TemperatureSensor")
+            .await
+            .unwrap();
+        engine
+            .register_sensor_with_rate("imu-001", "ImuSensor", 100)
+            .await
+            .unwrap();
+        engine
+            .inject_sensor_data("imu-001", SensorData::acceleration(0.0, 0.0, 9.8))
+            .await
+            .unwrap();
+        engine
+            .register_actuator("motor-001", "MotorActuator")
+            .await
+            .unwrap();
+
+        let report = engine.dry_run().await.unwrap();
+        assert_eq!(
+            report.issues,
+            vec![
+                ValidationIssue::SensorNeverInjected("temp-001".to_string()),
+                ValidationIssue::SensorRateAboveLoop {
+                    sensor_id: "imu-001".to_string(),
+                    hz: 100,
+                },
+                ValidationIssue::ActuatorWithoutCommand("motor-001".to_string()),
+            ]
+        );
+        assert_eq!(engine.get_iteration_count().await, 0);
+
+        let empty = SimulationEngine::new(SimulationConfig::default())
+            .await
+            .unwrap();
+        let report = empty.dry_run().await.unwrap();
+        assert_eq!(
+            report.issues,
+            vec![ValidationIssue::NoSensors, ValidationIssue::NoActuators]
+        );
+
+        let invalid = SimulationEngine::new(SimulationConfig {
+            control_loop_hz: 0,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert!(invalid.dry_run().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_zero_sampling_rate_rejected() {
+        let engine = SimulationEngine::new(SimulationConfig::default())
+            .await
+            .unwrap();
+        let result = engine
+            .register_sensor_with_rate("gps-001", "GpsSensor", 0)
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -1,6 +1,12 @@
 //! Component trait definitions for standardized lifecycle management
 
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::time::Duration;
+use telemetry::{
+    CircuitBreaker, CircuitState, DiagnosticEntry, DiagnosticLevel, DiagnosticsReport, HealthStatus,
+};
+use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 
 /// Error type for component operations
@@ -27,6 +33,9 @@ impl std::error::Error for ComponentError {}
 
 pub type ComponentResult<T> = Result<T, ComponentError>;
 
+/// Unique identifier of a component, as returned by [`Component::id`]
+pub type ComponentId = String;
+
 /// Trait for standardizing component lifecycle and behavior
 ///
 /// Sensors, actuators, and other components should implement this trait
@@ -70,56 +79,680 @@ pub trait Component: Send + Sync {
     fn configure(&mut self, _config: &str) -> ComponentResult<()> {
         Ok(())
     }
+
+    /// Optional: Execution priority within a `ComponentManager`
+    ///
+    /// Higher priorities are initialized and run first and shut down last.
+    /// Default implementation returns 0
+    fn priority(&self) -> i32 {
+        0
+    }
+}
+
+/// Default number of consecutive health-check failures before a component's breaker opens
+const DEFAULT_HEALTH_FAILURE_THRESHOLD: u32 = 3;
+/// Default time an open breaker waits before health checks are retried
+const DEFAULT_HEALTH_COOLDOWN_SECS: u64 = 30;
+
+/// Lifecycle state of a component, as driven by a `ComponentManager`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentState {
+    /// Registered but not yet initialized
+    Registered,
+    /// `init` completed successfully
+    Initialized,
+    /// `run` has been started
+    Running,
+    /// `run` returned successfully
+    Completed,
+    /// `shutdown` completed successfully
+    ShutDown,
+    /// A lifecycle method returned an error
+    Failed,
+}
+
+/// A registered component together with its health-check circuit breaker
+struct ManagedComponent {
+    component: Box<dyn Component>,
+    breaker: CircuitBreaker,
+    /// Ids of components that must initialize before this one
+    deps: Vec<ComponentId>,
+    state: ComponentState,
+    /// Number of times the manager restarted this component
+    restarts: u32,
+}
+
+impl ManagedComponent {
+    /// Record the outcome of a lifecycle call, moving to `Failed` on error
+    fn track(
+        &mut self,
+        result: ComponentResult<()>,
+        success: ComponentState,
+    ) -> ComponentResult<()> {
+        self.state = match result {
+            Ok(()) => success,
+            Err(_) => ComponentState::Failed,
+        };
+        result
+    }
 }
 
 /// A manager for handling multiple components
+///
+/// Each component's health checks go through its own `CircuitBreaker`: after
+/// repeated failures the manager stops invoking that component's
+/// `health_check` and reports it `Critical` until the cooldown elapses.
 pub struct ComponentManager {
-    components: Vec<Box<dyn Component>>,
+    components: Vec<ManagedComponent>,
+    health_failure_threshold: u32,
+    health_cooldown_secs: u64,
+    run_grace: Option<Duration>,
+    abandoned: Vec<ComponentId>,
 }
 
 impl ComponentManager {
     pub fn new() -> Self {
         Self {
             components: Vec::new(),
+            health_failure_threshold: DEFAULT_HEALTH_FAILURE_THRESHOLD,
+            health_cooldown_secs: DEFAULT_HEALTH_COOLDOWN_SECS,
+            run_grace: None,
+            abandoned: Vec::new(),
         }
     }
 
+    /// Abandon a component's `run` if it has not returned `grace` after shutdown is requested
+    pub fn with_run_grace(mut self, grace: Duration) -> Self {
+        self.run_grace = Some(grace);
+        self
+    }
+
+    /// Ids of components whose `run` was abandoned by the last `run_all`
+    pub fn abandoned_components(&self) -> &[ComponentId] {
+        &self.abandoned
+    }
+
+    /// Configure the health-check circuit breaker used for components registered afterwards
+    pub fn with_health_breaker(mut self, failure_threshold: u32, cooldown_secs: u64) -> Self {
+        self.health_failure_threshold = failure_threshold;
+        self.health_cooldown_secs = cooldown_secs;
+        self
+    }
+
+    /// Register a component, keeping components ordered by descending priority
+    ///
+    /// Components of equal priority keep their registration order.
     pub fn register(&mut self, component: Box<dyn Component>) {
-        self.components.push(component);
+        self.register_with_deps(component, &[]);
+    }
+
+    /// Register a component, rejecting it if its id is already registered
+    pub fn try_register(&mut self, component: Box<dyn Component>) -> ComponentResult<()> {
+        if self.contains(component.id()) {
+            return Err(ComponentError::new(format!(
+                "Component {} is already registered",
+                component.id()
+            )));
+        }
+        self.register(component);
+        Ok(())
+    }
+
+    /// Check whether a component with the given id is registered
+    pub fn contains(&self, id: &str) -> bool {
+        self.components
+            .iter()
+            .any(|managed| managed.component.id() == id)
+    }
+
+    /// Remove a component and hand it back, e.g. to hot-swap a sensor
+    ///
+    /// A component that is initialized or running is shut down first; a
+    /// failing `shutdown` is logged and the component is still removed.
+    /// Components that declared a dependency on it will fail `init_all`
+    /// until a replacement with the same id is registered.
+    pub async fn deregister(&mut self, id: &str) -> Option<Box<dyn Component>> {
+        let position = self
+            .components
+            .iter()
+            .position(|managed| managed.component.id() == id)?;
+        let mut managed = self.components.remove(position);
+
+        if matches!(
+            managed.state,
+            ComponentState::Initialized | ComponentState::Running | ComponentState::Completed
+        ) {
+            eprintln!("Shutting down component: {}", managed.component.name());
+            if let Err(e) = managed.component.shutdown().await {
+                eprintln!(
+                    "Warning: shutdown of {} during deregister failed: {}",
+                    id, e
+                );
+            }
+        }
+        Some(managed.component)
     }
 
+    /// Register a component that must not initialize before `deps`
+    ///
+    /// `deps` are component ids; they may be registered later, but must be
+    /// registered by the time `init_all` runs.
+    pub fn register_with_deps(&mut self, component: Box<dyn Component>, deps: &[&str]) {
+        let priority = component.priority();
+        let position = self
+            .components
+            .iter()
+            .position(|managed| managed.component.priority() < priority)
+            .unwrap_or(self.components.len());
+        self.components.insert(
+            position,
+            ManagedComponent {
+                component,
+                breaker: CircuitBreaker::new(
+                    self.health_failure_threshold,
+                    self.health_cooldown_secs,
+                ),
+                deps: deps.iter().map(|dep| dep.to_string()).collect(),
+                state: ComponentState::Registered,
+                restarts: 0,
+            },
+        );
+    }
+
+    /// Reorder components so every component follows its dependencies
+    ///
+    /// Among components whose dependencies are satisfied, priority order is
+    /// kept, so managers without declared dependencies are left unchanged.
+    fn sort_by_dependencies(&mut self) -> ComponentResult<()> {
+        let index_of: HashMap<String, usize> = self
+            .components
+            .iter()
+            .enumerate()
+            .map(|(index, managed)| (managed.component.id().to_string(), index))
+            .collect();
+        let mut deps = Vec::with_capacity(self.components.len());
+        for managed in &self.components {
+            let mut indices = Vec::with_capacity(managed.deps.len());
+            for dep in &managed.deps {
+                let Some(&index) = index_of.get(dep) else {
+                    return Err(ComponentError::new(format!(
+                        "{} depends on unregistered component {}",
+                        managed.component.id(),
+                        dep
+                    )));
+                };
+                indices.push(index);
+            }
+            deps.push(indices);
+        }
+
+        let mut placed = vec![false; deps.len()];
+        let mut order = Vec::with_capacity(deps.len());
+        while order.len() < deps.len() {
+            let ready = (0..deps.len()).find(|&i| !placed[i] && deps[i].iter().all(|&d| placed[d]));
+            let Some(next) = ready else {
+                return Err(self.describe_cycle(&deps, &placed));
+            };
+            placed[next] = true;
+            order.push(next);
+        }
+
+        let mut slots: Vec<Option<ManagedComponent>> = std::mem::take(&mut self.components)
+            .into_iter()
+            .map(Some)
+            .collect();
+        self.components = order
+            .into_iter()
+            .filter_map(|index| slots[index].take())
+            .collect();
+        Ok(())
+    }
+
+    /// Build an error naming a dependency cycle among unplaced components
+    fn describe_cycle(&self, deps: &[Vec<usize>], placed: &[bool]) -> ComponentError {
+        // Every unplaced component has an unplaced dependency, so following
+        // them must eventually revisit a component
+        let mut path = Vec::new();
+        let mut current = placed.iter().position(|done| !done).unwrap_or(0);
+        while !path.contains(&current) {
+            path.push(current);
+            current = deps[current]
+                .iter()
+                .copied()
+                .find(|&dep| !placed[dep])
+                .unwrap_or(current);
+        }
+        let start = path.iter().position(|&index| index == current).unwrap_or(0);
+        let mut ids: Vec<&str> = path[start..]
+            .iter()
+            .map(|&index| self.components[index].component.id())
+            .collect();
+        ids.push(self.components[current].component.id());
+        ComponentError::new(format!("Dependency cycle: {}", ids.join(" -> ")))
+    }
+
+    /// Dependency depth of each component: 0 without dependencies, otherwise
+    /// one more than its deepest dependency
+    ///
+    /// Expects `sort_by_dependencies` to have run, so every dependency
+    /// precedes its dependents.
+    fn dependency_levels(&self) -> Vec<usize> {
+        let mut level_of: HashMap<&str, usize> = HashMap::new();
+        self.components
+            .iter()
+            .map(|managed| {
+                let level = managed
+                    .deps
+                    .iter()
+                    .filter_map(|dep| level_of.get(dep.as_str()))
+                    .map(|level| level + 1)
+                    .max()
+                    .unwrap_or(0);
+                level_of.insert(managed.component.id(), level);
+                level
+            })
+            .collect()
+    }
+
+    /// Initialize components one at a time, after their dependencies
+    ///
+    /// Components are first reordered so each follows the components it was
+    /// registered as depending on; the new order also applies to `run_all`
+    /// and (reversed) to `shutdown_all`. Returns an error without
+    /// initializing anything if the dependencies contain a cycle or name an
+    /// unregistered component.
     pub async fn init_all(&mut self) -> ComponentResult<()> {
-        for component in &mut self.components {
-            eprintln!("Initializing component: {}", component.name());
-            component.init().await?;
+        self.sort_by_dependencies()?;
+        for managed in &mut self.components {
+            eprintln!("Initializing component: {}", managed.component.name());
+            let result = managed.component.init().await;
+            managed.track(result, ComponentState::Initialized)?;
         }
         Ok(())
     }
 
+    /// Initialize components concurrently, bounding each `init` by `timeout`
+    ///
+    /// Components are initialized in dependency levels: every component
+    /// whose dependencies are initialized starts at the same time, so
+    /// startup takes as long as the slowest chain of dependencies rather than
+    /// the sum of all components. Dependency errors are reported as in
+    /// `init_all`. If any `init` fails or times out, no further level is
+    /// started, the components that did initialize are shut down (in reverse
+    /// order) and the first error in init order is returned, naming the
+    /// offending component.
+    pub async fn init_all_concurrent(&mut self, timeout: Duration) -> ComponentResult<()> {
+        self.init_in_levels(Some(timeout)).await
+    }
+
+    /// Initialize every component concurrently, then run them concurrently
+    ///
+    /// Acts as a readiness barrier: no component's `run` starts until every
+    /// `init` has completed, so a fast component cannot start running before
+    /// a slower dependency is initialized. Initialization follows
+    /// `init_all_concurrent` without a timeout: if any `init` fails, the
+    /// components that did initialize are shut down, nothing runs and the
+    /// first error is returned. Running follows `run_all_concurrent`.
+    pub async fn run_after_init(&mut self, shutdown: CancellationToken) -> ComponentResult<()> {
+        self.init_in_levels(None).await?;
+        self.run_all_concurrent(shutdown).await
+    }
+
+    /// Initialize each dependency level concurrently, cleaning up on failure
+    async fn init_in_levels(&mut self, timeout: Option<Duration>) -> ComponentResult<()> {
+        self.sort_by_dependencies()?;
+        let levels = self.dependency_levels();
+        let depth = levels.iter().max().map_or(0, |deepest| deepest + 1);
+
+        let mut results: Vec<Option<ComponentResult<()>>> = vec![None; levels.len()];
+        for level in 0..depth {
+            let inits = self
+                .components
+                .iter_mut()
+                .zip(&levels)
+                .enumerate()
+                .filter(|(_, (_, &l))| l == level)
+                .map(|(index, (managed, _))| async move {
+                    (index, Self::init_component(managed, timeout).await)
+                });
+            for (index, result) in futures::future::join_all(inits).await {
+                results[index] = Some(result);
+            }
+            if results.iter().flatten().any(|result| result.is_err()) {
+                break;
+            }
+        }
+
+        let Some(first_error) = results
+            .iter()
+            .flatten()
+            .find_map(|result| result.as_ref().err())
+            .cloned()
+        else {
+            return Ok(());
+        };
+        for (managed, result) in self.components.iter_mut().zip(&results).rev() {
+            if let Some(Ok(())) = result {
+                eprintln!("Shutting down component: {}", managed.component.name());
+                let result = managed.component.shutdown().await;
+                if let Err(e) = managed.track(result, ComponentState::ShutDown) {
+                    eprintln!(
+                        "Warning: cleanup shutdown of {} failed: {}",
+                        managed.component.id(),
+                        e
+                    );
+                }
+            }
+        }
+        Err(first_error)
+    }
+
+    /// Initialize one component, bounded by `timeout` if given
+    async fn init_component(
+        managed: &mut ManagedComponent,
+        timeout: Option<Duration>,
+    ) -> ComponentResult<()> {
+        let id = managed.component.id().to_string();
+        eprintln!("Initializing component: {}", managed.component.name());
+        let init = managed.component.init();
+        let failed =
+            |e: ComponentError| ComponentError::new(format!("{} init failed: {}", id, e.message));
+        let result = match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, init).await {
+                Ok(result) => result.map_err(failed),
+                Err(_) => Err(ComponentError::new(format!(
+                    "{} init timed out after {:?}",
+                    id, timeout
+                ))),
+            },
+            None => init.await.map_err(failed),
+        };
+        managed.track(result, ComponentState::Initialized)
+    }
+
     /// Run all components, passing each a clone of the provided `CancellationToken`.
+    ///
+    /// Components run sequentially in priority order: each `run` must return
+    /// before the next one starts. Use `run_all_concurrent` to run them at
+    /// the same time.
+    ///
+    /// With a run grace configured, a component that ignores cancellation and
+    /// is still running `grace` after `shutdown` is cancelled has its `run`
+    /// future dropped; it is reported and recorded in `abandoned_components`.
     pub async fn run_all(&mut self, shutdown: CancellationToken) -> ComponentResult<()> {
-        for component in &mut self.components {
-            eprintln!("Running component: {}", component.name());
-            component.run(shutdown.clone()).await?;
+        self.abandoned.clear();
+        for managed in &mut self.components {
+            eprintln!("Running component: {}", managed.component.name());
+            managed.state = ComponentState::Running;
+            let Some(grace) = self.run_grace else {
+                let result = managed.component.run(shutdown.clone()).await;
+                managed.track(result, ComponentState::Completed)?;
+                continue;
+            };
+
+            let deadline = async {
+                shutdown.cancelled().await;
+                tokio::time::sleep(grace).await;
+            };
+            tokio::select! {
+                result = managed.component.run(shutdown.clone()) => {
+                    managed.track(result, ComponentState::Completed)?
+                }
+                _ = deadline => {
+                    let id = managed.component.id().to_string();
+                    eprintln!(
+                        "Warning: component {} did not stop within {:?} of shutdown, abandoning it",
+                        id, grace
+                    );
+                    self.abandoned.push(id);
+                }
+            }
         }
         Ok(())
     }
 
+    /// Run all components concurrently, each on its own task
+    ///
+    /// Unlike `run_all`, every component's `run` starts immediately. Each
+    /// receives a child of `shutdown`; the first component to fail cancels
+    /// it so the others stop, and that error is returned once every task has
+    /// finished. `shutdown` itself is never cancelled by this method.
+    ///
+    /// With a run grace configured, tasks still running `grace` after
+    /// cancellation are aborted and recorded in `abandoned_components`. An
+    /// abandoned or panicked component's task owned it, so it is removed
+    /// from the manager.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is not cancel safe. While it runs, every component is
+    /// owned by its task; if the returned future is dropped before it
+    /// completes (e.g. by `tokio::time::timeout` or a losing `select!`
+    /// branch), the tasks are aborted and the components are dropped with
+    /// them, leaving the manager empty. Cancel `shutdown` instead to stop
+    /// the components and get them back.
+    pub async fn run_all_concurrent(&mut self, shutdown: CancellationToken) -> ComponentResult<()> {
+        self.abandoned.clear();
+        let token = shutdown.child_token();
+        let components = std::mem::take(&mut self.components);
+        let ids: Vec<ComponentId> = components
+            .iter()
+            .map(|managed| managed.component.id().to_string())
+            .collect();
+        let mut slots: Vec<Option<ManagedComponent>> = components.iter().map(|_| None).collect();
+
+        let mut tasks = JoinSet::new();
+        let mut task_index = HashMap::new();
+        for (index, mut managed) in components.into_iter().enumerate() {
+            eprintln!("Running component: {}", managed.component.name());
+            let token = token.clone();
+            managed.state = ComponentState::Running;
+            let handle = tasks.spawn(async move {
+                let result = managed.component.run(token).await;
+                let result = managed.track(result, ComponentState::Completed);
+                (index, managed, result)
+            });
+            task_index.insert(handle.id(), index);
+        }
+
+        let grace = self.run_grace;
+        let deadline = async {
+            token.cancelled().await;
+            match grace {
+                Some(grace) => tokio::time::sleep(grace).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::pin!(deadline);
+
+        let mut first_error = None;
+        let mut aborted = false;
+        loop {
+            tokio::select! {
+                joined = tasks.join_next() => {
+                    let error = match joined {
+                        None => break,
+                        Some(Ok((index, managed, result))) => {
+                            slots[index] = Some(managed);
+                            result.err()
+                        }
+                        Some(Err(e)) if e.is_cancelled() => None,
+                        Some(Err(e)) => Some(ComponentError::new(format!(
+                            "{} run task panicked: {}",
+                            ids[task_index[&e.id()]],
+                            e
+                        ))),
+                    };
+                    if let Some(e) = error {
+                        token.cancel();
+                        first_error.get_or_insert(e);
+                    }
+                }
+                _ = &mut deadline, if !aborted => {
+                    aborted = true;
+                    tasks.abort_all();
+                }
+            }
+        }
+
+        if let (true, Some(grace)) = (aborted, grace) {
+            for (id, slot) in ids.into_iter().zip(&slots) {
+                if slot.is_none() {
+                    eprintln!(
+                        "Warning: component {} did not stop within {:?} of shutdown, abandoning it",
+                        id, grace
+                    );
+                    self.abandoned.push(id);
+                }
+            }
+        }
+        self.components = slots.into_iter().flatten().collect();
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
     pub async fn shutdown_all(&mut self) -> ComponentResult<()> {
-        // Shutdown in reverse order
-        for component in self.components.iter_mut().rev() {
-            eprintln!("Shutting down component: {}", component.name());
-            component.shutdown().await?;
+        // Shutdown in reverse order, so high-priority components stop last
+        for managed in self.components.iter_mut().rev() {
+            eprintln!("Shutting down component: {}", managed.component.name());
+            let result = managed.component.shutdown().await;
+            managed.track(result, ComponentState::ShutDown)?;
         }
         Ok(())
     }
 
-    pub async fn health_check_all(&self) -> ComponentResult<()> {
-        for component in &self.components {
-            component.health_check().await?;
+    /// Restart a single component by calling `shutdown` and then `init`
+    ///
+    /// Leaves the rest of the manager untouched, so a component that lost
+    /// its connection (e.g. a GPS without satellite lock) can recover in the
+    /// field. A failing `shutdown` is logged and `init` is still attempted.
+    /// On success the component is `Initialized` with a fresh health-check
+    /// breaker; the caller is responsible for running it again. Every
+    /// attempt is counted in `restart_count`.
+    ///
+    /// # Returns
+    /// An error if no component has the given id, or the `init` error
+    pub async fn restart(&mut self, id: &str) -> ComponentResult<()> {
+        let breaker = CircuitBreaker::new(self.health_failure_threshold, self.health_cooldown_secs);
+        let managed = self
+            .components
+            .iter_mut()
+            .find(|managed| managed.component.id() == id)
+            .ok_or_else(|| ComponentError::new(format!("Component {} not found", id)))?;
+
+        managed.restarts += 1;
+        eprintln!(
+            "Restarting component: {} (restart {})",
+            managed.component.name(),
+            managed.restarts
+        );
+        let result = managed.component.shutdown().await;
+        if let Err(e) = managed.track(result, ComponentState::ShutDown) {
+            eprintln!("Warning: shutdown of {} during restart failed: {}", id, e);
         }
+
+        let result = managed.component.init().await;
+        managed.track(result, ComponentState::Initialized)?;
+        managed.breaker = breaker;
         Ok(())
     }
+
+    /// Get the number of times the component with the given id was restarted
+    pub fn restart_count(&self, id: &str) -> Option<u32> {
+        self.components
+            .iter()
+            .find(|managed| managed.component.id() == id)
+            .map(|managed| managed.restarts)
+    }
+
+    /// Get the lifecycle state of the component with the given id
+    pub fn state_of(&self, id: &str) -> Option<ComponentState> {
+        self.components
+            .iter()
+            .find(|managed| managed.component.id() == id)
+            .map(|managed| managed.state)
+    }
+
+    /// Check the health of every component
+    ///
+    /// All components are checked, even after a failure, and every failing
+    /// component is reported together with its id. Components whose breaker
+    /// is open are reported as failing without invoking their health check.
+    pub async fn health_check_all(&self) -> Result<(), Vec<(ComponentId, ComponentError)>> {
+        let mut failures = Vec::new();
+        for managed in &self.components {
+            if let Err(e) = Self::check_component(managed).await {
+                failures.push((managed.component.id().to_string(), e));
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Check every component and report each result as a diagnostic entry
+    ///
+    /// Unlike `health_check_all`, healthy components are included too, so
+    /// the report can be rendered as a full health grid: one `Info` entry
+    /// per healthy component and one `Error` entry per failing one. Each
+    /// entry's `component_id` is the component id and its context carries
+    /// the component name under `"name"`.
+    pub async fn health_report(&self) -> DiagnosticsReport {
+        let mut report = DiagnosticsReport::new();
+        for managed in &self.components {
+            let id = managed.component.id().to_string();
+            let name = managed.component.name().to_string();
+            let entry = match Self::check_component(managed).await {
+                Ok(()) => {
+                    DiagnosticEntry::new(DiagnosticLevel::Info, id, format!("{} healthy", name))
+                }
+                Err(e) => DiagnosticEntry::new(DiagnosticLevel::Error, id, e.message),
+            };
+            report.add_entry(entry.with_context("name".to_string(), name));
+        }
+        report
+    }
+
+    /// Run one component's health check through its circuit breaker
+    async fn check_component(managed: &ManagedComponent) -> ComponentResult<()> {
+        managed.breaker.try_half_open().await;
+        if managed.breaker.state().await == CircuitState::Open {
+            return Err(ComponentError::new(format!(
+                "{} health checks suspended after repeated failures",
+                managed.component.id()
+            )));
+        }
+
+        let result = managed.component.health_check().await;
+        match result {
+            Ok(()) => managed.breaker.record_success().await,
+            Err(_) => managed.breaker.record_failure().await,
+        }
+        result
+    }
+
+    /// Get the health status of each component as tracked by its breaker
+    ///
+    /// An open breaker is `Critical`, a half-open one (cooldown elapsed,
+    /// recovering) is `Degraded`, and a closed one is `Healthy`.
+    pub async fn health_statuses(&self) -> Vec<(ComponentId, HealthStatus)> {
+        let mut statuses = Vec::with_capacity(self.components.len());
+        for managed in &self.components {
+            let status = match managed.breaker.state().await {
+                CircuitState::Open => HealthStatus::Critical,
+                CircuitState::HalfOpen => HealthStatus::Degraded,
+                CircuitState::Closed => HealthStatus::Healthy,
+            };
+            statuses.push((managed.component.id().to_string(), status));
+        }
+        statuses
+    }
 }
 
 impl Default for ComponentManager {
@@ -127,3 +760,697 @@ impl Default for ComponentManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    struct StubComponent {
+        id: String,
+        healthy: bool,
+        health_checks: Arc<AtomicU32>,
+    }
+
+    impl StubComponent {
+        fn new(id: &str, healthy: bool) -> Self {
+            Self {
+                id: id.to_string(),
+                healthy,
+                health_checks: Arc::new(AtomicU32::new(0)),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Component for StubComponent {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            "Stub"
+        }
+
+        async fn init(&mut self) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn run(&mut self, _shutdown: CancellationToken) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> ComponentResult<()> {
+            self.health_checks.fetch_add(1, Ordering::SeqCst);
+            if self.healthy {
+                Ok(())
+            } else {
+                Err(ComponentError::new(format!("{} unhealthy", self.id)))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check_all_reports_every_failure() {
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(StubComponent::new("bad-1", false)));
+        manager.register(Box::new(StubComponent::new("good-1", true)));
+        manager.register(Box::new(StubComponent::new("bad-2", false)));
+
+        let failures = manager.health_check_all().await.unwrap_err();
+        let ids: Vec<&str> = failures.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["bad-1", "bad-2"]);
+        assert_eq!(failures[1].1.message, "bad-2 unhealthy");
+    }
+
+    #[tokio::test]
+    async fn test_health_report_includes_every_component() {
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(StubComponent::new("bad-1", false)));
+        manager.register(Box::new(StubComponent::new("good-1", true)));
+        manager.register(Box::new(StubComponent::new("bad-2", false)));
+
+        let report = manager.health_report().await;
+        assert_eq!(report.total_entries, 3);
+        assert_eq!(report.entries_by_level["Error"], 2);
+        assert_eq!(report.entries_by_level["Info"], 1);
+
+        let rows: Vec<(&str, DiagnosticLevel, &str)> = report
+            .recent_entries
+            .iter()
+            .map(|e| (e.component_id.as_str(), e.level, e.message.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("bad-1", DiagnosticLevel::Error, "bad-1 unhealthy"),
+                ("good-1", DiagnosticLevel::Info, "Stub healthy"),
+                ("bad-2", DiagnosticLevel::Error, "bad-2 unhealthy"),
+            ]
+        );
+        let context = report.recent_entries[0].context.as_ref().unwrap();
+        assert_eq!(context["name"], "Stub");
+    }
+
+    #[tokio::test]
+    async fn test_restart_reinitializes_one_component() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut manager = ComponentManager::new();
+        manager.register(prioritized("gps", &log));
+        manager.register(prioritized("imu", &log));
+        manager.init_all().await.unwrap();
+        log.lock().unwrap().clear();
+
+        manager.restart("gps").await.unwrap();
+        manager.restart("gps").await.unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["shutdown:gps", "init:gps", "shutdown:gps", "init:gps"]
+        );
+        assert_eq!(manager.state_of("gps"), Some(ComponentState::Initialized));
+        assert_eq!(manager.restart_count("gps"), Some(2));
+        assert_eq!(manager.restart_count("imu"), Some(0));
+
+        let error = manager.restart("missing").await.unwrap_err();
+        assert_eq!(error.message, "Component missing not found");
+    }
+
+    #[tokio::test]
+    async fn test_deregister_and_duplicate_ids() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut manager = ComponentManager::new();
+        manager.try_register(prioritized("gps", &log)).unwrap();
+        manager.try_register(prioritized("imu", &log)).unwrap();
+        let error = manager.try_register(prioritized("gps", &log)).unwrap_err();
+        assert_eq!(error.message, "Component gps is already registered");
+
+        // Not yet initialized: removed without a shutdown
+        let imu = manager.deregister("imu").await.unwrap();
+        assert_eq!(imu.id(), "imu");
+        assert!(!manager.contains("imu"));
+        assert!(log.lock().unwrap().is_empty());
+
+        manager.init_all().await.unwrap();
+        assert!(manager.deregister("gps").await.is_some());
+        assert_eq!(*log.lock().unwrap(), vec!["init:gps", "shutdown:gps"]);
+        assert!(manager.deregister("gps").await.is_none());
+
+        // The id is free again for a replacement
+        manager.try_register(prioritized("gps", &log)).unwrap();
+        assert!(manager.contains("gps"));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_all_ok_when_healthy() {
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(StubComponent::new("good-1", true)));
+        assert!(manager.health_check_all().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_health_breaker_stops_checking_failing_component() {
+        let mut manager = ComponentManager::new().with_health_breaker(3, 60);
+        let flaky = StubComponent::new("flaky-1", false);
+        let health_checks = flaky.health_checks.clone();
+        manager.register(Box::new(flaky));
+        manager.register(Box::new(StubComponent::new("good-1", true)));
+
+        for _ in 0..5 {
+            let failures = manager.health_check_all().await.unwrap_err();
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].0, "flaky-1");
+        }
+
+        assert_eq!(health_checks.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            manager.health_statuses().await,
+            vec![
+                ("flaky-1".to_string(), HealthStatus::Critical),
+                ("good-1".to_string(), HealthStatus::Healthy),
+            ]
+        );
+    }
+
+    /// Component whose `run` never returns, even when cancelled
+    struct StubbornComponent;
+
+    #[async_trait]
+    impl Component for StubbornComponent {
+        fn id(&self) -> &str {
+            "stubborn-1"
+        }
+
+        fn name(&self) -> &str {
+            "Stubborn"
+        }
+
+        async fn init(&mut self) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn run(&mut self, _shutdown: CancellationToken) -> ComponentResult<()> {
+            loop {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        }
+
+        async fn shutdown(&mut self) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> ComponentResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_all_abandons_component_ignoring_cancellation() {
+        let mut manager = ComponentManager::new().with_run_grace(Duration::from_millis(50));
+        manager.register(Box::new(StubbornComponent));
+        manager.register(Box::new(StubComponent::new("good-1", true)));
+
+        let shutdown = CancellationToken::new();
+        let trigger = shutdown.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            trigger.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        tokio::time::timeout(Duration::from_secs(2), manager.run_all(shutdown))
+            .await
+            .expect("run_all blocked on a stubborn component")
+            .unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(70));
+        assert_eq!(manager.abandoned_components(), ["stubborn-1".to_string()]);
+    }
+
+    /// Component recording its lifecycle calls into a shared log
+    struct PrioritizedComponent {
+        id: String,
+        priority: i32,
+        log: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Component for PrioritizedComponent {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            "Prioritized"
+        }
+
+        async fn init(&mut self) -> ComponentResult<()> {
+            self.log.lock().unwrap().push(format!("init:{}", self.id));
+            Ok(())
+        }
+
+        async fn run(&mut self, _shutdown: CancellationToken) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> ComponentResult<()> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("shutdown:{}", self.id));
+            Ok(())
+        }
+
+        async fn health_check(&self) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
+    /// Component that logs when its `run` starts and how it ends
+    struct ConcurrentComponent {
+        id: String,
+        fail: bool,
+        log: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Component for ConcurrentComponent {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            "Concurrent"
+        }
+
+        async fn init(&mut self) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn run(&mut self, shutdown: CancellationToken) -> ComponentResult<()> {
+            self.log.lock().unwrap().push(format!("start:{}", self.id));
+            if self.fail {
+                return Err(ComponentError::new(format!("{} failed", self.id)));
+            }
+            let outcome = tokio::select! {
+                _ = shutdown.cancelled() => "cancelled",
+                _ = tokio::time::sleep(Duration::from_millis(50)) => "end",
+            };
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", outcome, self.id));
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> ComponentResult<()> {
+            Ok(())
+        }
+    }
+
+    fn concurrent_manager(
+        components: &[(&str, bool)],
+    ) -> (ComponentManager, Arc<std::sync::Mutex<Vec<String>>>) {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut manager = ComponentManager::new();
+        for (id, fail) in components {
+            manager.register(Box::new(ConcurrentComponent {
+                id: id.to_string(),
+                fail: *fail,
+                log: log.clone(),
+            }));
+        }
+        (manager, log)
+    }
+
+    #[tokio::test]
+    async fn test_run_all_concurrent_overlaps_runs() {
+        let (mut manager, log) = concurrent_manager(&[("sensor", false), ("motor", false)]);
+        manager
+            .run_all_concurrent(CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(manager.health_statuses().await.len(), 2);
+        assert_eq!(manager.state_of("motor"), Some(ComponentState::Completed));
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 4);
+        assert!(
+            log[..2].iter().all(|e| e.starts_with("start:")),
+            "{:?}",
+            *log
+        );
+    }
+
+    #[tokio::test]
+    async fn test_state_of_tracks_lifecycle() {
+        let (mut manager, _log) = concurrent_manager(&[("motor", false), ("sensor", true)]);
+        assert_eq!(manager.state_of("motor"), Some(ComponentState::Registered));
+        assert_eq!(manager.state_of("missing"), None);
+
+        manager.init_all().await.unwrap();
+        assert_eq!(
+            manager.state_of("sensor"),
+            Some(ComponentState::Initialized)
+        );
+
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+        assert!(manager.run_all(shutdown).await.is_err());
+        assert_eq!(manager.state_of("motor"), Some(ComponentState::Completed));
+        assert_eq!(manager.state_of("sensor"), Some(ComponentState::Failed));
+
+        manager.shutdown_all().await.unwrap();
+        assert_eq!(manager.state_of("motor"), Some(ComponentState::ShutDown));
+    }
+
+    #[tokio::test]
+    async fn test_run_all_concurrent_cancels_rest_on_error() {
+        let (mut manager, log) = concurrent_manager(&[("motor", false), ("sensor", true)]);
+        let shutdown = CancellationToken::new();
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            manager.run_all_concurrent(shutdown.clone()),
+        )
+        .await
+        .expect("a failing component did not stop the others");
+        assert_eq!(result.unwrap_err().message, "sensor failed");
+        assert!(log.lock().unwrap().contains(&"cancelled:motor".to_string()));
+        assert!(!shutdown.is_cancelled());
+
+        // Components are handed back to the manager in priority order
+        let ids: Vec<ComponentId> = manager
+            .health_statuses()
+            .await
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec!["motor", "sensor"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_all_concurrent_dropped_future_empties_manager() {
+        let (mut manager, log) = concurrent_manager(&[("motor", false)]);
+        let shutdown = CancellationToken::new();
+
+        // Cancelling the token hands the components back
+        shutdown.cancel();
+        manager.run_all_concurrent(shutdown).await.unwrap();
+        assert!(manager.contains("motor"));
+
+        // Dropping the future mid-run loses them, as documented
+        let dropped = tokio::time::timeout(
+            Duration::from_millis(10),
+            manager.run_all_concurrent(CancellationToken::new()),
+        )
+        .await;
+        assert!(dropped.is_err());
+        assert!(!manager.contains("motor"));
+        assert!(!log.lock().unwrap().contains(&"end:motor".to_string()));
+    }
+
+    /// Component whose `init` takes `init_delay`, logging lifecycle events
+    struct SlowInitComponent {
+        id: String,
+        init_delay: Duration,
+        log: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Component for SlowInitComponent {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            "SlowInit"
+        }
+
+        async fn init(&mut self) -> ComponentResult<()> {
+            tokio::time::sleep(self.init_delay).await;
+            self.log.lock().unwrap().push(format!("init:{}", self.id));
+            Ok(())
+        }
+
+        async fn run(&mut self, _shutdown: CancellationToken) -> ComponentResult<()> {
+            self.log.lock().unwrap().push(format!("run:{}", self.id));
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> ComponentResult<()> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("shutdown:{}", self.id));
+            Ok(())
+        }
+
+        async fn health_check(&self) -> ComponentResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_after_init_waits_for_slow_init() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut manager = ComponentManager::new();
+        for (id, delay_ms) in [("fast", 0), ("slow", 50)] {
+            manager.register(Box::new(SlowInitComponent {
+                id: id.to_string(),
+                init_delay: Duration::from_millis(delay_ms),
+                log: log.clone(),
+            }));
+        }
+
+        manager
+            .run_after_init(CancellationToken::new())
+            .await
+            .unwrap();
+
+        let log = log.lock().unwrap();
+        let slow_init = log.iter().position(|e| e == "init:slow").unwrap();
+        let first_run = log.iter().position(|e| e.starts_with("run:")).unwrap();
+        assert!(slow_init < first_run, "{:?}", *log);
+        assert_eq!(log.len(), 4);
+    }
+
+    /// Component whose `init` always fails
+    struct BrokenInitComponent;
+
+    #[async_trait]
+    impl Component for BrokenInitComponent {
+        fn id(&self) -> &str {
+            "broken"
+        }
+
+        fn name(&self) -> &str {
+            "Broken"
+        }
+
+        async fn init(&mut self) -> ComponentResult<()> {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Err(ComponentError::new("no device"))
+        }
+
+        async fn run(&mut self, _shutdown: CancellationToken) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> ComponentResult<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> ComponentResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_after_init_cleans_up_on_init_failure() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(SlowInitComponent {
+            id: "gps".to_string(),
+            init_delay: Duration::from_millis(0),
+            log: log.clone(),
+        }));
+        manager.register(Box::new(BrokenInitComponent));
+        manager.register_with_deps(
+            Box::new(SlowInitComponent {
+                id: "motor".to_string(),
+                init_delay: Duration::from_millis(0),
+                log: log.clone(),
+            }),
+            &["broken"],
+        );
+
+        let error = manager
+            .run_after_init(CancellationToken::new())
+            .await
+            .unwrap_err();
+        assert_eq!(error.message, "broken init failed: no device");
+        // The dependent never initialized, nothing ran, and gps was cleaned up
+        assert_eq!(*log.lock().unwrap(), vec!["init:gps", "shutdown:gps"]);
+        assert_eq!(manager.state_of("gps"), Some(ComponentState::ShutDown));
+        assert_eq!(manager.state_of("broken"), Some(ComponentState::Failed));
+        assert_eq!(manager.state_of("motor"), Some(ComponentState::Registered));
+    }
+
+    #[tokio::test]
+    async fn test_init_all_concurrent_times_out_hung_component() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut manager = ComponentManager::new();
+        for (id, delay_ms) in [("gps", 20), ("hung-imu", 10_000), ("baro", 0)] {
+            manager.register(Box::new(SlowInitComponent {
+                id: id.to_string(),
+                init_delay: Duration::from_millis(delay_ms),
+                log: log.clone(),
+            }));
+        }
+
+        let error = tokio::time::timeout(
+            Duration::from_secs(2),
+            manager.init_all_concurrent(Duration::from_millis(100)),
+        )
+        .await
+        .expect("hung init blocked startup")
+        .unwrap_err();
+        assert!(error.message.contains("hung-imu"), "{}", error);
+
+        let log = log.lock().unwrap();
+        for id in ["gps", "baro"] {
+            assert!(log.contains(&format!("shutdown:{}", id)), "{:?}", *log);
+        }
+        assert!(!log.iter().any(|e| e.ends_with("hung-imu")), "{:?}", *log);
+    }
+
+    #[tokio::test]
+    async fn test_init_all_concurrent_respects_dependencies() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let slow = |id: &str, delay_ms| {
+            Box::new(SlowInitComponent {
+                id: id.to_string(),
+                init_delay: Duration::from_millis(delay_ms),
+                log: log.clone(),
+            })
+        };
+        let mut manager = ComponentManager::new();
+        manager.register_with_deps(slow("motor", 0), &["temp"]);
+        manager.register(slow("temp", 50));
+        manager.register(slow("status-led", 10));
+
+        manager
+            .init_all_concurrent(Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["init:status-led", "init:temp", "init:motor"]
+        );
+
+        let mut manager = ComponentManager::new();
+        manager.register_with_deps(slow("a", 0), &["b"]);
+        manager.register_with_deps(slow("b", 0), &["a"]);
+        let error = manager
+            .init_all_concurrent(Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert_eq!(error.message, "Dependency cycle: a -> b -> a");
+    }
+
+    fn prioritized(id: &str, log: &Arc<std::sync::Mutex<Vec<String>>>) -> Box<dyn Component> {
+        Box::new(PrioritizedComponent {
+            id: id.to_string(),
+            priority: 0,
+            log: log.clone(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_init_all_respects_dependencies() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut manager = ComponentManager::new();
+        manager.register_with_deps(prioritized("motor", &log), &["temp"]);
+        manager.register(prioritized("status-led", &log));
+        manager.register(prioritized("temp", &log));
+
+        manager.init_all().await.unwrap();
+        manager.shutdown_all().await.unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "init:status-led",
+                "init:temp",
+                "init:motor",
+                "shutdown:motor",
+                "shutdown:temp",
+                "shutdown:status-led",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_all_rejects_dependency_cycle() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut manager = ComponentManager::new();
+        manager.register(prioritized("gps", &log));
+        manager.register_with_deps(prioritized("a", &log), &["b"]);
+        manager.register_with_deps(prioritized("b", &log), &["a"]);
+
+        let error = manager.init_all().await.unwrap_err();
+        assert_eq!(error.message, "Dependency cycle: a -> b -> a");
+        assert!(log.lock().unwrap().is_empty());
+
+        let mut manager = ComponentManager::new();
+        manager.register_with_deps(prioritized("motor", &log), &["missing"]);
+        let error = manager.init_all().await.unwrap_err();
+        assert!(error.message.contains("missing"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_priority_orders_init_and_reverses_shutdown() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut manager = ComponentManager::new();
+        for (id, priority) in [
+            ("low", -1),
+            ("default-a", 0),
+            ("high", 10),
+            ("default-b", 0),
+        ] {
+            manager.register(Box::new(PrioritizedComponent {
+                id: id.to_string(),
+                priority,
+                log: log.clone(),
+            }));
+        }
+
+        manager.init_all().await.unwrap();
+        manager.shutdown_all().await.unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "init:high",
+                "init:default-a",
+                "init:default-b",
+                "init:low",
+                "shutdown:low",
+                "shutdown:default-b",
+                "shutdown:default-a",
+                "shutdown:high",
+            ]
+        );
+    }
+}
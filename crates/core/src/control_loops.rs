@@ -31,7 +31,7 @@ impl ControlLoopTask for ExampleControlLoop {
         self.state = (self.iteration as f32 * 0.1).sin();
 
         // Every 50 iterations (~500ms at 100Hz), print status
-        if self.iteration % 50 == 0 {
+        if self.iteration.is_multiple_of(50) {
             let elapsed = self.start_time.elapsed().as_secs_f32();
             println!(
                 "[{}] Control: iteration {}, state {:.3}, elapsed {:.2}s",
@@ -54,13 +54,31 @@ pub struct PidControlLoop {
     setpoint: f32,
     current_value: f32,
     integral: f32,
-    last_error: f32,
+    /// Measurement seen by the previous update, for the derivative term
+    last_measurement: Option<f32>,
+    /// Low-pass filtered derivative term
+    filtered_derivative: f32,
+    /// Weight of the newest sample in the derivative filter (1.0 = unfiltered)
+    derivative_filter_alpha: f32,
     kp: f32,
     ki: f32,
     kd: f32,
+    /// Feed-forward gain applied to the setpoint
+    kff: f32,
+    /// Lower bound on the accumulated integral
+    integral_min: f32,
+    /// Upper bound on the accumulated integral
+    integral_max: f32,
     iteration: u32,
 }
 
+/// Default bound on the accumulated integral, enough for the default `ki`
+/// to saturate the output on its own
+const DEFAULT_INTEGRAL_LIMIT: f32 = 10.0;
+
+/// Default derivative filter coefficient, a mild smoothing
+const DEFAULT_DERIVATIVE_FILTER_ALPHA: f32 = 0.8;
+
 impl PidControlLoop {
     pub fn new(name: impl Into<String>, setpoint: f32) -> Self {
         Self {
@@ -68,45 +86,187 @@ impl PidControlLoop {
             setpoint,
             current_value: 0.0,
             integral: 0.0,
-            last_error: 0.0,
+            last_measurement: None,
+            filtered_derivative: 0.0,
+            derivative_filter_alpha: DEFAULT_DERIVATIVE_FILTER_ALPHA,
             kp: 0.5,
             ki: 0.1,
             kd: 0.2,
+            kff: 0.0,
+            integral_min: -DEFAULT_INTEGRAL_LIMIT,
+            integral_max: DEFAULT_INTEGRAL_LIMIT,
             iteration: 0,
         }
     }
-}
 
-impl ControlLoopTask for PidControlLoop {
-    fn execute(&mut self) -> SchedulerResult<()> {
-        self.iteration += 1;
+    /// Create a controller with a feed-forward term `kff * setpoint`
+    pub fn with_feedforward(name: impl Into<String>, setpoint: f32, kff: f32) -> Self {
+        let mut pid = Self::new(name, setpoint);
+        pid.kff = kff;
+        pid
+    }
+
+    /// Set the proportional, integral and derivative gains
+    pub fn with_gains(mut self, kp: f32, ki: f32, kd: f32) -> Self {
+        self.kp = kp;
+        self.ki = ki;
+        self.kd = kd;
+        self
+    }
+
+    /// Bound the accumulated integral to `[min, max]`
+    ///
+    /// Reversed bounds are swapped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either bound is NaN.
+    pub fn with_integral_limits(mut self, min: f32, max: f32) -> Self {
+        assert!(
+            !min.is_nan() && !max.is_nan(),
+            "integral limits must not be NaN"
+        );
+        self.integral_min = min.min(max);
+        self.integral_max = min.max(max);
+        self
+    }
+
+    /// Get the target value
+    pub fn setpoint(&self) -> f32 {
+        self.setpoint
+    }
+
+    /// Change the target value while running
+    ///
+    /// The derivative term acts on the measurement, so a setpoint step does
+    /// not produce a derivative kick.
+    pub fn set_setpoint(&mut self, setpoint: f32) {
+        self.setpoint = setpoint;
+    }
+
+    /// Get the proportional, integral and derivative gains
+    pub fn gains(&self) -> (f32, f32, f32) {
+        (self.kp, self.ki, self.kd)
+    }
+
+    /// Change the gains while running, e.g. for gain scheduling
+    pub fn set_gains(&mut self, kp: f32, ki: f32, kd: f32) {
+        self.kp = kp;
+        self.ki = ki;
+        self.kd = kd;
+    }
+
+    /// Get the feed-forward gain
+    pub fn feedforward(&self) -> f32 {
+        self.kff
+    }
+
+    /// Change the feed-forward gain while running (0.0 disables it)
+    pub fn set_feedforward(&mut self, kff: f32) {
+        self.kff = kff;
+    }
+
+    /// Get the derivative filter coefficient
+    pub fn derivative_filter_alpha(&self) -> f32 {
+        self.derivative_filter_alpha
+    }
+
+    /// Set the derivative low-pass filter coefficient, clamped to [0.0, 1.0]
+    ///
+    /// Each update the derivative term becomes
+    /// `alpha * raw + (1 - alpha) * previous`. Lower values smooth more and
+    /// suppress spikes from noisy measurements; 1.0 disables the filter.
+    pub fn set_derivative_filter_alpha(&mut self, alpha: f32) {
+        self.derivative_filter_alpha = alpha.clamp(0.0, 1.0);
+    }
+
+    /// Get the simulated controlled variable driven by `execute`
+    pub fn current_value(&self) -> f32 {
+        self.current_value
+    }
+
+    /// Clear the controller's memory for a bumpless mode transition
+    ///
+    /// Zeroes the integral, the derivative history and the iteration count.
+    /// Gains, limits, setpoint and the controlled variable are kept.
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last_measurement = None;
+        self.filtered_derivative = 0.0;
+        self.iteration = 0;
+    }
+
+    /// Get the accumulated integral
+    pub fn integral(&self) -> f32 {
+        self.integral
+    }
 
+    /// Compute the control output for an externally measured value
+    ///
+    /// The output is clamped to [-1.0, 1.0]. Use this to drive a plant model
+    /// (see `plant::run_closed_loop`) instead of the built-in simulation.
+    ///
+    /// The derivative is taken on the measurement rather than the error.
+    /// Both agree while the setpoint is constant, but this way changing the
+    /// setpoint does not spike the output.
+    ///
+    /// Integral anti-windup: the integral stays within its configured limits,
+    /// and it is not accumulated while the output is saturated in the
+    /// direction the error would push it further.
+    pub fn update(&mut self, measurement: f32) -> f32 {
         // Calculate error
-        let error = self.setpoint - self.current_value;
+        let error = self.setpoint - measurement;
 
         // Proportional term
         let p = self.kp * error;
 
-        // Integral term (accumulate error)
-        self.integral += error;
-        let i = self.ki * self.integral;
+        // Derivative term (rate of change of the measurement, negated so it
+        // matches the error's rate of change under a constant setpoint)
+        let raw_d = match self.last_measurement {
+            Some(last) => -self.kd * (measurement - last),
+            None => 0.0,
+        };
+
+        // First-order low-pass filter on the derivative
+        let alpha = self.derivative_filter_alpha;
+        self.filtered_derivative = alpha * raw_d + (1.0 - alpha) * self.filtered_derivative;
+        let d = self.filtered_derivative;
 
-        // Derivative term (rate of change)
-        let d = self.kd * (error - self.last_error);
+        // Integral term (accumulate error within limits)
+        let previous_integral = self.integral;
+        self.integral = (self.integral + error).clamp(self.integral_min, self.integral_max);
+
+        // Feed-forward term, so the output need not come from the error alone
+        let ff = self.kff * self.setpoint;
 
         // Calculate control output
-        let output = p + i + d;
+        let mut output = p + self.ki * self.integral + d + ff;
+
+        // Conditional integration: undo the step if it deepens saturation
+        if output.abs() > 1.0 && output.signum() == error.signum() {
+            self.integral = previous_integral;
+            output = p + self.ki * self.integral + d + ff;
+        }
+
+        self.last_measurement = Some(measurement);
 
         // Clamp output
-        let output = output.clamp(-1.0, 1.0);
+        output.clamp(-1.0, 1.0)
+    }
+}
+
+impl ControlLoopTask for PidControlLoop {
+    fn execute(&mut self) -> SchedulerResult<()> {
+        self.iteration += 1;
+
+        let error = self.setpoint - self.current_value;
+        let output = self.update(self.current_value);
 
         // Simulate system response: move toward setpoint
         self.current_value += output * 0.01;
 
-        self.last_error = error;
-
         // Print every 100 iterations (~1s at 100Hz)
-        if self.iteration % 100 == 0 {
+        if self.iteration.is_multiple_of(100) {
             println!(
                 "[{}] PID: iteration {}, setpoint {:.2}, current {:.2}, error {:.2}, output {:.2}",
                 self.name, self.iteration, self.setpoint, self.current_value, error, output
@@ -120,3 +280,133 @@ impl ControlLoopTask for PidControlLoop {
         &self.name
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plant::{run_closed_loop, FirstOrderPlant};
+
+    #[test]
+    fn test_integral_stays_bounded_under_saturation() {
+        // Large step: the output saturates and the integral must not wind up
+        let mut pid = PidControlLoop::new("windup", 100.0);
+        for _ in 0..1000 {
+            assert_eq!(pid.update(0.0), 1.0);
+            assert!(pid.integral() <= DEFAULT_INTEGRAL_LIMIT);
+        }
+        assert_eq!(pid.integral(), 0.0);
+
+        // Pure integral control never saturates, so only the limit applies
+        let mut pid = PidControlLoop::new("bounded", 1.0)
+            .with_gains(0.0, 0.01, 0.0)
+            .with_integral_limits(-5.0, 5.0);
+        for _ in 0..1000 {
+            pid.update(0.0);
+            assert!(pid.integral() <= 5.0);
+        }
+        assert_eq!(pid.integral(), 5.0);
+    }
+
+    #[test]
+    fn test_reversed_integral_limits_are_swapped() {
+        let mut pid = PidControlLoop::new("reversed", 1.0)
+            .with_gains(0.0, 0.01, 0.0)
+            .with_integral_limits(5.0, -5.0);
+        for _ in 0..1000 {
+            pid.update(0.0);
+        }
+        assert_eq!(pid.integral(), 5.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "integral limits must not be NaN")]
+    fn test_nan_integral_limit_is_rejected() {
+        let _ = PidControlLoop::new("nan", 1.0).with_integral_limits(f32::NAN, 1.0);
+    }
+
+    #[test]
+    fn test_setpoint_change_has_no_derivative_kick() {
+        let mut pid = PidControlLoop::new("retarget", 0.5).with_gains(0.1, 0.0, 5.0);
+        pid.update(0.2);
+        assert!((pid.update(0.2) - 0.03).abs() < 1e-6);
+
+        // Only the proportional term responds to the new setpoint
+        pid.set_setpoint(0.8);
+        assert_eq!(pid.setpoint(), 0.8);
+        assert!((pid.update(0.2) - 0.06).abs() < 1e-6);
+
+        pid.set_gains(0.2, 0.0, 5.0);
+        assert_eq!(pid.gains(), (0.2, 0.0, 5.0));
+        assert!((pid.update(0.2) - 0.12).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reset_keeps_configuration() {
+        let mut pid = PidControlLoop::with_feedforward("reset", 0.5, 0.2).with_gains(0.3, 0.2, 0.1);
+        for _ in 0..50 {
+            pid.execute().unwrap();
+        }
+        assert!(pid.integral() > 0.0);
+        let value = pid.current_value();
+        assert!(value > 0.0);
+
+        pid.reset();
+        assert_eq!(pid.integral(), 0.0);
+        assert_eq!(pid.iteration, 0);
+        assert_eq!(pid.current_value(), value);
+        assert_eq!(pid.gains(), (0.3, 0.2, 0.1));
+        assert_eq!(pid.feedforward(), 0.2);
+        assert_eq!(pid.setpoint(), 0.5);
+
+        // Behaves like a fresh controller at the same measurement
+        let mut fresh =
+            PidControlLoop::with_feedforward("fresh", 0.5, 0.2).with_gains(0.3, 0.2, 0.1);
+        assert_eq!(pid.update(value), fresh.update(value));
+    }
+
+    /// Steps until a first-order plant first reaches 90% of the setpoint
+    fn rise_steps(mut pid: PidControlLoop) -> usize {
+        let mut plant = FirstOrderPlant::new(1.0, 0.5, 0.01);
+        let target = 0.9 * pid.setpoint();
+        run_closed_loop(&mut pid, &mut plant, 2000)
+            .iter()
+            .position(|&y| y >= target)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_feedforward_shortens_rise_time() {
+        let without = rise_steps(PidControlLoop::new("pid", 0.5));
+        let with = rise_steps(PidControlLoop::with_feedforward("pid-ff", 0.5, 1.0));
+        assert!(with < without, "{} vs {}", with, without);
+
+        // The combined output is still clamped
+        let mut pid = PidControlLoop::with_feedforward("pid-ff", 0.5, 10.0);
+        assert_eq!(pid.update(0.5), 1.0);
+        pid.set_feedforward(0.0);
+        assert_eq!(pid.feedforward(), 0.0);
+    }
+
+    /// Output change caused by a one-sample spike in the measurement
+    fn spike_response(alpha: f32) -> f32 {
+        let mut pid = PidControlLoop::new("spike", 0.5).with_gains(0.0, 0.0, 1.0);
+        pid.set_derivative_filter_alpha(alpha);
+        for _ in 0..10 {
+            pid.update(0.5);
+        }
+        let steady = pid.update(0.5);
+        (pid.update(0.9) - steady).abs()
+    }
+
+    #[test]
+    fn test_derivative_filter_bounds_spike() {
+        let unfiltered = spike_response(1.0);
+        let filtered = spike_response(0.2);
+        assert!((unfiltered - 0.4).abs() < 1e-6);
+        assert!((filtered - 0.2 * unfiltered).abs() < 1e-6);
+
+        let mut pid = PidControlLoop::new("clamp", 0.0);
+        pid.set_derivative_filter_alpha(1.5);
+        assert_eq!(pid.derivative_filter_alpha(), 1.0);
+    }
+}
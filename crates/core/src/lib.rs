@@ -6,21 +6,28 @@
 pub mod component;
 pub mod control_loops;
 pub mod models;
+pub mod plant;
 pub mod scheduler;
 pub mod sensors;
 
 #[cfg(feature = "mock_sensors")]
 pub mod mocks;
 
-pub use component::{Component, ComponentError, ComponentManager, ComponentResult};
+pub use component::{
+    Component, ComponentError, ComponentId, ComponentManager, ComponentResult, ComponentState,
+};
 pub use control_loops::{ExampleControlLoop, PidControlLoop};
+pub use plant::{run_closed_loop, FirstOrderPlant, Plant, SecondOrderPlant};
 pub use scheduler::{
-    ControlLoopTask, MixedPriorityRuntime, RealTimeLoop, SchedulerError, SchedulerResult,
+    AsyncControlLoopTask, ControlLoopTask, DeadlinePolicy, ExecutionHistogram, JitterStats,
+    MixedPriorityRuntime, RealTimeLoop, SchedulerError, SchedulerResult, StepController,
 };
-pub use sensors::{MotorActuator, TemperatureSensor};
+pub use sensors::{MotorActuator, MotorControl, TemperatureSensor};
 
 #[cfg(feature = "mock_sensors")]
-pub use mocks::{MockBarometerSensor, MockGpsSensor, MockImuSensor};
+pub use mocks::{
+    FaultHandle, FaultKind, MockAdaptiveSensor, MockBarometerSensor, MockGpsSensor, MockImuSensor,
+};
 
 pub fn add(left: usize, right: usize) -> usize {
     left + right
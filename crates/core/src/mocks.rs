@@ -2,11 +2,143 @@
 //!
 //! These sensors simulate realistic data patterns to enable rapid development
 //! and testing of application logic before integrating real hardware.
+//!
+//! Sensors built with `new` follow a fixed nominal pattern. Sensors built
+//! with `with_seed` add Gaussian noise from a seeded generator, so a given
+//! seed reproduces the same readings on every run.
+//!
+//! The GPS, IMU and barometer mocks also accept injected faults (see
+//! [`FaultKind`]) so error paths can be exercised deterministically. Faults
+//! live behind a shared [`FaultHandle`], so they can be injected while the
+//! sensor is owned by a `ComponentManager` and running.
 
 use crate::component::{Component, ComponentResult};
 use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use telemetry::{SensorData, SensorReading};
+use tokio::sync::mpsc::{self, error::TrySendError};
 use tokio_util::sync::CancellationToken;
 
+/// Seeded Gaussian noise generator (SplitMix64 with a Box-Muller transform)
+#[derive(Debug, Clone)]
+struct SeededNoise {
+    state: u64,
+}
+
+impl SeededNoise {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in [0, 1)
+    fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Normally distributed sample with mean 0
+    fn gaussian(&mut self, std_dev: f64) -> f64 {
+        let u1 = 1.0 - self.uniform();
+        let u2 = self.uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos() * std_dev
+    }
+}
+
+/// A failure mode that can be injected into a mock sensor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultKind {
+    /// The sensor stops producing data and its health check fails
+    LoseSignal,
+    /// Readings freeze at their last value; the health check still passes
+    StuckValue,
+    /// Readings jump outside the sensor's valid range
+    OutOfRange,
+    /// The next `init` returns an error
+    InitFailure,
+}
+
+/// Shared handle for injecting faults into a mock sensor
+///
+/// `run` holds the sensor mutably, so tests and fault-injection tools clone
+/// this handle (see `fault_handle`) to change the fault while it runs.
+#[derive(Debug, Clone, Default)]
+pub struct FaultHandle {
+    fault: Arc<Mutex<Option<FaultKind>>>,
+}
+
+impl FaultHandle {
+    /// Make the sensor misbehave until the fault is cleared
+    pub fn inject(&self, fault: FaultKind) {
+        *self.lock() = Some(fault);
+    }
+
+    /// Return the sensor to normal behavior
+    pub fn clear(&self) {
+        *self.lock() = None;
+    }
+
+    /// Get the currently injected fault, if any
+    pub fn active(&self) -> Option<FaultKind> {
+        *self.lock()
+    }
+
+    fn is(&self, fault: FaultKind) -> bool {
+        self.active() == Some(fault)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<FaultKind>> {
+        self.fault.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Add `fault_handle`, `inject_fault`, `clear_fault` and `active_fault` to
+/// mock sensors
+macro_rules! impl_fault_injection {
+    ($($sensor:ty),*) => {
+        $(
+            impl $sensor {
+                /// Get a handle for injecting faults while the sensor runs
+                pub fn fault_handle(&self) -> FaultHandle {
+                    self.fault.clone()
+                }
+
+                /// Make the sensor misbehave until the fault is cleared
+                pub fn inject_fault(&self, fault: FaultKind) {
+                    self.fault.inject(fault);
+                }
+
+                /// Return the sensor to normal behavior
+                pub fn clear_fault(&self) {
+                    self.fault.clear();
+                }
+
+                /// Get the currently injected fault, if any
+                pub fn active_fault(&self) -> Option<FaultKind> {
+                    self.fault.active()
+                }
+            }
+        )*
+    };
+}
+
+impl_fault_injection!(MockGpsSensor, MockImuSensor, MockBarometerSensor);
+
+/// Add seeded noise to `value`, or leave it unchanged without a generator
+fn add_noise(noise: &mut Option<SeededNoise>, value: f64, std_dev: f64) -> f64 {
+    match noise {
+        Some(noise) => value + noise.gaussian(std_dev),
+        None => value,
+    }
+}
+
 /// Mock GPS sensor that generates synthetic coordinates
 ///
 /// Simulates a GPS module by incrementally updating latitude/longitude
@@ -21,6 +153,10 @@ pub struct MockGpsSensor {
     satellites: u8,
     accuracy: f32,
     is_initialized: bool,
+    noise: Option<SeededNoise>,
+    /// Measurement noise on the last fix, kept out of the tracked position
+    noise_offset: (f64, f64),
+    fault: FaultHandle,
 }
 
 impl MockGpsSensor {
@@ -34,8 +170,62 @@ impl MockGpsSensor {
             satellites: 0,
             accuracy: 0.0,
             is_initialized: false,
+            noise: None,
+            noise_offset: (0.0, 0.0),
+            fault: FaultHandle::default(),
+        }
+    }
+
+    /// Create a GPS whose fixes carry reproducible Gaussian noise
+    pub fn with_seed(id: impl Into<String>, name: impl Into<String>, seed: u64) -> Self {
+        Self {
+            noise: Some(SeededNoise::new(seed)),
+            ..Self::new(id, name)
+        }
+    }
+
+    /// Latitude and longitude as reported, including measurement noise and
+    /// an injected out-of-range fault
+    fn position(&self) -> (f64, f64) {
+        if self.fault.is(FaultKind::OutOfRange) {
+            (999.0, 999.0)
+        } else {
+            let (lat_noise, lon_noise) = self.noise_offset;
+            (self.latitude + lat_noise, self.longitude + lon_noise)
         }
     }
+
+    /// Compute the fix for the given acquisition iteration
+    fn advance(&mut self, iteration: u32) {
+        match self.fault.active() {
+            // An out-of-range fault only corrupts the reported fix (see
+            // `position`), so the tracked position survives it
+            Some(FaultKind::LoseSignal | FaultKind::StuckValue | FaultKind::OutOfRange) => return,
+            Some(FaultKind::InitFailure) | None => {}
+        }
+
+        // Simulate gradual satellite acquisition
+        if iteration < 3 {
+            self.satellites = (4 + iteration as u8).min(12);
+        }
+
+        // Improve accuracy as satellites lock on
+        self.accuracy = (5.0 - (iteration as f32 * 0.5)).max(0.5);
+
+        // Simulate slow drift in position; the ~0.1m of horizontal noise is
+        // drawn fresh for each fix rather than accumulated into the track
+        self.latitude += 0.00001 * (iteration as f64 % 5.0 - 2.0);
+        self.longitude -= 0.00001 * (iteration as f64 % 3.0 - 1.5);
+        self.noise_offset = (
+            add_noise(&mut self.noise, 0.0, 0.000001),
+            add_noise(&mut self.noise, 0.0, 0.000001),
+        );
+        self.altitude = add_noise(
+            &mut self.noise,
+            100.0 + (iteration as f64 * 0.5) % 50.0,
+            0.5,
+        );
+    }
 }
 
 #[async_trait]
@@ -49,6 +239,11 @@ impl Component for MockGpsSensor {
     }
 
     async fn init(&mut self) -> ComponentResult<()> {
+        if self.fault.is(FaultKind::InitFailure) {
+            return Err(crate::component::ComponentError::new(
+                "GPS sensor init failed (injected fault)",
+            ));
+        }
         println!("[{}] Initializing GPS sensor...", self.name);
         tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
         self.is_initialized = true;
@@ -74,23 +269,12 @@ impl Component for MockGpsSensor {
                 }
                 _ = tokio::time::sleep(tokio::time::Duration::from_millis(500)) => {
                     iteration += 1;
-
-                    // Simulate gradual satellite acquisition
-                    if iteration < 3 {
-                        self.satellites = (4 + iteration as u8).min(12);
-                    }
-
-                    // Improve accuracy as satellites lock on
-                    self.accuracy = (5.0 - (iteration as f32 * 0.5)).max(0.5);
-
-                    // Simulate slow drift in position
-                    self.latitude += 0.00001 * (iteration as f64 % 5.0 - 2.0);
-                    self.longitude -= 0.00001 * (iteration as f64 % 3.0 - 1.5);
-                    self.altitude = 100.0 + (iteration as f64 * 0.5) % 50.0;
+                    self.advance(iteration);
+                    let (latitude, longitude) = self.position();
 
                     println!(
                         "[{}] Fix: Lat {:.4}°, Lon {:.4}°, Alt {:.1}m, Sats {}, Acc {:.1}m",
-                        self.name, self.latitude, self.longitude, self.altitude, self.satellites, self.accuracy
+                        self.name, latitude, longitude, self.altitude, self.satellites, self.accuracy
                     );
 
                     if iteration >= 10 {
@@ -114,11 +298,17 @@ impl Component for MockGpsSensor {
         if !self.is_initialized {
             return Err(crate::component::ComponentError::new("GPS not initialized"));
         }
-        if self.satellites < 4 {
+        if self.satellites < 4 || self.fault.is(FaultKind::LoseSignal) {
             return Err(crate::component::ComponentError::new(
                 "Insufficient satellite lock",
             ));
         }
+        let (latitude, longitude) = self.position();
+        if latitude.abs() > 90.0 || longitude.abs() > 180.0 {
+            return Err(crate::component::ComponentError::new(
+                "GPS fix out of range",
+            ));
+        }
         Ok(())
     }
 }
@@ -144,6 +334,8 @@ pub struct MockImuSensor {
     mag_z: f32,
     temperature: f32,
     is_initialized: bool,
+    noise: Option<SeededNoise>,
+    fault: FaultHandle,
 }
 
 impl MockImuSensor {
@@ -162,8 +354,49 @@ impl MockImuSensor {
             mag_z: 40.0,
             temperature: 25.0,
             is_initialized: false,
+            noise: None,
+            fault: FaultHandle::default(),
+        }
+    }
+
+    /// Create an IMU whose readings carry reproducible Gaussian noise
+    pub fn with_seed(id: impl Into<String>, name: impl Into<String>, seed: u64) -> Self {
+        Self {
+            noise: Some(SeededNoise::new(seed)),
+            ..Self::new(id, name)
         }
     }
+
+    /// Compute the readings for the given iteration
+    fn advance(&mut self, iteration: u32) {
+        match self.fault.active() {
+            Some(FaultKind::LoseSignal | FaultKind::StuckValue) => return,
+            Some(FaultKind::OutOfRange) => {
+                self.temperature = 150.0;
+                return;
+            }
+            Some(FaultKind::InitFailure) | None => {}
+        }
+
+        let t = iteration as f32;
+        let noise = &mut self.noise;
+        let noisy = |noise: &mut Option<SeededNoise>, value: f32, std_dev: f64| {
+            add_noise(noise, value as f64, std_dev) as f32
+        };
+
+        // Simulate motion: gradual rotation
+        self.gyro_x = noisy(noise, (t * 0.5).sin() * 10.0, 0.1); // ±10 deg/s
+        self.gyro_y = noisy(noise, (t * 0.3).cos() * 5.0, 0.1); // ±5 deg/s
+        self.gyro_z = noisy(noise, 0.0, 0.1);
+
+        // Simulate acceleration from motion
+        self.accel_x = noisy(noise, (t * 0.2).sin() * 2.0, 0.05); // ±2 m/s²
+        self.accel_y = noisy(noise, (t * 0.1).cos() * 1.5, 0.05); // ±1.5 m/s²
+        self.accel_z = noisy(noise, 9.81 + (t * 0.1).sin() * 0.5, 0.05);
+
+        // Simulate temperature drift
+        self.temperature = noisy(noise, 25.0 + (t * 0.05), 0.02);
+    }
 }
 
 #[async_trait]
@@ -177,6 +410,11 @@ impl Component for MockImuSensor {
     }
 
     async fn init(&mut self) -> ComponentResult<()> {
+        if self.fault.is(FaultKind::InitFailure) {
+            return Err(crate::component::ComponentError::new(
+                "IMU sensor init failed (injected fault)",
+            ));
+        }
         println!("[{}] Initializing IMU sensor...", self.name);
         tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
         self.is_initialized = true;
@@ -200,19 +438,7 @@ impl Component for MockImuSensor {
                 }
                 _ = tokio::time::sleep(tokio::time::Duration::from_millis(300)) => {
                     iteration += 1;
-
-                    // Simulate motion: gradual rotation
-                    self.gyro_x = (iteration as f32 * 0.5).sin() * 10.0; // ±10 deg/s
-                    self.gyro_y = (iteration as f32 * 0.3).cos() * 5.0;  // ±5 deg/s
-                    self.gyro_z = 0.0;
-
-                    // Simulate acceleration from motion
-                    self.accel_x = (iteration as f32 * 0.2).sin() * 2.0; // ±2 m/s²
-                    self.accel_y = (iteration as f32 * 0.1).cos() * 1.5; // ±1.5 m/s²
-                    self.accel_z = 9.81 + (iteration as f32 * 0.1).sin() * 0.5;
-
-                    // Simulate temperature drift
-                    self.temperature = 25.0 + (iteration as f32 * 0.05);
+                    self.advance(iteration);
 
                     println!(
                         "[{}] Accel: [{:6.2}, {:6.2}, {:6.2}] m/s² | Gyro: [{:6.1}, {:6.1}, {:6.1}] °/s | Temp: {:.1}°C",
@@ -243,6 +469,9 @@ impl Component for MockImuSensor {
         if !self.is_initialized {
             return Err(crate::component::ComponentError::new("IMU not initialized"));
         }
+        if self.fault.is(FaultKind::LoseSignal) {
+            return Err(crate::component::ComponentError::new("IMU signal lost"));
+        }
         // Check temperature is within operating range
         if self.temperature < -40.0 || self.temperature > 85.0 {
             return Err(crate::component::ComponentError::new(
@@ -265,6 +494,8 @@ pub struct MockBarometerSensor {
     temperature: f32, // in °C
     altitude: f32,    // in meters
     is_initialized: bool,
+    noise: Option<SeededNoise>,
+    fault: FaultHandle,
 }
 
 impl MockBarometerSensor {
@@ -276,7 +507,40 @@ impl MockBarometerSensor {
             temperature: 15.0,
             altitude: 0.0,
             is_initialized: false,
+            noise: None,
+            fault: FaultHandle::default(),
+        }
+    }
+
+    /// Create a barometer whose readings carry reproducible Gaussian noise
+    pub fn with_seed(id: impl Into<String>, name: impl Into<String>, seed: u64) -> Self {
+        Self {
+            noise: Some(SeededNoise::new(seed)),
+            ..Self::new(id, name)
+        }
+    }
+
+    /// Compute the readings for the given iteration
+    fn advance(&mut self, iteration: u32) {
+        match self.fault.active() {
+            Some(FaultKind::LoseSignal | FaultKind::StuckValue) => return,
+            Some(FaultKind::OutOfRange) => {
+                self.pressure = 2000.0;
+                return;
+            }
+            Some(FaultKind::InitFailure) | None => {}
         }
+
+        // Simulate gradual climb: altitude increases, pressure decreases
+        self.altitude = iteration as f32 * 5.0; // 5m per reading
+
+        // Pressure decreases ~12 Pa per 100m
+        let pressure = 1013.25 - (self.altitude * 0.12);
+        self.pressure = add_noise(&mut self.noise, pressure as f64, 0.05) as f32;
+
+        // Temperature decreases ~6.5°C per 1000m
+        let temperature = 15.0 - (self.altitude * 0.0065);
+        self.temperature = add_noise(&mut self.noise, temperature as f64, 0.05) as f32;
     }
 }
 
@@ -291,6 +555,11 @@ impl Component for MockBarometerSensor {
     }
 
     async fn init(&mut self) -> ComponentResult<()> {
+        if self.fault.is(FaultKind::InitFailure) {
+            return Err(crate::component::ComponentError::new(
+                "Barometer sensor init failed (injected fault)",
+            ));
+        }
         println!("[{}] Initializing Barometer sensor...", self.name);
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
         self.is_initialized = true;
@@ -316,14 +585,7 @@ impl Component for MockBarometerSensor {
                 }
                 _ = tokio::time::sleep(tokio::time::Duration::from_millis(600)) => {
                     iteration += 1;
-
-                    // Simulate gradual climb: altitude increases, pressure decreases
-                    self.altitude = iteration as f32 * 5.0; // 5m per reading
-                    // Pressure decreases ~12 Pa per 100m
-                    self.pressure = 1013.25 - (self.altitude * 0.12);
-
-                    // Temperature decreases ~6.5°C per 1000m
-                    self.temperature = 15.0 - (self.altitude * 0.0065);
+                    self.advance(iteration);
 
                     println!(
                         "[{}] Pressure: {:.2} hPa, Temp: {:.1}°C, Altitude: {:.1}m",
@@ -353,6 +615,11 @@ impl Component for MockBarometerSensor {
                 "Barometer not initialized",
             ));
         }
+        if self.fault.is(FaultKind::LoseSignal) {
+            return Err(crate::component::ComponentError::new(
+                "Barometer signal lost",
+            ));
+        }
         if self.pressure < 300.0 || self.pressure > 1100.0 {
             return Err(crate::component::ComponentError::new(
                 "Barometer pressure out of range",
@@ -361,3 +628,333 @@ impl Component for MockBarometerSensor {
         Ok(())
     }
 }
+
+/// Mock sensor that adapts its emission rate to downstream backpressure
+///
+/// Readings are pushed into a bounded channel with `try_send`. When the
+/// channel is more than three quarters full (or full) the emission interval
+/// doubles; when it is less than a quarter full the interval halves, within
+/// `[min_interval, max_interval]`. This makes backpressure from a slow
+/// transport visible at the source.
+#[derive(Debug)]
+pub struct MockAdaptiveSensor {
+    id: String,
+    name: String,
+    tx: mpsc::Sender<SensorReading>,
+    min_interval: Duration,
+    max_interval: Duration,
+    interval: Duration,
+    emitted: u64,
+    dropped: u64,
+    is_initialized: bool,
+}
+
+impl MockAdaptiveSensor {
+    /// Create a sensor emitting into `tx`, starting at its fastest rate
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        tx: mpsc::Sender<SensorReading>,
+        min_interval: Duration,
+        max_interval: Duration,
+    ) -> Self {
+        let max_interval = max_interval.max(min_interval);
+        Self {
+            id: id.into(),
+            name: name.into(),
+            tx,
+            min_interval,
+            max_interval,
+            interval: min_interval,
+            emitted: 0,
+            dropped: 0,
+            is_initialized: false,
+        }
+    }
+
+    /// Current emission interval
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Current effective emission rate in Hz
+    pub fn effective_rate_hz(&self) -> f64 {
+        1.0 / self.interval().as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Number of readings accepted by the channel
+    pub fn emitted(&self) -> u64 {
+        self.emitted
+    }
+
+    /// Number of readings dropped because the channel was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Emit one reading and adjust the interval to the channel's fill level
+    fn emit(&mut self) {
+        let reading = SensorReading::new(
+            self.id.clone(),
+            self.name.clone(),
+            SensorData::Analog {
+                value: self.emitted as f32,
+                unit: "count".to_string(),
+            },
+            self.emitted,
+        );
+        let full = match self.tx.try_send(reading) {
+            Ok(()) => {
+                self.emitted += 1;
+                false
+            }
+            Err(TrySendError::Full(_)) => {
+                self.dropped += 1;
+                true
+            }
+            Err(TrySendError::Closed(_)) => false,
+        };
+
+        let max = self.tx.max_capacity().max(1);
+        let used = max - self.tx.capacity();
+        if full || used * 4 > max * 3 {
+            self.interval = (self.interval * 2).min(self.max_interval);
+        } else if used * 4 < max {
+            self.interval = (self.interval / 2).max(self.min_interval);
+        }
+    }
+}
+
+#[async_trait]
+impl Component for MockAdaptiveSensor {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn init(&mut self) -> ComponentResult<()> {
+        self.is_initialized = true;
+        Ok(())
+    }
+
+    async fn run(&mut self, shutdown: CancellationToken) -> ComponentResult<()> {
+        if !self.is_initialized {
+            return Err(crate::component::ComponentError::new(
+                "Adaptive sensor not initialized",
+            ));
+        }
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => return Ok(()),
+                _ = tokio::time::sleep(self.interval()) => {
+                    if self.tx.is_closed() {
+                        return Ok(());
+                    }
+                    self.emit();
+                }
+            }
+        }
+    }
+
+    async fn shutdown(&mut self) -> ComponentResult<()> {
+        self.is_initialized = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> ComponentResult<()> {
+        if !self.is_initialized {
+            return Err(crate::component::ComponentError::new(
+                "Adaptive sensor not initialized",
+            ));
+        }
+        if self.tx.is_closed() {
+            return Err(crate::component::ComponentError::new(
+                "Adaptive sensor output closed",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gps_track(mut gps: MockGpsSensor) -> Vec<(f64, f64, f64)> {
+        (1..=10)
+            .map(|iteration| {
+                gps.advance(iteration);
+                let (latitude, longitude) = gps.position();
+                (latitude, longitude, gps.altitude)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_seeded_mocks_are_reproducible() {
+        let a = gps_track(MockGpsSensor::with_seed("gps-1", "GPS", 42));
+        let b = gps_track(MockGpsSensor::with_seed("gps-1", "GPS", 42));
+        let other = gps_track(MockGpsSensor::with_seed("gps-1", "GPS", 7));
+        let nominal = gps_track(MockGpsSensor::new("gps-1", "GPS"));
+        assert_eq!(a, b);
+        assert_ne!(a, other);
+        assert_ne!(a, nominal);
+        // Noise stays small relative to the nominal signal
+        for ((_, _, noisy), (_, _, clean)) in a.iter().zip(&nominal) {
+            assert!((noisy - clean).abs() < 5.0);
+        }
+
+        let mut imu_a = MockImuSensor::with_seed("imu-1", "IMU", 42);
+        let mut imu_b = MockImuSensor::with_seed("imu-1", "IMU", 42);
+        let mut baro_a = MockBarometerSensor::with_seed("baro-1", "Baro", 42);
+        let mut baro_b = MockBarometerSensor::with_seed("baro-1", "Baro", 42);
+        for iteration in 1..=5 {
+            imu_a.advance(iteration);
+            imu_b.advance(iteration);
+            baro_a.advance(iteration);
+            baro_b.advance(iteration);
+        }
+        assert_eq!(imu_a.accel_z, imu_b.accel_z);
+        assert_ne!(imu_a.accel_z, 9.81 + 0.5_f32.sin() * 0.5);
+        assert_eq!(baro_a.pressure, baro_b.pressure);
+    }
+
+    #[test]
+    fn test_gps_noise_does_not_accumulate() {
+        let mut noisy = MockGpsSensor::with_seed("gps-1", "GPS", 42);
+        let mut clean = MockGpsSensor::new("gps-1", "GPS");
+        for iteration in 1..=1000 {
+            noisy.advance(iteration);
+            clean.advance(iteration);
+        }
+
+        // The tracked position follows the nominal drift exactly; only the
+        // reported fix is perturbed, by a single sample's worth of noise
+        assert_eq!(
+            (noisy.latitude, noisy.longitude),
+            (clean.latitude, clean.longitude)
+        );
+        let (latitude, longitude) = noisy.position();
+        assert_ne!(latitude, clean.latitude);
+        assert!((latitude - clean.latitude).abs() < 0.00001);
+        assert!((longitude - clean.longitude).abs() < 0.00001);
+    }
+
+    #[tokio::test]
+    async fn test_injected_faults_drive_error_paths() {
+        let mut gps = MockGpsSensor::new("gps-1", "GPS");
+        gps.init().await.unwrap();
+        assert!(gps.health_check().await.is_ok());
+
+        gps.inject_fault(FaultKind::LoseSignal);
+        assert_eq!(gps.active_fault(), Some(FaultKind::LoseSignal));
+        let error = gps.health_check().await.unwrap_err();
+        assert_eq!(error.message, "Insufficient satellite lock");
+        gps.clear_fault();
+        assert!(gps.health_check().await.is_ok());
+
+        gps.inject_fault(FaultKind::StuckValue);
+        gps.advance(1);
+        let stuck = gps.position();
+        gps.advance(2);
+        assert_eq!(gps.position(), stuck);
+        assert!(gps.health_check().await.is_ok());
+
+        let mut imu = MockImuSensor::new("imu-1", "IMU");
+        imu.inject_fault(FaultKind::InitFailure);
+        assert!(imu.init().await.is_err());
+        imu.clear_fault();
+        imu.init().await.unwrap();
+
+        let mut baro = MockBarometerSensor::new("baro-1", "Baro");
+        baro.init().await.unwrap();
+        baro.inject_fault(FaultKind::OutOfRange);
+        baro.advance(1);
+        let error = baro.health_check().await.unwrap_err();
+        assert_eq!(error.message, "Barometer pressure out of range");
+        baro.clear_fault();
+        baro.advance(2);
+        assert!(baro.health_check().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_gps_recovers_after_out_of_range_fault() {
+        let mut gps = MockGpsSensor::new("gps-1", "GPS");
+        gps.init().await.unwrap();
+        gps.advance(1);
+        let fix = gps.position();
+
+        // Inject through a cloned handle, as a test would while the sensor runs
+        let faults = gps.fault_handle();
+        faults.inject(FaultKind::OutOfRange);
+        gps.advance(2);
+        assert_eq!(gps.active_fault(), Some(FaultKind::OutOfRange));
+        assert_eq!(gps.position(), (999.0, 999.0));
+        let error = gps.health_check().await.unwrap_err();
+        assert_eq!(error.message, "GPS fix out of range");
+
+        faults.clear();
+        assert_eq!(gps.position(), fix);
+        assert!(gps.health_check().await.is_ok());
+        gps.advance(3);
+        assert!(gps.health_check().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fault_handle_reaches_running_sensor() {
+        let mut gps = MockGpsSensor::new("gps-1", "GPS");
+        let faults = gps.fault_handle();
+        gps.init().await.unwrap();
+
+        let shutdown = CancellationToken::new();
+        let stop = shutdown.clone();
+        let running = tokio::spawn(async move {
+            gps.run(shutdown).await.unwrap();
+            gps
+        });
+        faults.inject(FaultKind::LoseSignal);
+        stop.cancel();
+        let gps = running.await.unwrap();
+
+        assert_eq!(gps.active_fault(), Some(FaultKind::LoseSignal));
+        assert!(gps.health_check().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_sensor_slows_for_slow_consumer() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut sensor = MockAdaptiveSensor::new(
+            "adaptive-1",
+            "Adaptive",
+            tx,
+            Duration::from_millis(1),
+            Duration::from_millis(64),
+        );
+        let initial_rate = sensor.effective_rate_hz();
+        sensor.init().await.unwrap();
+
+        let shutdown = CancellationToken::new();
+        let consumer = async {
+            // Slow consumer: one reading every 25ms
+            for _ in 0..12 {
+                tokio::time::sleep(Duration::from_millis(25)).await;
+                rx.recv().await;
+            }
+            shutdown.cancel();
+        };
+        let (result, _) = tokio::join!(sensor.run(shutdown.clone()), consumer);
+
+        result.unwrap();
+        assert!(
+            sensor.effective_rate_hz() <= initial_rate / 8.0,
+            "rate {} Hz did not drop from {} Hz",
+            sensor.effective_rate_hz(),
+            initial_rate
+        );
+        assert!(sensor.emitted() >= 8);
+    }
+}
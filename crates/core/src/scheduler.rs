@@ -4,9 +4,13 @@
 //! at guaranteed frequencies (e.g., 100Hz) while handling lower-priority async
 //! tasks concurrently.
 
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 
 /// Result type for scheduler operations
@@ -45,6 +49,8 @@ pub struct LoopStats {
     pub slack_time_ms: i32,
     /// Number of iterations completed
     pub iteration_count: u64,
+    /// Number of iterations that overran their period
+    pub missed_deadlines: u64,
 }
 
 impl LoopStats {
@@ -54,6 +60,170 @@ impl LoopStats {
     }
 }
 
+/// Period timing quality accumulated over a loop's run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JitterStats {
+    /// Number of measured periods
+    pub samples: u64,
+    /// Shortest measured period (milliseconds)
+    pub min_period_ms: f64,
+    /// Longest measured period (milliseconds)
+    pub max_period_ms: f64,
+    /// Mean measured period (milliseconds)
+    pub mean_period_ms: f64,
+    /// Population standard deviation of the period (milliseconds)
+    pub jitter_ms: f64,
+}
+
+/// Running period statistics in constant memory (Welford's algorithm)
+#[derive(Debug, Clone, Copy, Default)]
+struct PeriodAccumulator {
+    count: u64,
+    min: f64,
+    max: f64,
+    mean: f64,
+    /// Sum of squared deviations from the running mean
+    m2: f64,
+}
+
+impl PeriodAccumulator {
+    fn record(&mut self, period_ms: f64) {
+        if self.count == 0 {
+            self.min = period_ms;
+            self.max = period_ms;
+        } else {
+            self.min = self.min.min(period_ms);
+            self.max = self.max.max(period_ms);
+        }
+        self.count += 1;
+        let delta = period_ms - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (period_ms - self.mean);
+    }
+
+    fn stats(&self) -> JitterStats {
+        let variance = if self.count == 0 {
+            0.0
+        } else {
+            self.m2 / self.count as f64
+        };
+        JitterStats {
+            samples: self.count,
+            min_period_ms: self.min,
+            max_period_ms: self.max,
+            mean_period_ms: self.mean,
+            jitter_ms: variance.sqrt(),
+        }
+    }
+}
+
+/// Default execution-time bucket boundaries (milliseconds)
+const DEFAULT_HISTOGRAM_BOUNDS_MS: [f64; 4] = [1.0, 2.0, 5.0, 10.0];
+
+/// Histogram of per-iteration execution times
+///
+/// Buckets are delimited by ascending upper bounds in milliseconds; a final
+/// open-ended bucket collects everything above the last bound. The default
+/// buckets are 0-1, 1-2, 2-5, 5-10 and 10+ ms.
+#[derive(Debug, Clone)]
+pub struct ExecutionHistogram {
+    bounds_ms: Vec<f64>,
+    counts: Vec<u64>,
+    total: u64,
+    max_ms: f64,
+}
+
+impl ExecutionHistogram {
+    /// Create a histogram with the given bucket upper bounds (milliseconds)
+    ///
+    /// Bounds are sorted and duplicates removed.
+    pub fn new(mut bounds_ms: Vec<f64>) -> Self {
+        bounds_ms.retain(|bound| bound.is_finite());
+        bounds_ms.sort_by(f64::total_cmp);
+        bounds_ms.dedup();
+        let counts = vec![0; bounds_ms.len() + 1];
+        Self {
+            bounds_ms,
+            counts,
+            total: 0,
+            max_ms: 0.0,
+        }
+    }
+
+    /// Record one execution time
+    pub fn record(&mut self, execution_ms: f64) {
+        let bucket = self
+            .bounds_ms
+            .partition_point(|bound| *bound <= execution_ms);
+        self.counts[bucket] += 1;
+        self.total += 1;
+        self.max_ms = self.max_ms.max(execution_ms);
+    }
+
+    /// Number of recorded executions
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Upper bound of the bucket holding the `quantile` (0.0-1.0) execution time
+    ///
+    /// Resolution is limited to the bucket boundaries; a quantile falling in
+    /// the open-ended bucket reports the longest execution seen. Returns
+    /// `None` if nothing has been recorded.
+    pub fn percentile(&self, quantile: f64) -> Option<f64> {
+        if self.total == 0 {
+            return None;
+        }
+        let rank = ((quantile.clamp(0.0, 1.0) * self.total as f64).ceil() as u64).max(1);
+        let mut cumulative = 0;
+        for (bucket, count) in self.counts.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= rank {
+                return Some(self.bounds_ms.get(bucket).copied().unwrap_or(self.max_ms));
+            }
+        }
+        Some(self.max_ms)
+    }
+
+    /// 99th percentile execution time (milliseconds)
+    pub fn p99(&self) -> Option<f64> {
+        self.percentile(0.99)
+    }
+
+    /// 99.9th percentile execution time (milliseconds)
+    pub fn p999(&self) -> Option<f64> {
+        self.percentile(0.999)
+    }
+
+    /// Bucket counts keyed by label (e.g. "0-1", "5-10", "10+")
+    pub fn bucket_counts(&self) -> HashMap<String, u64> {
+        let mut lower = 0.0;
+        let mut buckets = HashMap::with_capacity(self.counts.len());
+        for (bound, count) in self.bounds_ms.iter().zip(&self.counts) {
+            buckets.insert(format!("{}-{}", lower, bound), *count);
+            lower = *bound;
+        }
+        buckets.insert(format!("{}+", lower), self.counts[self.bounds_ms.len()]);
+        buckets
+    }
+}
+
+impl Default for ExecutionHistogram {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTOGRAM_BOUNDS_MS.to_vec())
+    }
+}
+
+/// How a `RealTimeLoop` recovers after an iteration overruns its period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeadlinePolicy {
+    /// Drop the missed slots and realign to the next period boundary
+    #[default]
+    Skip,
+    /// Run the missed slots back-to-back, without sleeping, until caught up
+    CatchUp,
+}
+
 /// Trait for tasks that must run at fixed, guaranteed frequencies
 pub trait ControlLoopTask: Send {
     /// Execute one iteration of the control loop
@@ -68,6 +238,41 @@ pub trait ControlLoopTask: Send {
     }
 }
 
+/// Trait for fixed-frequency tasks whose iteration needs to await
+///
+/// Use this when an iteration must read a sensor or write an actuator over
+/// an async transport. The runtime awaits `execute` each period and applies
+/// the same deadline handling as for `ControlLoopTask`: time spent awaiting
+/// counts against the period.
+///
+/// Awaiting does not make blocking acceptable. Blocking calls (synchronous
+/// I/O, `std::thread::sleep`, heavy computation) inside `execute` stall the
+/// executor thread and still violate the loop's real-time guarantees.
+#[async_trait]
+pub trait AsyncControlLoopTask: Send {
+    /// Execute one iteration of the control loop
+    async fn execute(&mut self) -> SchedulerResult<()>;
+
+    /// Optional: Get the name of this task for logging
+    fn name(&self) -> &str {
+        "AsyncControlLoopTask"
+    }
+}
+
+/// Adapts a `ControlLoopTask` so both kinds share one runtime loop
+struct SyncControlLoop<'a>(&'a mut dyn ControlLoopTask);
+
+#[async_trait]
+impl AsyncControlLoopTask for SyncControlLoop<'_> {
+    async fn execute(&mut self) -> SchedulerResult<()> {
+        self.0.execute()
+    }
+
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+}
+
 /// A rate-limited, real-time control loop scheduler
 ///
 /// Guarantees that a task will execute at a specified frequency,
@@ -79,10 +284,16 @@ pub struct RealTimeLoop {
     period: Duration,
     /// Statistics tracking
     stats: LoopStats,
-    /// Time of last iteration start
+    /// Start of the current period slot
     last_iteration: Instant,
     /// Last measured period
     measured_period: Duration,
+    /// Recovery behavior after a missed deadline
+    policy: DeadlinePolicy,
+    /// Period statistics over the whole run
+    periods: PeriodAccumulator,
+    /// Opt-in execution time histogram
+    histogram: Option<ExecutionHistogram>,
 }
 
 impl RealTimeLoop {
@@ -94,12 +305,16 @@ impl RealTimeLoop {
     /// # Returns
     /// SchedulerResult containing the loop or an error if frequency is invalid
     pub fn new(frequency_hz: u32) -> SchedulerResult<Self> {
-        if frequency_hz == 0 || frequency_hz > 10000 {
-            return Err(SchedulerError::InvalidFrequency);
-        }
+        Self::with_policy(frequency_hz, DeadlinePolicy::default())
+    }
 
-        let period_ms = 1000 / frequency_hz;
-        let period = Duration::from_millis(period_ms as u64);
+    /// Create a new real-time loop with an explicit deadline-miss policy
+    ///
+    /// Periods are phase-locked: each slot starts where the previous one
+    /// ended, so sleep overshoot does not accumulate into drift. `policy`
+    /// decides how the phase is advanced after an overrun.
+    pub fn with_policy(frequency_hz: u32, policy: DeadlinePolicy) -> SchedulerResult<Self> {
+        let (period_ms, period) = Self::period_for(frequency_hz)?;
 
         Ok(Self {
             frequency_hz,
@@ -110,39 +325,107 @@ impl RealTimeLoop {
                 execution_time_ms: 0,
                 slack_time_ms: 0,
                 iteration_count: 0,
+                missed_deadlines: 0,
             },
             last_iteration: Instant::now(),
             measured_period: Duration::ZERO,
+            policy,
+            periods: PeriodAccumulator::default(),
+            histogram: None,
         })
     }
 
+    /// Validate a frequency and compute its period
+    fn period_for(frequency_hz: u32) -> SchedulerResult<(u32, Duration)> {
+        if frequency_hz == 0 || frequency_hz > 10000 {
+            return Err(SchedulerError::InvalidFrequency);
+        }
+
+        let period_ms = 1000 / frequency_hz;
+        Ok((period_ms, Duration::from_millis(period_ms as u64)))
+    }
+
+    /// Change the loop frequency while running
+    ///
+    /// The new period takes effect on the next `wait_next_period` call.
+    /// Iteration statistics are kept.
+    ///
+    /// # Returns
+    /// `SchedulerError::InvalidFrequency` if `frequency_hz` is outside
+    /// 1..=10000, in which case the current frequency is left unchanged
+    pub fn set_frequency(&mut self, frequency_hz: u32) -> SchedulerResult<()> {
+        let (period_ms, period) = Self::period_for(frequency_hz)?;
+        self.frequency_hz = frequency_hz;
+        self.period = period;
+        self.stats.period_ms = period_ms;
+        Ok(())
+    }
+
+    /// Get the current loop frequency in Hz
+    pub fn frequency_hz(&self) -> u32 {
+        self.frequency_hz
+    }
+
+    /// Get the deadline-miss policy
+    pub fn policy(&self) -> DeadlinePolicy {
+        self.policy
+    }
+
     /// Wait until the next period boundary, maintaining guaranteed frequency
     ///
     /// This should be called at the end of each iteration.
     pub async fn wait_next_period(&mut self) {
         let elapsed = self.last_iteration.elapsed();
+        let previous_start = self.last_iteration;
+        if let Some(ref mut histogram) = self.histogram {
+            histogram.record(elapsed.as_secs_f64() * 1000.0);
+        }
 
         // Calculate how long to sleep to maintain frequency
-        if elapsed < self.period {
-            let sleep_time = self.period - elapsed;
-            tokio::time::sleep(sleep_time).await;
-        } else if elapsed > self.period {
+        if elapsed <= self.period {
+            tokio::time::sleep(self.period - elapsed).await;
+            self.last_iteration += self.period;
+        } else {
             // Missed deadline warning
             eprintln!(
                 "[{}Hz Loop] Warning: Missed deadline by {:.1}ms",
                 self.frequency_hz,
                 (elapsed - self.period).as_secs_f32() * 1000.0
             );
+            self.stats.missed_deadlines += 1;
+
+            match self.policy {
+                DeadlinePolicy::Skip => {
+                    // Sleep to the next boundary after now, dropping missed slots
+                    let slots = (elapsed.as_nanos() / self.period.as_nanos()) as u32 + 1;
+                    let boundary = previous_start + self.period * slots;
+                    tokio::time::sleep(boundary.saturating_duration_since(Instant::now())).await;
+                    self.last_iteration = boundary;
+                }
+                DeadlinePolicy::CatchUp => {
+                    // Start the next slot immediately; it is due at once
+                    self.last_iteration += self.period;
+                }
+            }
         }
 
         // Update statistics
         let now = Instant::now();
-        self.measured_period = now - self.last_iteration;
+        self.measured_period = now - previous_start;
         self.stats.measured_period_ms = self.measured_period.as_millis() as u32;
+        self.periods
+            .record(self.measured_period.as_secs_f64() * 1000.0);
         self.stats.execution_time_ms = elapsed.as_millis() as u32;
         self.stats.slack_time_ms = (self.period.as_millis() as i32) - (elapsed.as_millis() as i32);
         self.stats.iteration_count += 1;
-        self.last_iteration = now;
+    }
+
+    /// Restart period timing from now
+    ///
+    /// Used after the loop was held outside its schedule (e.g. paused by a
+    /// `StepController`) so the pause is not reported as a missed deadline.
+    pub(crate) fn restart_period(&mut self) {
+        self.last_iteration = Instant::now();
     }
 
     /// Get current loop statistics
@@ -150,6 +433,29 @@ impl RealTimeLoop {
         self.stats
     }
 
+    /// Get period min/max/mean and jitter over every iteration so far
+    ///
+    /// Jitter is the standard deviation of the measured period, computed
+    /// incrementally so memory stays constant however long the loop runs.
+    pub fn jitter(&self) -> JitterStats {
+        self.periods.stats()
+    }
+
+    /// Start recording execution times into `histogram`
+    pub fn enable_histogram(&mut self, histogram: ExecutionHistogram) {
+        self.histogram = Some(histogram);
+    }
+
+    /// Stop recording execution times, returning the histogram so far
+    pub fn disable_histogram(&mut self) -> Option<ExecutionHistogram> {
+        self.histogram.take()
+    }
+
+    /// Get the execution time histogram, if enabled
+    pub fn histogram(&self) -> Option<&ExecutionHistogram> {
+        self.histogram.as_ref()
+    }
+
     /// Log current loop statistics
     pub fn log_stats(&self) {
         println!(
@@ -164,6 +470,72 @@ impl RealTimeLoop {
     }
 }
 
+/// Single-step control for debugging a control loop
+///
+/// While paused, the runtime waits for a `step()` before each
+/// `task.execute()`, so an engineer can run one iteration, inspect state and
+/// then advance. `resume()` returns the loop to free-running. Clones share
+/// the same state, so one handle can be attached to the runtime while
+/// another drives it.
+#[derive(Clone)]
+pub struct StepController {
+    steps: Arc<Semaphore>,
+    free_running: Arc<AtomicBool>,
+}
+
+impl StepController {
+    /// Create a controller that starts paused
+    pub fn new() -> Self {
+        Self {
+            steps: Arc::new(Semaphore::new(0)),
+            free_running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Allow exactly one more iteration while paused
+    pub fn step(&self) {
+        self.steps.add_permits(1);
+    }
+
+    /// Return to free-running, releasing a loop waiting for a step
+    pub fn resume(&self) {
+        self.free_running.store(true, Ordering::SeqCst);
+        self.steps.add_permits(1);
+    }
+
+    /// Pause before the next iteration, discarding unused steps
+    pub fn pause(&self) {
+        self.free_running.store(false, Ordering::SeqCst);
+        while let Ok(permit) = self.steps.try_acquire() {
+            permit.forget();
+        }
+    }
+
+    /// Check whether the loop is paused
+    pub fn is_paused(&self) -> bool {
+        !self.free_running.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the next iteration may run
+    ///
+    /// Returns `true` if the loop had to wait for a step.
+    async fn wait_turn(&self) -> bool {
+        if !self.is_paused() {
+            return false;
+        }
+        if let Ok(permit) = self.steps.acquire().await {
+            permit.forget();
+        }
+        true
+    }
+}
+
+impl Default for StepController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Mixed-priority runtime that runs high-frequency control loops
 /// alongside lower-priority async tasks
 pub struct MixedPriorityRuntime {
@@ -174,6 +546,8 @@ pub struct MixedPriorityRuntime {
     background_tasks: Arc<Mutex<Vec<Box<dyn std::any::Any + Send>>>>,
     /// Cancellation token
     shutdown_token: CancellationToken,
+    /// Optional single-step debugging control
+    step_controller: Option<StepController>,
 }
 
 impl MixedPriorityRuntime {
@@ -187,9 +561,16 @@ impl MixedPriorityRuntime {
             loop_frequency,
             background_tasks: Arc::new(Mutex::new(Vec::new())),
             shutdown_token: CancellationToken::new(),
+            step_controller: None,
         })
     }
 
+    /// Attach a step controller, holding each iteration until it is stepped
+    pub fn with_step_controller(mut self, controller: StepController) -> Self {
+        self.step_controller = Some(controller);
+        self
+    }
+
     /// Run a control loop task at guaranteed frequency with background async support
     ///
     /// # Arguments
@@ -206,6 +587,29 @@ impl MixedPriorityRuntime {
         task: &mut dyn ControlLoopTask,
         shutdown: CancellationToken,
     ) -> SchedulerResult<()> {
+        self.drive_loop(&mut SyncControlLoop(task), shutdown)
+            .await
+            .map(|_| ())
+    }
+
+    /// Run an async control loop task at guaranteed frequency
+    ///
+    /// `task.execute()` is awaited each period. If it overruns the period the
+    /// deadline is reported as missed, exactly as for `run_control_loop`.
+    pub async fn run_async_control_loop(
+        &self,
+        task: &mut dyn AsyncControlLoopTask,
+        shutdown: CancellationToken,
+    ) -> SchedulerResult<()> {
+        self.drive_loop(task, shutdown).await.map(|_| ())
+    }
+
+    /// Run `task` each period until `shutdown`, returning the final loop statistics
+    async fn drive_loop(
+        &self,
+        task: &mut dyn AsyncControlLoopTask,
+        shutdown: CancellationToken,
+    ) -> SchedulerResult<LoopStats> {
         let mut loop_scheduler = RealTimeLoop::new(self.loop_frequency)?;
 
         println!(
@@ -224,7 +628,7 @@ impl MixedPriorityRuntime {
                         loop_scheduler.stats().iteration_count
                     );
                     loop_scheduler.log_stats();
-                    return Ok(());
+                    return Ok(loop_scheduler.stats());
                 }
                 _ = tokio::time::sleep(Duration::from_millis(1)) => {
                     // Check for shutdown without blocking
@@ -232,8 +636,21 @@ impl MixedPriorityRuntime {
                         continue;
                     }
 
+                    // Hold here while a step controller is paused
+                    if let Some(ref controller) = self.step_controller {
+                        tokio::select! {
+                            biased;
+                            _ = shutdown.cancelled() => continue,
+                            waited = controller.wait_turn() => {
+                                if waited {
+                                    loop_scheduler.restart_period();
+                                }
+                            }
+                        }
+                    }
+
                     // Execute the control loop task
-                    task.execute()?;
+                    task.execute().await?;
 
                     // Wait until next period to maintain frequency
                     loop_scheduler.wait_next_period().await;
@@ -290,6 +707,188 @@ mod tests {
         assert!(loop_too_high.is_err());
     }
 
+    #[tokio::test]
+    async fn test_set_frequency_mid_run() {
+        let mut rt_loop = RealTimeLoop::new(100).unwrap();
+        for _ in 0..3 {
+            rt_loop.wait_next_period().await;
+        }
+        assert_eq!(rt_loop.stats().period_ms, 10);
+
+        rt_loop.set_frequency(50).unwrap();
+        assert_eq!(rt_loop.frequency_hz(), 50);
+        for _ in 0..3 {
+            rt_loop.wait_next_period().await;
+        }
+        let stats = rt_loop.stats();
+        assert_eq!(stats.period_ms, 20);
+        assert!(stats.measured_period_ms >= 19);
+        assert_eq!(stats.iteration_count, 6);
+
+        assert!(rt_loop.set_frequency(0).is_err());
+        assert_eq!(rt_loop.frequency_hz(), 50);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_deadline_policies_after_overrun() {
+        // Skip: an overrun of 2.5 periods realigns to the 30ms boundary
+        let mut rt_loop = RealTimeLoop::with_policy(100, DeadlinePolicy::Skip).unwrap();
+        let start = rt_loop.last_iteration;
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        rt_loop.wait_next_period().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(30));
+        assert_eq!(rt_loop.last_iteration - start, Duration::from_millis(30));
+        assert_eq!(rt_loop.stats().missed_deadlines, 1);
+        assert_eq!(rt_loop.stats().iteration_count, 1);
+
+        // CatchUp: missed slots run back-to-back without sleeping
+        let mut rt_loop = RealTimeLoop::with_policy(100, DeadlinePolicy::CatchUp).unwrap();
+        let start = rt_loop.last_iteration;
+        tokio::time::sleep(Duration::from_millis(45)).await;
+        let caught_up = Instant::now();
+        for _ in 0..4 {
+            rt_loop.wait_next_period().await;
+        }
+        assert_eq!(caught_up.elapsed(), Duration::ZERO);
+        assert_eq!(rt_loop.last_iteration - start, Duration::from_millis(40));
+        assert_eq!(rt_loop.stats().missed_deadlines, 4);
+        assert_eq!(rt_loop.stats().iteration_count, 4);
+    }
+
+    #[test]
+    fn test_period_accumulator_matches_batch_statistics() {
+        let mut periods = PeriodAccumulator::default();
+        assert_eq!(periods.stats().samples, 0);
+        for period in [10.0, 12.0, 8.0, 10.0] {
+            periods.record(period);
+        }
+
+        let stats = periods.stats();
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.min_period_ms, 8.0);
+        assert_eq!(stats.max_period_ms, 12.0);
+        assert!((stats.mean_period_ms - 10.0).abs() < 1e-9);
+        assert!((stats.jitter_ms - 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_execution_histogram_buckets_and_percentiles() {
+        let mut histogram = ExecutionHistogram::default();
+        assert_eq!(histogram.p99(), None);
+
+        for _ in 0..990 {
+            histogram.record(0.4);
+        }
+        for _ in 0..9 {
+            histogram.record(3.0);
+        }
+        histogram.record(12.5);
+
+        assert_eq!(histogram.total(), 1000);
+        assert_eq!(histogram.percentile(0.5), Some(1.0));
+        assert_eq!(histogram.p99(), Some(1.0));
+        assert_eq!(histogram.p999(), Some(5.0));
+        assert_eq!(histogram.percentile(1.0), Some(12.5));
+
+        let buckets = histogram.bucket_counts();
+        assert_eq!(buckets.len(), 5);
+        assert_eq!(buckets["0-1"], 990);
+        assert_eq!(buckets["1-2"], 0);
+        assert_eq!(buckets["2-5"], 9);
+        assert_eq!(buckets["10+"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_histogram_is_opt_in() {
+        let mut rt_loop = RealTimeLoop::new(500).unwrap();
+        rt_loop.wait_next_period().await;
+        assert!(rt_loop.histogram().is_none());
+
+        rt_loop.enable_histogram(ExecutionHistogram::default());
+        for _ in 0..3 {
+            rt_loop.wait_next_period().await;
+        }
+        assert_eq!(rt_loop.histogram().unwrap().total(), 3);
+
+        let histogram = rt_loop.disable_histogram().unwrap();
+        rt_loop.wait_next_period().await;
+        assert_eq!(histogram.total(), 3);
+        assert!(rt_loop.histogram().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_jitter_tracks_every_period() {
+        let mut rt_loop = RealTimeLoop::new(200).unwrap();
+        for _ in 0..5 {
+            rt_loop.wait_next_period().await;
+        }
+
+        let jitter = rt_loop.jitter();
+        assert_eq!(jitter.samples, 5);
+        assert!(jitter.min_period_ms >= 5.0);
+        assert!(jitter.min_period_ms <= jitter.mean_period_ms);
+        assert!(jitter.mean_period_ms <= jitter.max_period_ms);
+    }
+
+    #[tokio::test]
+    async fn test_step_controller_runs_one_iteration_per_step() {
+        let controller = StepController::new();
+        let runtime = MixedPriorityRuntime::new(1000)
+            .unwrap()
+            .with_step_controller(controller.clone());
+        let shutdown = CancellationToken::new();
+        let mut task = TestTask { iterations: 0 };
+
+        let driver = async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            for _ in 0..3 {
+                controller.step();
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            shutdown.cancel();
+        };
+        let (result, _) = tokio::join!(
+            runtime.run_control_loop(&mut task, shutdown.clone()),
+            driver
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(task.iterations, 3);
+    }
+
+    /// Async task whose every iteration awaits longer than a 100Hz period
+    struct SlowAsyncTask {
+        iterations: u32,
+    }
+
+    #[async_trait]
+    impl AsyncControlLoopTask for SlowAsyncTask {
+        async fn execute(&mut self) -> SchedulerResult<()> {
+            tokio::time::sleep(Duration::from_millis(15)).await;
+            self.iterations += 1;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_control_loop_reports_overrun() {
+        let runtime = MixedPriorityRuntime::new(100).unwrap();
+        let shutdown = CancellationToken::new();
+        let mut task = SlowAsyncTask { iterations: 0 };
+
+        let trigger = shutdown.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            trigger.cancel();
+        });
+        let stats = runtime.drive_loop(&mut task, shutdown).await.unwrap();
+
+        assert!(task.iterations > 0);
+        assert_eq!(stats.iteration_count, task.iterations as u64);
+        assert_eq!(stats.missed_deadlines, stats.iteration_count);
+    }
+
     #[test]
     fn test_loop_stats_utilization() {
         let stats = LoopStats {
@@ -298,6 +897,7 @@ mod tests {
             execution_time_ms: 5,
             slack_time_ms: 5,
             iteration_count: 0,
+            missed_deadlines: 0,
         };
 
         assert_eq!(stats.utilization_percent(), 50.0);
@@ -2,8 +2,18 @@
 
 use crate::component::{Component, ComponentResult};
 use async_trait::async_trait;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use telemetry::{SensorData, SensorReading};
+use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
+/// Default time between temperature readings
+const DEFAULT_INTERVAL_MS: u64 = 200;
+/// Default number of readings per `run`
+const DEFAULT_MAX_READINGS: u32 = 5;
+
 /// Example temperature sensor component
 #[derive(Debug)]
 pub struct TemperatureSensor {
@@ -11,6 +21,14 @@ pub struct TemperatureSensor {
     name: String,
     current_value: f32,
     is_initialized: bool,
+    /// Optional channel receiving every reading
+    sink: Option<mpsc::Sender<SensorReading>>,
+    /// Sequence number of the next reading sent to the sink
+    sequence: u64,
+    /// Time between readings (milliseconds)
+    interval_ms: u64,
+    /// Readings per `run` (`None` runs until cancelled)
+    max_readings: Option<u32>,
 }
 
 impl TemperatureSensor {
@@ -20,8 +38,72 @@ impl TemperatureSensor {
             name: name.into(),
             current_value: 20.0,
             is_initialized: false,
+            sink: None,
+            sequence: 0,
+            interval_ms: DEFAULT_INTERVAL_MS,
+            max_readings: Some(DEFAULT_MAX_READINGS),
+        }
+    }
+
+    /// Set the time between readings (default 200ms)
+    pub fn with_interval_ms(mut self, interval_ms: u64) -> Self {
+        self.interval_ms = interval_ms;
+        self
+    }
+
+    /// Set how many readings `run` takes before returning (default 5)
+    ///
+    /// `None` keeps reading until the shutdown token is cancelled.
+    pub fn with_max_readings(mut self, max_readings: Option<u32>) -> Self {
+        self.max_readings = max_readings;
+        self
+    }
+
+    /// Create a sensor that also sends each reading to `sender`
+    ///
+    /// This feeds the telemetry pipeline directly instead of only printing
+    /// readings. Sending waits for channel capacity, but a shutdown request
+    /// still stops `run` while it waits; if the receiver is dropped the
+    /// sensor keeps running and stops sending.
+    pub fn with_sink(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        sender: mpsc::Sender<SensorReading>,
+    ) -> Self {
+        Self {
+            sink: Some(sender),
+            ..Self::new(id, name)
         }
     }
+
+    /// Send the current value to the sink, if any
+    ///
+    /// Returns `false` if `shutdown` was cancelled while waiting for channel
+    /// capacity, in which case the reading is discarded.
+    async fn publish(&mut self, shutdown: &CancellationToken) -> bool {
+        let Some(ref sink) = self.sink else {
+            return true;
+        };
+        let reading = SensorReading::new(
+            self.id.clone(),
+            self.name.clone(),
+            SensorData::Temperature {
+                value: self.current_value,
+                unit: "°C".to_string(),
+            },
+            self.sequence,
+        );
+        self.sequence += 1;
+        let sent = tokio::select! {
+            _ = shutdown.cancelled() => return false,
+            result = sink.send(reading) => result.is_ok(),
+        };
+        if !sent {
+            println!("[{}] Reading sink closed, no longer sending", self.name);
+            self.sink = None;
+        }
+        true
+    }
 }
 
 #[async_trait]
@@ -53,17 +135,23 @@ impl Component for TemperatureSensor {
 
         println!("[{}] Running sensor loop...", self.name);
         // Simulate sensor reading; react to shutdown token
-        for i in 0..5 {
+        let mut i = 0;
+        while self.max_readings.is_none_or(|max| i < max) {
             tokio::select! {
                 _ = shutdown.cancelled() => {
                     println!("[{}] Shutdown requested, stopping sensor loop", self.name);
                     return Ok(());
                 }
-                _ = tokio::time::sleep(tokio::time::Duration::from_millis(200)) => {
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(self.interval_ms)) => {
                     self.current_value += 0.5;
                     println!("[{}] Reading {}: {:.1}°C", self.name, i + 1, self.current_value);
+                    if !self.publish(&shutdown).await {
+                        println!("[{}] Shutdown requested, stopping sensor loop", self.name);
+                        return Ok(());
+                    }
                 }
             }
+            i += 1;
         }
         Ok(())
     }
@@ -89,13 +177,66 @@ impl Component for TemperatureSensor {
     }
 }
 
+/// Default motor speed target (percent)
+const DEFAULT_TARGET_SPEED: f64 = 100.0;
+/// Default motor ramp rate (percent per second)
+const DEFAULT_RAMP_RATE: f64 = 60.0;
+/// Default time between motor speed updates
+const DEFAULT_STEP_INTERVAL_MS: u64 = 300;
+
+/// Shared handle for driving a `MotorActuator` while it runs
+///
+/// `run` holds the actuator mutably, so a controller or telemetry task
+/// clones this handle to change the target and read the current speed.
+#[derive(Debug, Clone)]
+pub struct MotorControl {
+    target_speed: Arc<AtomicU64>,
+    current_speed: Arc<AtomicU64>,
+}
+
+impl MotorControl {
+    fn new(target_speed: f64) -> Self {
+        Self {
+            target_speed: Arc::new(AtomicU64::new(target_speed.to_bits())),
+            current_speed: Arc::new(AtomicU64::new(0.0_f64.to_bits())),
+        }
+    }
+
+    /// Set the speed the motor ramps toward, clamped to 0-100%
+    pub fn set_target_speed(&self, speed: f64) {
+        let speed = speed.clamp(0.0, 100.0);
+        self.target_speed.store(speed.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the speed the motor is ramping toward (percent)
+    pub fn target_speed(&self) -> f64 {
+        f64::from_bits(self.target_speed.load(Ordering::Relaxed))
+    }
+
+    /// Get the current motor speed (percent)
+    pub fn current_speed(&self) -> f64 {
+        f64::from_bits(self.current_speed.load(Ordering::Relaxed))
+    }
+
+    fn set_current_speed(&self, speed: f64) {
+        self.current_speed.store(speed.to_bits(), Ordering::Relaxed);
+    }
+}
+
 /// Example actuator component
+///
+/// While running, the motor ramps its speed toward a target at a limited
+/// rate, one step per interval, until the shutdown token is cancelled.
 #[derive(Debug)]
 pub struct MotorActuator {
     id: String,
     name: String,
     is_running: bool,
     is_initialized: bool,
+    control: MotorControl,
+    /// Maximum speed change (percent per second)
+    ramp_rate: f64,
+    step_interval: Duration,
 }
 
 impl MotorActuator {
@@ -105,8 +246,54 @@ impl MotorActuator {
             name: name.into(),
             is_running: false,
             is_initialized: false,
+            control: MotorControl::new(DEFAULT_TARGET_SPEED),
+            ramp_rate: DEFAULT_RAMP_RATE,
+            step_interval: Duration::from_millis(DEFAULT_STEP_INTERVAL_MS),
         }
     }
+
+    /// Set the initial target speed (default 100%)
+    pub fn with_target_speed(self, speed: f64) -> Self {
+        self.control.set_target_speed(speed);
+        self
+    }
+
+    /// Set the maximum speed change in percent per second (default 60)
+    pub fn with_ramp_rate(mut self, percent_per_sec: f64) -> Self {
+        self.ramp_rate = percent_per_sec.abs();
+        self
+    }
+
+    /// Set the time between speed updates (default 300ms)
+    pub fn with_step_interval(mut self, interval: Duration) -> Self {
+        self.step_interval = interval;
+        self
+    }
+
+    /// Set the speed the motor ramps toward, clamped to 0-100%
+    pub fn set_target_speed(&mut self, speed: f64) {
+        self.control.set_target_speed(speed);
+    }
+
+    /// Get the current motor speed (percent)
+    pub fn current_speed(&self) -> f64 {
+        self.control.current_speed()
+    }
+
+    /// Get a handle for driving the motor while it runs
+    pub fn control(&self) -> MotorControl {
+        self.control.clone()
+    }
+
+    /// Move the current speed one step toward the target
+    fn step(&mut self) -> f64 {
+        let max_change = self.ramp_rate * self.step_interval.as_secs_f64();
+        let current = self.control.current_speed();
+        let change = (self.control.target_speed() - current).clamp(-max_change, max_change);
+        let speed = current + change;
+        self.control.set_current_speed(speed);
+        speed
+    }
 }
 
 #[async_trait]
@@ -137,22 +324,22 @@ impl Component for MotorActuator {
         println!("[{}] Starting motor...", self.name);
         self.is_running = true;
 
-        for speed in (0..=100).step_by(20) {
+        loop {
             tokio::select! {
                 _ = shutdown.cancelled() => {
                     println!("[{}] Shutdown requested, stopping motor...", self.name);
                     self.is_running = false;
                     return Ok(());
                 }
-                _ = tokio::time::sleep(tokio::time::Duration::from_millis(300)) => {
-                    println!("[{}] Motor speed: {}%", self.name, speed);
+                _ = tokio::time::sleep(self.step_interval) => {
+                    let previous = self.current_speed();
+                    let speed = self.step();
+                    if speed != previous {
+                        println!("[{}] Motor speed: {:.0}%", self.name, speed);
+                    }
                 }
             }
         }
-
-        println!("[{}] Stopping motor...", self.name);
-        self.is_running = false;
-        Ok(())
     }
 
     async fn shutdown(&mut self) -> ComponentResult<()> {
@@ -173,3 +360,129 @@ impl Component for MotorActuator {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_temperature_sensor_sends_readings_to_sink() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut sensor = TemperatureSensor::with_sink("temp-001", "Temperature", tx);
+        sensor.init().await.unwrap();
+
+        let shutdown = CancellationToken::new();
+        let handle = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { sensor.run(shutdown).await }
+        });
+
+        let first = rx.recv().await.unwrap();
+        let second = rx.recv().await.unwrap();
+        shutdown.cancel();
+        handle.await.unwrap().unwrap();
+
+        assert_eq!(first.component_id, "temp-001");
+        assert_eq!((first.sequence, second.sequence), (0, 1));
+        match first.data {
+            SensorData::Temperature { value, ref unit } => {
+                assert_eq!(value, 23.0);
+                assert_eq!(unit, "°C");
+            }
+            ref other => panic!("unexpected data {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_temperature_sensor_reading_count_and_interval() {
+        let (tx, mut rx) = mpsc::channel(16);
+        let mut sensor = TemperatureSensor::with_sink("temp-001", "Temperature", tx)
+            .with_interval_ms(5)
+            .with_max_readings(Some(2));
+        sensor.init().await.unwrap();
+        sensor.run(CancellationToken::new()).await.unwrap();
+        assert_eq!(rx.try_recv().unwrap().sequence, 0);
+        assert_eq!(rx.try_recv().unwrap().sequence, 1);
+        assert!(rx.try_recv().is_err());
+
+        // Unbounded: keeps reading past the default five until cancelled
+        sensor = sensor.with_max_readings(None);
+        let shutdown = CancellationToken::new();
+        let handle = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { sensor.run(shutdown).await }
+        });
+        for expected in 2..10 {
+            assert_eq!(rx.recv().await.unwrap().sequence, expected);
+        }
+        shutdown.cancel();
+        handle.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_temperature_sensor_stops_while_sink_is_full() {
+        let (tx, _rx) = mpsc::channel(1);
+        let mut sensor = TemperatureSensor::with_sink("temp-001", "Temperature", tx)
+            .with_interval_ms(1)
+            .with_max_readings(None);
+        sensor.init().await.unwrap();
+
+        let shutdown = CancellationToken::new();
+        let trigger = shutdown.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            trigger.cancel();
+        });
+        tokio::time::timeout(Duration::from_secs(1), sensor.run(shutdown))
+            .await
+            .expect("a stalled consumer blocked shutdown")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_motor_ramps_toward_target() {
+        let mut motor = MotorActuator::new("motor-001", "Motor")
+            .with_target_speed(50.0)
+            .with_ramp_rate(100.0)
+            .with_step_interval(Duration::from_millis(100));
+
+        let ramp: Vec<f64> = (0..6).map(|_| motor.step()).collect();
+        assert_eq!(ramp, vec![10.0, 20.0, 30.0, 40.0, 50.0, 50.0]);
+
+        motor.set_target_speed(35.0);
+        assert_eq!(motor.step(), 40.0);
+        assert_eq!(motor.step(), 35.0);
+
+        motor.set_target_speed(250.0);
+        assert_eq!(motor.control().target_speed(), 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_motor_runs_until_cancelled() {
+        let mut motor = MotorActuator::new("motor-001", "Motor")
+            .with_target_speed(0.0)
+            .with_ramp_rate(10_000.0)
+            .with_step_interval(Duration::from_millis(5));
+        let control = motor.control();
+        motor.init().await.unwrap();
+
+        let shutdown = CancellationToken::new();
+        let handle = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { motor.run(shutdown).await }
+        });
+
+        control.set_target_speed(80.0);
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while control.current_speed() != 80.0 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("motor did not reach its target");
+        assert!(!handle.is_finished());
+
+        shutdown.cancel();
+        handle.await.unwrap().unwrap();
+    }
+}
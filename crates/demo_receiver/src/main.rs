@@ -1,12 +1,50 @@
+use std::sync::Arc;
+use telemetry::{Metrics, TelemetryPacket, SCHEMA_VERSION};
 use warp::Filter;
 
 #[tokio::main]
 async fn main() {
+    let metrics = Arc::new(Metrics::new());
+
+    let received = metrics.clone();
     let telemetry = warp::post()
         .and(warp::path("telemetry"))
         .and(warp::body::bytes())
-        .map(|body: bytes::Bytes| {
+        .map(move |body: bytes::Bytes| {
             println!("Received telemetry ({} bytes):", body.len());
+            received.increment("requests_received");
+            received.record_value("request_bytes", body.len() as f64);
+            if let Ok(mut packet) = TelemetryPacket::from_json_bytes(&body) {
+                if packet.is_newer_schema() {
+                    received.increment("packets_rejected");
+                    eprintln!(
+                        "Rejecting packet {}: schema version {} is newer than supported {}",
+                        packet.sequence, packet.schema_version, SCHEMA_VERSION
+                    );
+                    return warp::reply::with_status(
+                        "unsupported schema version",
+                        warp::http::StatusCode::UNPROCESSABLE_ENTITY,
+                    );
+                }
+                if packet.schema_version < SCHEMA_VERSION {
+                    println!(
+                        "Migrating packet {} from schema version {}",
+                        packet.sequence, packet.schema_version
+                    );
+                    if let Err(e) = packet.migrate_to_current() {
+                        received.increment("packets_rejected");
+                        eprintln!("Rejecting packet {}: {}", packet.sequence, e);
+                        return warp::reply::with_status(
+                            "unsupported schema version",
+                            warp::http::StatusCode::UNPROCESSABLE_ENTITY,
+                        );
+                    }
+                    if let Ok(json) = packet.to_json() {
+                        println!("{}", json);
+                    }
+                    return warp::reply::with_status("ok", warp::http::StatusCode::OK);
+                }
+            }
             if let Ok(s) = std::str::from_utf8(&body) {
                 println!("{}", s);
             } else {
@@ -15,6 +53,18 @@ async fn main() {
             warp::reply::with_status("ok", warp::http::StatusCode::OK)
         });
 
+    // Prometheus scrape endpoint
+    let scrape = warp::get().and(warp::path("metrics")).map(move || {
+        warp::reply::with_header(
+            metrics.to_prometheus(),
+            "content-type",
+            "text/plain; version=0.0.4",
+        )
+    });
+
     println!("Demo receiver listening on http://127.0.0.1:3030/telemetry");
-    warp::serve(telemetry).run(([127, 0, 0, 1], 3030)).await;
+    println!("Metrics available at http://127.0.0.1:3030/metrics");
+    warp::serve(telemetry.or(scrape))
+        .run(([127, 0, 0, 1], 3030))
+        .await;
 }
@@ -1,24 +1,32 @@
 use std::time::Duration;
 
-use telemetry::TelemetryPacket;
-use tokio::time::sleep;
+use telemetry::{ProducerHandle, TelemetryPacket};
+use tokio::sync::mpsc;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Demo simulator: sending telemetry to http://127.0.0.1:3030/telemetry");
 
-    for i in 0..10u32 {
-        let mut pkt = TelemetryPacket::new(i as u64);
+    let (tx, mut rx) = mpsc::channel::<TelemetryPacket>(16);
+    let producer = ProducerHandle::spawn(tx, Duration::from_secs(1), |i| {
+        if i >= 10 {
+            return None;
+        }
         // attach a simple diagnostic entry to carry demo info
-        let mut diag = pkt.diagnostics.clone();
-        diag.add_entry(telemetry::DiagnosticEntry::new(
-            telemetry::DiagnosticLevel::Info,
-            format!("sim-{}", i),
-            format!("demo counter={}", i),
-        ));
-        pkt.diagnostics = diag;
+        let pkt = TelemetryPacket::builder()
+            .sequence(i)
+            .add_diagnostic(telemetry::DiagnosticEntry::new(
+                telemetry::DiagnosticLevel::Info,
+                format!("sim-{}", i),
+                format!("demo counter={}", i),
+            ))
+            .metadata("source", "demo_simulator")
+            .build();
+        Some(pkt)
+    });
 
-        let client = reqwest::Client::new();
+    let client = reqwest::Client::new();
+    while let Some(pkt) = rx.recv().await {
         let res = client
             .post("http://127.0.0.1:3030/telemetry")
             .json(&pkt)
@@ -26,12 +34,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .await;
 
         match res {
-            Ok(r) => println!("Sent telemetry {}: status {}", i, r.status()),
-            Err(e) => eprintln!("Failed to send telemetry {}: {}", i, e),
+            Ok(r) => println!("Sent telemetry {}: status {}", pkt.sequence, r.status()),
+            Err(e) => eprintln!("Failed to send telemetry {}: {}", pkt.sequence, e),
         }
-
-        sleep(Duration::from_secs(1)).await;
     }
 
+    let produced = producer.join().await;
+    println!("Demo simulator finished after {} packets", produced);
+
     Ok(())
 }
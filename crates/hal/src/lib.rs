@@ -12,6 +12,6 @@ pub mod registers;
 pub mod traits;
 
 pub use device::Device;
-pub use peripherals::{GpioPin, SpiInterface, TimerUnit, UartPort};
-pub use registers::{Register, RegisterValue};
+pub use peripherals::{GpioPin, PinMode, Pull, SpiInterface, TimerUnit, UartPort};
+pub use registers::{Register, RegisterValue, RegisterValue32, RegisterWidth};
 pub use traits::HalTrait;
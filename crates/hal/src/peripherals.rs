@@ -6,22 +6,57 @@
 //! These implementations focus on type-safety and preventing common errors
 //! like accessing uninitialized peripherals or using invalid configurations.
 
+/// Internal resistor biasing an input pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pull {
+    /// No bias; the pin floats unless driven externally
+    #[default]
+    None,
+    /// Pulled high when not driven
+    Up,
+    /// Pulled low when not driven
+    Down,
+}
+
+/// Direction of a GPIO pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinMode {
+    /// Digital input, e.g. a button or limit switch
+    Input(Pull),
+    /// Digital output
+    Output,
+}
+
 /// GPIO Pin abstraction
 ///
-/// Safely manages digital output pins without exposing register details
-/// to the application layer.
+/// Safely manages digital input and output pins without exposing register
+/// details to the application layer. Output operations fail on input pins
+/// and `read` fails on output pins.
 #[derive(Debug, Clone, Copy)]
 pub struct GpioPin {
     pin_num: u8,
+    mode: PinMode,
     is_high: bool,
+    /// Level an external circuit drives onto an input pin, if any
+    driven: Option<bool>,
+    /// Last level driven onto the pin, which a floating input keeps reading
+    last_driven: bool,
 }
 
 impl GpioPin {
-    /// Create a new GPIO pin
+    /// Create a new GPIO output pin
     pub fn new(pin_num: u8) -> Self {
+        Self::with_mode(pin_num, PinMode::Output)
+    }
+
+    /// Create a new GPIO pin in the given mode
+    pub fn with_mode(pin_num: u8, mode: PinMode) -> Self {
         Self {
             pin_num,
+            mode,
             is_high: false,
+            driven: None,
+            last_driven: false,
         }
     }
 
@@ -30,13 +65,57 @@ impl GpioPin {
         self.pin_num
     }
 
+    /// Get the pin mode
+    pub fn mode(&self) -> PinMode {
+        self.mode
+    }
+
+    /// Reconfigure the pin as input or output
+    pub fn set_mode(&mut self, mode: PinMode) {
+        println!("[GPIO {}] Setting mode {:?}", self.pin_num, mode);
+        self.mode = mode;
+    }
+
     /// Check if pin is currently high
     pub fn is_high(&self) -> bool {
         self.is_high
     }
 
+    /// Read the level of an input pin
+    ///
+    /// An undriven input reads its pull level; with `Pull::None` it is
+    /// floating and reads the last level driven onto it, or low if it was
+    /// never driven. Only output pins fail.
+    pub fn read(&self) -> Result<bool, String> {
+        let PinMode::Input(pull) = self.mode else {
+            return Err(format!("GPIO {} is configured as output", self.pin_num));
+        };
+        match (self.driven, pull) {
+            (Some(level), _) => Ok(level),
+            (None, Pull::Up) => Ok(true),
+            (None, Pull::Down) => Ok(false),
+            (None, Pull::None) => Ok(self.last_driven),
+        }
+    }
+
+    /// Simulate an external circuit driving an input pin (`None` releases it)
+    pub fn drive_input(&mut self, level: Option<bool>) {
+        self.driven = level;
+        if let Some(level) = level {
+            self.last_driven = level;
+        }
+    }
+
+    fn ensure_output(&self) -> Result<(), String> {
+        match self.mode {
+            PinMode::Output => Ok(()),
+            PinMode::Input(_) => Err(format!("GPIO {} is configured as input", self.pin_num)),
+        }
+    }
+
     /// Set pin to LOW
     pub fn set_low(&mut self) -> Result<(), String> {
+        self.ensure_output()?;
         println!("[GPIO {}] Setting pin LOW", self.pin_num);
         self.is_high = false;
         Ok(())
@@ -44,6 +123,7 @@ impl GpioPin {
 
     /// Set pin to HIGH
     pub fn set_high(&mut self) -> Result<(), String> {
+        self.ensure_output()?;
         println!("[GPIO {}] Setting pin HIGH", self.pin_num);
         self.is_high = true;
         Ok(())
@@ -51,6 +131,7 @@ impl GpioPin {
 
     /// Toggle the pin state
     pub fn toggle(&mut self) -> Result<(), String> {
+        self.ensure_output()?;
         self.is_high = !self.is_high;
         println!(
             "[GPIO {}] Toggled to {}",
@@ -220,3 +301,36 @@ impl TimerUnit {
         self.is_running
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_pin_reads_pull_and_driven_level() {
+        let mut button = GpioPin::with_mode(4, PinMode::Input(Pull::Up));
+        assert_eq!(button.read(), Ok(true));
+        button.drive_input(Some(false));
+        assert_eq!(button.read(), Ok(false));
+        assert!(button.set_high().is_err());
+
+        button.set_mode(PinMode::Input(Pull::None));
+        button.drive_input(None);
+        assert_eq!(button.read(), Ok(false));
+        button.drive_input(Some(true));
+        button.drive_input(None);
+        assert_eq!(button.read(), Ok(true));
+
+        let floating = GpioPin::with_mode(5, PinMode::Input(Pull::None));
+        assert_eq!(floating.read(), Ok(false));
+    }
+
+    #[test]
+    fn test_output_pin_rejects_read() {
+        let mut led = GpioPin::new(13);
+        assert_eq!(led.mode(), PinMode::Output);
+        led.set_high().unwrap();
+        assert!(led.is_high());
+        assert!(led.read().is_err());
+    }
+}
@@ -52,42 +52,149 @@ impl<T: Copy> Register<T> {
     }
 }
 
-/// Wrapper for register values with bit-level access patterns
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Unsigned integer widths a `RegisterValue` can hold (u8, u16, u32, u64)
+///
+/// This trait is sealed; it cannot be implemented outside this crate.
+pub trait RegisterWidth:
+    sealed::Sealed
+    + Copy
+    + std::fmt::Debug
+    + PartialEq
+    + std::ops::BitAnd<Output = Self>
+    + std::ops::BitOr<Output = Self>
+    + std::ops::Not<Output = Self>
+    + std::ops::Shl<u32, Output = Self>
+    + std::ops::Shr<u32, Output = Self>
+{
+    /// Number of bits in the register
+    const BITS: u32;
+    /// All bits clear
+    const ZERO: Self;
+    /// Only bit 0 set
+    const ONE: Self;
+    /// All bits set
+    const MAX: Self;
+}
+
+macro_rules! impl_register_width {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl RegisterWidth for $ty {
+                const BITS: u32 = <$ty>::BITS;
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MAX: Self = <$ty>::MAX;
+            }
+        )*
+    };
+}
+
+impl_register_width!(u8, u16, u32, u64);
+
+/// Wrapper for register values of any width with bit-level access patterns
+///
+/// Bit indices and field bounds are checked against the width, so a
+/// full-width field never overflows the mask computation. The width
+/// defaults to 32 bits.
 #[derive(Debug, Clone, Copy)]
-pub struct RegisterValue(pub u32);
+pub struct RegisterValue<T: RegisterWidth = u32>(pub T);
+
+/// 32-bit register value
+pub type RegisterValue32 = RegisterValue<u32>;
 
-impl RegisterValue {
+impl<T: RegisterWidth> RegisterValue<T> {
     /// Check if a specific bit is set
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is not below the register width.
     pub fn is_bit_set(&self, bit: u32) -> bool {
-        (self.0 & (1 << bit)) != 0
+        (self.0 & Self::bit_mask(bit)) != T::ZERO
     }
 
     /// Set a specific bit to 1
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is not below the register width.
     pub fn set_bit(&mut self, bit: u32) {
-        self.0 |= 1 << bit;
+        self.0 = self.0 | Self::bit_mask(bit);
     }
 
     /// Clear a specific bit to 0
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is not below the register width.
     pub fn clear_bit(&mut self, bit: u32) {
-        self.0 &= !(1 << bit);
+        self.0 = self.0 & !Self::bit_mask(bit);
     }
 
     /// Extract a field of bits from [start_bit, end_bit)
-    pub fn get_bits(&self, start_bit: u32, end_bit: u32) -> u32 {
-        let mask = (1 << (end_bit - start_bit)) - 1;
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_bit > end_bit` or `end_bit` exceeds the register width.
+    pub fn get_bits(&self, start_bit: u32, end_bit: u32) -> T {
+        let mask = Self::field_mask(start_bit, end_bit);
+        if mask == T::ZERO {
+            return T::ZERO;
+        }
         (self.0 >> start_bit) & mask
     }
 
     /// Set a field of bits from [start_bit, end_bit)
-    pub fn set_bits(&mut self, start_bit: u32, end_bit: u32, value: u32) {
-        let mask = (1 << (end_bit - start_bit)) - 1;
+    ///
+    /// Bits of `value` above the field width are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_bit > end_bit` or `end_bit` exceeds the register width.
+    pub fn set_bits(&mut self, start_bit: u32, end_bit: u32, value: T) {
+        let mask = Self::field_mask(start_bit, end_bit);
+        if mask == T::ZERO {
+            return;
+        }
         self.0 = (self.0 & !(mask << start_bit)) | ((value & mask) << start_bit);
     }
 
     /// Raw value accessor
-    pub fn as_u32(&self) -> u32 {
+    pub fn value(&self) -> T {
         self.0
     }
+
+    fn bit_mask(bit: u32) -> T {
+        assert!(
+            bit < T::BITS,
+            "bit {} out of range for {}-bit register",
+            bit,
+            T::BITS
+        );
+        T::ONE << bit
+    }
+
+    /// Mask of `end_bit - start_bit` low bits, without shifting by the full width
+    fn field_mask(start_bit: u32, end_bit: u32) -> T {
+        assert!(
+            start_bit <= end_bit && end_bit <= T::BITS,
+            "bit field [{}, {}) out of range for {}-bit register",
+            start_bit,
+            end_bit,
+            T::BITS
+        );
+        let width = end_bit - start_bit;
+        if width == 0 {
+            T::ZERO
+        } else {
+            T::MAX >> (T::BITS - width)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -96,7 +203,7 @@ mod tests {
 
     #[test]
     fn test_register_value_bit_operations() {
-        let mut val = RegisterValue(0);
+        let mut val: RegisterValue32 = RegisterValue(0);
         val.set_bit(3);
         assert!(val.is_bit_set(3));
         assert!(!val.is_bit_set(2));
@@ -107,9 +214,42 @@ mod tests {
 
     #[test]
     fn test_register_value_field_operations() {
-        let mut val = RegisterValue(0);
+        let mut val: RegisterValue32 = RegisterValue(0);
         val.set_bits(2, 6, 0b1010); // Set bits [2, 6) to 1010
         assert_eq!(val.get_bits(2, 6), 0b1010);
-        assert_eq!(val.as_u32(), 0b101000);
+        assert_eq!(val.value(), 0b101000);
+    }
+
+    #[test]
+    fn test_u8_register_field_operations() {
+        let mut val = RegisterValue(0u8);
+        val.set_bits(4, 8, 0xF);
+        assert_eq!(val.get_bits(4, 8), 0xF);
+        assert_eq!(val.value(), 0xF0);
+
+        val.set_bits(0, 8, 0x5A);
+        assert_eq!(val.get_bits(0, 8), 0x5A);
+        val.set_bit(7);
+        assert!(val.is_bit_set(7));
+    }
+
+    #[test]
+    fn test_u64_register_field_operations() {
+        let mut val = RegisterValue(0u64);
+        val.set_bits(40, 64, 0xFFFF_FFFF);
+        assert_eq!(val.get_bits(40, 64), 0xFF_FFFF);
+        assert_eq!(val.value(), 0xFFFF_FF00_0000_0000);
+
+        val.set_bits(0, 64, u64::MAX);
+        assert_eq!(val.get_bits(0, 64), u64::MAX);
+        val.clear_bit(63);
+        assert!(!val.is_bit_set(63));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_register_bit_out_of_range() {
+        let val = RegisterValue(0u16);
+        val.is_bit_set(16);
     }
 }
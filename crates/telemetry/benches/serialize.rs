@@ -1,5 +1,5 @@
-use criterion::{criterion_group, criterion_main, Criterion};
-use telemetry::TelemetryPacket;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use telemetry::{EncodedBatch, TelemetryPacket};
 
 fn serialize_bench(c: &mut Criterion) {
     let mut packet = TelemetryPacket::new(42);
@@ -23,5 +23,46 @@ fn serialize_bench(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, serialize_bench);
+/// Multi-transport send: encoding per transport vs. the pipeline's
+/// serialize-once `EncodedBatch`
+fn serialize_once_bench(c: &mut Criterion) {
+    const TRANSPORTS: usize = 3;
+    let batch: Vec<TelemetryPacket> = (0..10)
+        .map(|seq| {
+            let mut packet = TelemetryPacket::new(seq);
+            for i in 0..50 {
+                packet.sensor_readings.push(telemetry::SensorReading::new(
+                    format!("sensor-{}", i),
+                    format!("Sensor {}", i),
+                    telemetry::SensorData::Temperature {
+                        value: 20.0 + i as f32 * 0.1,
+                        unit: "C".to_string(),
+                    },
+                    i,
+                ));
+            }
+            packet
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("multi_transport_serialize");
+    group.bench_function("per_transport", |b| {
+        b.iter(|| {
+            for _ in 0..TRANSPORTS {
+                black_box(EncodedBatch::encode(&batch, true).unwrap());
+            }
+        })
+    });
+    group.bench_function("serialize_once", |b| {
+        b.iter(|| {
+            let encoded = EncodedBatch::encode(&batch, true).unwrap();
+            for _ in 0..TRANSPORTS {
+                black_box(encoded.packets.as_slice());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, serialize_bench, serialize_once_bench);
 criterion_main!(benches);
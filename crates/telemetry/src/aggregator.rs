@@ -0,0 +1,133 @@
+//! Health rollup across several telemetry collectors
+//!
+//! A device made of several subsystems may run one `TelemetryCollector` per
+//! subsystem. `HealthAggregator` combines their health reports into a single
+//! `SystemHealth` using a worst-of status and summed component counts.
+
+use crate::types::{HealthStatus, SystemHealth};
+use crate::TelemetryCollector;
+use std::sync::Arc;
+
+/// Combines the health of multiple collectors into one report
+#[derive(Default)]
+pub struct HealthAggregator {
+    collectors: Vec<Arc<TelemetryCollector>>,
+}
+
+impl HealthAggregator {
+    /// Create an aggregator with no collectors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a collector to the rollup
+    pub fn add_collector(&mut self, collector: Arc<TelemetryCollector>) {
+        self.collectors.push(collector);
+    }
+
+    /// Get the number of collectors in the rollup
+    pub fn len(&self) -> usize {
+        self.collectors.len()
+    }
+
+    /// Check whether the rollup has no collectors
+    pub fn is_empty(&self) -> bool {
+        self.collectors.is_empty()
+    }
+
+    /// Snapshot every collector's health and combine the results
+    pub async fn aggregate(&self) -> SystemHealth {
+        let mut reports = Vec::with_capacity(self.collectors.len());
+        for collector in &self.collectors {
+            reports.push(collector.get_health().await);
+        }
+        Self::combine(&reports)
+    }
+
+    /// Combine health snapshots into a single report
+    ///
+    /// The status is the worst of the inputs (Critical > Degraded > Unknown >
+    /// Healthy) and component counts and memory usage are summed. Uptime and
+    /// temperature take the maximum, CPU usage the mean, and error messages
+    /// are joined. An empty input yields a default (`Unknown`) report.
+    pub fn combine(reports: &[SystemHealth]) -> SystemHealth {
+        let mut combined = SystemHealth::new();
+        let Some(first) = reports.first() else {
+            return combined;
+        };
+
+        combined.status = first.status;
+        combined.temperature_celsius = first.temperature_celsius;
+        let mut cpu_total = 0.0;
+        let mut errors = Vec::new();
+
+        for report in reports {
+            if severity(report.status) > severity(combined.status) {
+                combined.status = report.status;
+            }
+            combined.healthy_components += report.healthy_components;
+            combined.degraded_components += report.degraded_components;
+            combined.failed_components += report.failed_components;
+            combined.uptime_seconds = combined.uptime_seconds.max(report.uptime_seconds);
+            combined.memory_usage_bytes += report.memory_usage_bytes;
+            combined.temperature_celsius =
+                combined.temperature_celsius.max(report.temperature_celsius);
+            cpu_total += report.cpu_usage_percent;
+            if let Some(ref message) = report.error_message {
+                errors.push(message.clone());
+            }
+        }
+
+        combined.cpu_usage_percent = cpu_total / reports.len() as f32;
+        if !errors.is_empty() {
+            combined.error_message = Some(errors.join("; "));
+        }
+        combined
+    }
+}
+
+/// Rank a status so that larger values are worse
+fn severity(status: HealthStatus) -> u8 {
+    match status {
+        HealthStatus::Healthy => 0,
+        HealthStatus::Unknown => 1,
+        HealthStatus::Degraded => 2,
+        HealthStatus::Critical => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_aggregate_worst_of_with_summed_counts() {
+        let power = Arc::new(TelemetryCollector::new());
+        let mut health = SystemHealth::new();
+        health.healthy_components = 3;
+        health.recalculate_status();
+        power.update_health(health).await;
+
+        let propulsion = Arc::new(TelemetryCollector::new());
+        let mut health = SystemHealth::new();
+        health.healthy_components = 1;
+        health.failed_components = 2;
+        health.error_message = Some("motor driver fault".to_string());
+        health.recalculate_status();
+        propulsion.update_health(health).await;
+
+        let mut aggregator = HealthAggregator::new();
+        aggregator.add_collector(power);
+        aggregator.add_collector(propulsion);
+
+        let combined = aggregator.aggregate().await;
+        assert_eq!(combined.status, HealthStatus::Critical);
+        assert_eq!(combined.healthy_components, 4);
+        assert_eq!(combined.degraded_components, 0);
+        assert_eq!(combined.failed_components, 2);
+        assert_eq!(
+            combined.error_message.as_deref(),
+            Some("motor driver fault")
+        );
+    }
+}
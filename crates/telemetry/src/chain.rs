@@ -0,0 +1,108 @@
+//! Tamper-evident hash chain for telemetry logs
+//!
+//! Each linked packet carries a CRC32 in its metadata under
+//! [`CHAIN_HASH_KEY`], computed over the previous packet's hash and the
+//! packet's own contents. Altering, removing or reordering any packet in a
+//! stored log changes every hash after it, so [`verify_chain`] can point at
+//! the first packet that no longer matches.
+//!
+//! The chain detects accidental or casual modification; CRC32 is not a
+//! cryptographic digest and offers no protection against a deliberate
+//! attacker who recomputes the chain.
+
+use crate::TelemetryPacket;
+
+/// Metadata key holding a packet's chain hash
+pub const CHAIN_HASH_KEY: &str = "chain_hash";
+
+/// Running state for stamping packets with chained hashes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashChain {
+    last: u32,
+}
+
+impl HashChain {
+    /// Start a new chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash of the most recently linked packet (0 before the first link)
+    pub fn last_hash(&self) -> u32 {
+        self.last
+    }
+
+    /// Stamp `packet` with the next hash in the chain
+    pub fn link(&mut self, packet: &mut TelemetryPacket) {
+        let hash = chain_hash(self.last, packet);
+        packet
+            .metadata
+            .insert(CHAIN_HASH_KEY.to_string(), format_hash(hash));
+        self.last = hash;
+    }
+}
+
+/// Verify that `packets` form an unbroken chain starting from a fresh `HashChain`
+///
+/// Returns the index of the first packet whose stored hash is missing or
+/// does not match its contents and predecessor.
+pub fn verify_chain(packets: &[TelemetryPacket]) -> Result<(), usize> {
+    let mut previous = 0;
+    for (index, packet) in packets.iter().enumerate() {
+        let hash = chain_hash(previous, packet);
+        match packet.metadata.get(CHAIN_HASH_KEY) {
+            Some(stored) if *stored == format_hash(hash) => previous = hash,
+            _ => return Err(index),
+        }
+    }
+    Ok(())
+}
+
+/// CRC32 over the previous hash and the packet with its own hash removed
+fn chain_hash(previous: u32, packet: &TelemetryPacket) -> u32 {
+    // Going through `serde_json::Value` sorts object keys, so metadata
+    // HashMaps hash identically regardless of iteration order.
+    let mut value = serde_json::to_value(packet).unwrap_or_default();
+    if let Some(metadata) = value.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+        metadata.remove(CHAIN_HASH_KEY);
+    }
+    let body = serde_json::to_vec(&value).unwrap_or_default();
+
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&previous.to_le_bytes());
+    hasher.update(&body);
+    hasher.finalize()
+}
+
+fn format_hash(hash: u32) -> String {
+    format!("{:08x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_chain_reports_tampered_index() {
+        let mut chain = HashChain::new();
+        let mut packets: Vec<TelemetryPacket> = (0..5)
+            .map(|seq| {
+                let mut packet = TelemetryPacket::builder()
+                    .sequence(seq)
+                    .metadata("source", "chain-test")
+                    .build();
+                chain.link(&mut packet);
+                packet
+            })
+            .collect();
+        assert_eq!(verify_chain(&packets), Ok(()));
+
+        // Survives a round trip through the log format
+        let json = serde_json::to_string(&packets).unwrap();
+        let restored: Vec<TelemetryPacket> = serde_json::from_str(&json).unwrap();
+        assert_eq!(verify_chain(&restored), Ok(()));
+
+        packets[2].health.cpu_usage_percent = 99.0;
+        assert_eq!(verify_chain(&packets), Err(2));
+    }
+}
@@ -1,8 +1,33 @@
 //! Telemetry collector for gathering and managing system telemetry
 
+use crate::history::HistoryStore;
+use crate::producer::ProducerHandle;
+use crate::source::CollectorSource;
 use crate::types::*;
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::Sender;
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// Number of sensor readings a `TelemetryCollector` keeps by default
+pub const DEFAULT_READING_CAPACITY: usize = 1000;
+
+/// Summary of one component's scalar readings over a time window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadingStats {
+    /// Number of readings in the window
+    pub count: usize,
+    /// Smallest value
+    pub min: f32,
+    /// Largest value
+    pub max: f32,
+    /// Arithmetic mean
+    pub mean: f32,
+    /// Most recently recorded value
+    pub latest: f32,
+}
 
 /// Collects telemetry from all system components
 pub struct TelemetryCollector {
@@ -12,34 +37,88 @@ pub struct TelemetryCollector {
     health: Arc<Mutex<SystemHealth>>,
     /// Diagnostics report
     diagnostics: Arc<Mutex<DiagnosticsReport>>,
-    /// Recent sensor readings
-    sensor_readings: Arc<Mutex<Vec<SensorReading>>>,
+    /// Recent sensor readings, oldest first
+    sensor_readings: Arc<Mutex<VecDeque<SensorReading>>>,
+    /// Maximum number of sensor readings kept
+    reading_capacity: usize,
+    /// Optional long-term history of generated packets
+    history: Option<Arc<dyn HistoryStore>>,
+    /// Bounds applied to the context of recorded diagnostics
+    context_limits: ContextLimits,
 }
 
 impl TelemetryCollector {
     /// Create a new telemetry collector
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_READING_CAPACITY)
+    }
+
+    /// Create a collector keeping at most `capacity` recent sensor readings
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
             sequence: Arc::new(Mutex::new(0)),
             health: Arc::new(Mutex::new(SystemHealth::new())),
             diagnostics: Arc::new(Mutex::new(DiagnosticsReport::new())),
-            sensor_readings: Arc::new(Mutex::new(Vec::new())),
+            sensor_readings: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            reading_capacity: capacity,
+            history: None,
+            context_limits: ContextLimits::default(),
         }
     }
 
-    /// Record a sensor reading
+    /// Bound the context of recorded diagnostics to `limits`
+    pub fn with_context_limits(mut self, limits: ContextLimits) -> Self {
+        self.context_limits = limits;
+        self
+    }
+
+    /// Persist every generated packet to the given history store
+    ///
+    /// No history is kept by default, so packets are not copied anywhere.
+    /// Use a `MemoryHistoryStore` for capped in-memory retention or a
+    /// `FileHistoryStore` to keep history out of RAM.
+    pub fn with_history_store(mut self, store: Arc<dyn HistoryStore>) -> Self {
+        self.history = Some(store);
+        self
+    }
+
+    /// Get the attached history store, if any
+    pub fn history_store(&self) -> Option<Arc<dyn HistoryStore>> {
+        self.history.clone()
+    }
+
+    /// Maximum number of sensor readings kept
+    pub fn capacity(&self) -> usize {
+        self.reading_capacity
+    }
+
+    /// Record a sensor reading, evicting the oldest once at capacity
     pub async fn record_sensor_reading(&self, reading: SensorReading) {
         let mut readings = self.sensor_readings.lock().await;
-        readings.push(reading);
+        readings.push_back(reading);
 
-        // Keep only last 1000 readings
-        if readings.len() > 1000 {
-            readings.remove(0);
+        while readings.len() > self.reading_capacity {
+            readings.pop_front();
         }
     }
 
-    /// Record a diagnostic event
-    pub async fn record_diagnostic(&self, entry: DiagnosticEntry) {
+    /// Drop readings timestamped more than `age` ago, returning how many
+    pub async fn prune_older_than(&self, age: Duration) -> usize {
+        let Some(cutoff) = chrono::Duration::from_std(age)
+            .ok()
+            .and_then(|age| chrono::Utc::now().checked_sub_signed(age))
+        else {
+            return 0;
+        };
+        let mut readings = self.sensor_readings.lock().await;
+        let before = readings.len();
+        readings.retain(|reading| reading.timestamp >= cutoff);
+        before - readings.len()
+    }
+
+    /// Record a diagnostic event, trimming oversized context
+    pub async fn record_diagnostic(&self, mut entry: DiagnosticEntry) {
+        self.context_limits.enforce(&mut entry);
         let mut diagnostics = self.diagnostics.lock().await;
         diagnostics.add_entry(entry);
     }
@@ -58,16 +137,40 @@ impl TelemetryCollector {
         drop(seq);
 
         let health = self.health.lock().await.clone();
-        let sensor_readings = self.sensor_readings.lock().await.clone();
+        let sensor_readings = self.sensor_readings.lock().await.iter().cloned().collect();
         let diagnostics = self.diagnostics.lock().await.clone();
 
-        TelemetryPacket {
+        let packet = TelemetryPacket {
             sequence,
+            schema_version: SCHEMA_VERSION,
             timestamp: chrono::Utc::now(),
             health,
             sensor_readings,
             diagnostics,
+            metadata: Default::default(),
+        };
+
+        if let Some(ref history) = self.history {
+            if let Err(e) = history.append(&packet).await {
+                tracing::warn!("Failed to persist packet {} to history: {}", sequence, e);
+            }
         }
+
+        packet
+    }
+
+    /// Generate and send a packet every `interval` until `token` is cancelled
+    ///
+    /// Wires the collector straight into a pipeline sender (see
+    /// `StreamingPipeline::get_sender`). The returned handle's `join` or
+    /// `stop` yields the number of packets sent.
+    pub fn spawn_periodic(
+        self: &Arc<Self>,
+        interval: Duration,
+        sender: Sender<TelemetryPacket>,
+        token: CancellationToken,
+    ) -> ProducerHandle {
+        ProducerHandle::spawn_source(sender, interval, token, CollectorSource::new(self.clone()))
     }
 
     /// Get current health status
@@ -81,6 +184,69 @@ impl TelemetryCollector {
         readings.iter().rev().take(limit).cloned().collect()
     }
 
+    /// Get the most recent readings from one component, newest first
+    pub async fn get_readings_for(&self, component_id: &str, limit: usize) -> Vec<SensorReading> {
+        let readings = self.sensor_readings.lock().await;
+        readings
+            .iter()
+            .rev()
+            .filter(|reading| reading.component_id == component_id)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Summarize a component's readings timestamped within the last `window`
+    ///
+    /// Only scalar variants contribute (Temperature, Pressure, Humidity and
+    /// Analog, see `SensorData::scalar_value`); other readings are skipped.
+    /// Returns `None` when no scalar reading falls inside the window.
+    pub async fn aggregate(&self, component_id: &str, window: Duration) -> Option<ReadingStats> {
+        let cutoff = chrono::Duration::from_std(window)
+            .ok()
+            .and_then(|window| chrono::Utc::now().checked_sub_signed(window));
+        let readings = self.sensor_readings.lock().await;
+
+        let mut stats: Option<ReadingStats> = None;
+        let mut sum = 0.0f64;
+        let values = readings
+            .iter()
+            .filter(|reading| reading.component_id == component_id)
+            .filter(|reading| cutoff.is_none_or(|cutoff| reading.timestamp >= cutoff))
+            .filter_map(|reading| reading.data.scalar_value());
+        for value in values {
+            sum += value as f64;
+            let s = stats.get_or_insert(ReadingStats {
+                count: 0,
+                min: value,
+                max: value,
+                mean: 0.0,
+                latest: value,
+            });
+            s.count += 1;
+            s.min = s.min.min(value);
+            s.max = s.max.max(value);
+            s.latest = value;
+        }
+
+        stats.map(|mut s| {
+            s.mean = (sum / s.count as f64) as f32;
+            s
+        })
+    }
+
+    /// List the components with retained readings, in order of first report
+    pub async fn component_ids(&self) -> Vec<String> {
+        let readings = self.sensor_readings.lock().await;
+        let mut ids: Vec<String> = Vec::new();
+        for reading in readings.iter() {
+            if !ids.contains(&reading.component_id) {
+                ids.push(reading.component_id.clone());
+            }
+        }
+        ids
+    }
+
     /// Clear all telemetry data
     pub async fn clear(&self) {
         let mut readings = self.sensor_readings.lock().await;
@@ -119,6 +285,109 @@ mod tests {
         assert_eq!(readings[0].component_id, "test-01");
     }
 
+    #[tokio::test]
+    async fn test_collector_capacity_and_age_pruning() {
+        let collector = TelemetryCollector::with_capacity(3);
+        for i in 0..5 {
+            let mut reading = SensorReading::new(
+                format!("temp-{:02}", i),
+                "Temperature".to_string(),
+                SensorData::Temperature {
+                    value: 20.0 + i as f32,
+                    unit: "°C".to_string(),
+                },
+                i,
+            );
+            if i < 3 {
+                reading.timestamp = chrono::Utc::now() - chrono::Duration::seconds(60);
+            }
+            collector.record_sensor_reading(reading).await;
+        }
+
+        // Only the newest three survive, newest first
+        let readings = collector.get_sensor_readings(10).await;
+        let ids: Vec<_> = readings.iter().map(|r| r.component_id.as_str()).collect();
+        assert_eq!(ids, vec!["temp-04", "temp-03", "temp-02"]);
+
+        assert_eq!(collector.prune_older_than(Duration::from_secs(30)).await, 1);
+        assert_eq!(collector.get_sensor_readings(10).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_collector_readings_by_component() {
+        let collector = TelemetryCollector::new();
+        for i in 0..6u64 {
+            let id = if i % 2 == 0 { "temp-01" } else { "baro-01" };
+            collector
+                .record_sensor_reading(SensorReading::new(
+                    id.to_string(),
+                    "Test Sensor".to_string(),
+                    SensorData::Analog {
+                        value: i as f32,
+                        unit: String::new(),
+                    },
+                    i,
+                ))
+                .await;
+        }
+
+        let temps = collector.get_readings_for("temp-01", 2).await;
+        let sequences: Vec<_> = temps.iter().map(|r| r.sequence).collect();
+        assert_eq!(sequences, vec![4, 2]);
+        assert!(collector.get_readings_for("gps-01", 5).await.is_empty());
+        assert_eq!(collector.component_ids().await, vec!["temp-01", "baro-01"]);
+    }
+
+    #[tokio::test]
+    async fn test_collector_aggregate_over_window() {
+        let collector = TelemetryCollector::new();
+        let temperature = |value: f32, age_secs: i64| {
+            let mut reading = SensorReading::new(
+                "temp-01".to_string(),
+                "Temperature".to_string(),
+                SensorData::Temperature {
+                    value,
+                    unit: "°C".to_string(),
+                },
+                0,
+            );
+            reading.timestamp = chrono::Utc::now() - chrono::Duration::seconds(age_secs);
+            reading
+        };
+        collector
+            .record_sensor_reading(temperature(-40.0, 120))
+            .await;
+        collector.record_sensor_reading(temperature(20.0, 5)).await;
+        collector.record_sensor_reading(temperature(26.0, 3)).await;
+        collector
+            .record_sensor_reading(SensorReading::new(
+                "temp-01".to_string(),
+                "Temperature".to_string(),
+                SensorData::Digital {
+                    state: true,
+                    label: "overheat".to_string(),
+                },
+                0,
+            ))
+            .await;
+        collector.record_sensor_reading(temperature(22.0, 1)).await;
+
+        let stats = collector
+            .aggregate("temp-01", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.min, 20.0);
+        assert_eq!(stats.max, 26.0);
+        assert!((stats.mean - 22.666_666).abs() < 1e-4);
+        assert_eq!(stats.latest, 22.0);
+
+        assert!(collector
+            .aggregate("baro-01", Duration::from_secs(60))
+            .await
+            .is_none());
+    }
+
     #[tokio::test]
     async fn test_collector_diagnostic() {
         let collector = TelemetryCollector::new();
@@ -150,4 +419,47 @@ mod tests {
         assert_eq!(p1.sequence, 1);
         assert_eq!(p2.sequence, 2);
     }
+
+    #[tokio::test]
+    async fn test_collector_writes_history() {
+        let store = Arc::new(crate::history::MemoryHistoryStore::new());
+        let collector = TelemetryCollector::new().with_history_store(store.clone());
+
+        let start = chrono::Utc::now();
+        collector.generate_packet().await;
+        collector.generate_packet().await;
+
+        let history = store
+            .query(start..chrono::Utc::now() + chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_collector_keeps_no_history_by_default() {
+        let collector = TelemetryCollector::new();
+        collector.generate_packet().await;
+        assert!(collector.history_store().is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_spawn_periodic_emits_on_schedule() {
+        let collector = Arc::new(TelemetryCollector::new());
+        let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+        let token = CancellationToken::new();
+
+        let producer = collector.spawn_periodic(Duration::from_millis(20), tx, token.clone());
+        tokio::time::sleep(Duration::from_millis(110)).await;
+        token.cancel();
+        let produced = producer.join().await;
+
+        // One packet immediately, then one per 20ms interval: 0, 20, ..., 100ms
+        assert_eq!(produced, 6);
+        let mut sequences = Vec::new();
+        while let Ok(packet) = rx.try_recv() {
+            sequences.push(packet.sequence);
+        }
+        assert_eq!(sequences, (1..=produced).collect::<Vec<_>>());
+    }
 }
@@ -3,14 +3,44 @@
 //! Supports loading configuration from:
 //! - YAML files (config/telemetry.yaml)
 //! - TOML files (config/telemetry.toml)
+//! - JSON files (config/telemetry.json)
 //! - Environment variables (TELEMETRY_* prefix)
+//! - Command-line arguments (`--telemetry-*` flags)
 //! - Programmatic defaults
+//!
+//! Precedence, highest first: CLI args > environment > file > defaults.
+//!
+//! Supported environment variables, each ignored if its value does not parse:
+//! `TELEMETRY_PIPELINE_BATCH_SIZE`, `TELEMETRY_PIPELINE_BATCH_TIMEOUT_SECS`,
+//! `TELEMETRY_PIPELINE_ENABLE_COMPRESSION`, `TELEMETRY_PIPELINE_ENABLE_RESILIENCE`,
+//! `TELEMETRY_PIPELINE_CHANNEL_CAPACITY`, `TELEMETRY_RESILIENCE_MAX_RETRIES`,
+//! `TELEMETRY_RESILIENCE_INITIAL_BACKOFF_MS`, `TELEMETRY_RESILIENCE_MAX_BACKOFF_MS`,
+//! `TELEMETRY_RESILIENCE_BACKOFF_MULTIPLIER`, `TELEMETRY_RESILIENCE_FAILURE_THRESHOLD`,
+//! `TELEMETRY_RESILIENCE_HALF_OPEN_TIMEOUT_SECS`, `TELEMETRY_RESILIENCE_BUFFER_SIZE`,
+//! `TELEMETRY_APP_NAME` and `TELEMETRY_LOG_LEVEL`.
+//!
+//! `ConfigLoader::watch` additionally reloads the config file whenever it
+//! changes, for tuning a long-running service without a restart.
+//!
+//! Supported flags, each taking a value as `--flag=value` or `--flag value`:
+//! `--telemetry-batch-size`, `--telemetry-batch-timeout-secs`,
+//! `--telemetry-enable-compression`, `--telemetry-enable-resilience`,
+//! `--telemetry-channel-capacity`, `--telemetry-max-retries`,
+//! `--telemetry-failure-threshold`, `--telemetry-buffer-size`,
+//! `--telemetry-app-name` and `--telemetry-log-level`. Arguments without the
+//! `--telemetry-` prefix are ignored so the full process argv can be passed.
 
 use crate::resilience::ResilienceConfig;
 use crate::PipelineConfig;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::{mpsc, watch};
+
+/// Quiet time after a file event before reloading, so editors finish writing
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(100);
 
 /// Configuration error types
 #[derive(Error, Debug)]
@@ -23,10 +53,35 @@ pub enum ConfigError {
     Invalid(String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Failed to watch config file: {0}")]
+    Watch(String),
+}
+
+/// File format of a saved configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfigFormat {
+    /// Choose from the file extension (`.yaml`/`.yml`, `.toml` or `.json`)
+    #[default]
+    Auto,
+    Yaml,
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// Format implied by the extension of `path`, if recognised
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
 }
 
 /// Complete telemetry system configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TelemetryConfig {
     /// Pipeline configuration
     pub pipeline: PipelineConfig,
@@ -38,6 +93,163 @@ pub struct TelemetryConfig {
     /// Log level (trace, debug, info, warn, error)
     #[serde(default = "default_log_level")]
     pub log_level: String,
+    /// Transports the pipeline should send to
+    #[serde(default)]
+    pub transports: Vec<TransportSpec>,
+}
+
+/// Accepted values of `TelemetryConfig::log_level`
+const LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+
+impl TelemetryConfig {
+    /// Check for values the pipeline cannot work with
+    ///
+    /// Returns `ConfigError::Invalid` naming the first offending field.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let pipeline = &self.pipeline;
+        if pipeline.batch_size < 1 {
+            return Err(ConfigError::Invalid(
+                "pipeline.batch_size must be at least 1".to_string(),
+            ));
+        }
+        if pipeline.channel_capacity < pipeline.batch_size {
+            return Err(ConfigError::Invalid(format!(
+                "pipeline.channel_capacity ({}) must be at least pipeline.batch_size ({})",
+                pipeline.channel_capacity, pipeline.batch_size
+            )));
+        }
+
+        let resilience = &self.resilience;
+        if resilience.backoff_multiplier.is_nan() || resilience.backoff_multiplier <= 1.0 {
+            return Err(ConfigError::Invalid(format!(
+                "resilience.backoff_multiplier must be greater than 1.0 (got {})",
+                resilience.backoff_multiplier
+            )));
+        }
+        if resilience.max_backoff_ms < resilience.initial_backoff_ms {
+            return Err(ConfigError::Invalid(format!(
+                "resilience.max_backoff_ms ({}) must be at least resilience.initial_backoff_ms ({})",
+                resilience.max_backoff_ms, resilience.initial_backoff_ms
+            )));
+        }
+
+        if !LOG_LEVELS.contains(&self.log_level.to_ascii_lowercase().as_str()) {
+            return Err(ConfigError::Invalid(format!(
+                "log_level must be one of {} (got {:?})",
+                LOG_LEVELS.join(", "),
+                self.log_level
+            )));
+        }
+        Ok(())
+    }
+
+    /// Write this configuration to `path`, e.g. to record the effective
+    /// settings after overrides
+    ///
+    /// With `ConfigFormat::Auto` the format follows the file extension. The
+    /// file loads back into an equal configuration via `ConfigLoader`.
+    pub fn save_to(&self, path: &Path, format: ConfigFormat) -> Result<(), ConfigError> {
+        let format = match format {
+            ConfigFormat::Auto => ConfigFormat::from_path(path).ok_or_else(|| {
+                ConfigError::Invalid(format!(
+                    "cannot infer config format from {}",
+                    path.display()
+                ))
+            })?,
+            explicit => explicit,
+        };
+        let content = match format {
+            ConfigFormat::Yaml | ConfigFormat::Auto => serde_yaml::to_string(self)
+                .map_err(|e| ConfigError::ParseError(format!("YAML serialization error: {}", e)))?,
+            ConfigFormat::Toml => toml::to_string_pretty(self)
+                .map_err(|e| ConfigError::ParseError(format!("TOML serialization error: {}", e)))?,
+            ConfigFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| ConfigError::ParseError(format!("JSON serialization error: {}", e)))?,
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// Declarative description of a pipeline transport
+///
+/// The MQTT and serial adapters are file-backed unless an MQTT `broker` is
+/// given; `out_path` selects the file they append JSON lines to (their
+/// built-in default is used when omitted). `sanitize_non_finite` replaces
+/// NaN/infinite floats with 0.0 before writing to a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum TransportSpec {
+    /// MQTT adapter, publishing to `broker` if given (requires the
+    /// `mqtt_real` feature) and appending to `out_path` otherwise
+    Mqtt {
+        #[serde(default)]
+        out_path: Option<PathBuf>,
+        #[serde(default)]
+        sanitize_non_finite: bool,
+        #[serde(default)]
+        broker: Option<MqttBrokerSpec>,
+    },
+    /// Serial/UART adapter
+    Serial {
+        #[serde(default)]
+        out_path: Option<PathBuf>,
+        #[serde(default)]
+        sanitize_non_finite: bool,
+    },
+    /// JSON lines appended to `path`, rotated once the file would exceed
+    /// `max_bytes` (never rotated when omitted), keeping `max_files` old files
+    File {
+        path: PathBuf,
+        #[serde(default)]
+        max_bytes: Option<u64>,
+        #[serde(default = "default_max_files")]
+        max_files: usize,
+        #[serde(default)]
+        sanitize_non_finite: bool,
+    },
+}
+
+/// MQTT broker connection parameters for `TransportSpec::Mqtt`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MqttBrokerSpec {
+    /// Broker host name or address
+    pub host: String,
+    /// Broker port (1883 by default, usually 8883 with TLS)
+    #[serde(default = "default_mqtt_port")]
+    pub port: u16,
+    /// Client id, generated from the process id when omitted
+    #[serde(default)]
+    pub client_id: Option<String>,
+    /// Topic telemetry is published to
+    #[serde(default = "default_mqtt_topic")]
+    pub topic: String,
+    /// QoS level (0, 1, or 2)
+    #[serde(default = "default_mqtt_qos")]
+    pub qos: u8,
+    /// Connect over TLS using the system CA store
+    #[serde(default)]
+    pub use_tls: bool,
+}
+
+fn default_max_files() -> usize {
+    5
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_mqtt_topic() -> String {
+    "telemetry/system".to_string()
+}
+
+fn default_mqtt_qos() -> u8 {
+    1
 }
 
 fn default_app_name() -> String {
@@ -55,13 +267,18 @@ impl Default for TelemetryConfig {
             resilience: ResilienceConfig::default(),
             app_name: default_app_name(),
             log_level: default_log_level(),
+            transports: Vec::new(),
         }
     }
 }
 
-/// Configuration loader with precedence: YAML/TOML > Env > Defaults
+/// Configuration loader with precedence: CLI args > Env > YAML/TOML > Defaults
+#[derive(Debug, Clone)]
 pub struct ConfigLoader {
     config_dirs: Vec<PathBuf>,
+    args: Vec<String>,
+    /// Variables read instead of the process environment, if set
+    env: Option<HashMap<String, String>>,
 }
 
 impl ConfigLoader {
@@ -73,6 +290,8 @@ impl ConfigLoader {
                 PathBuf::from("."),
                 PathBuf::from("/etc/telemetry"),
             ],
+            args: Vec::new(),
+            env: None,
         }
     }
 
@@ -82,49 +301,176 @@ impl ConfigLoader {
         self
     }
 
-    /// Load configuration from files or environment
-    pub async fn load(&self) -> Result<TelemetryConfig, ConfigError> {
-        // Try to load from YAML or TOML files
-        if let Ok(config) = self.load_from_files().await {
-            // Override with environment variables
-            return Ok(self.apply_env_overrides(config).await);
-        }
+    /// Apply `--telemetry-*` command-line flags on top of files and environment
+    ///
+    /// Typically called with `std::env::args().collect::<Vec<_>>()`.
+    pub fn with_args(mut self, args: &[String]) -> Self {
+        self.args = args.to_vec();
+        self
+    }
 
-        // Fall back to environment variables only
-        Ok(self.apply_env_overrides(TelemetryConfig::default()).await)
+    /// Read `TELEMETRY_*` overrides from `vars` instead of the process
+    /// environment
+    ///
+    /// Useful for tests and embedders that must not depend on, or mutate,
+    /// the shared process environment.
+    pub fn with_env<K, V>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env = Some(
+            vars.into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        );
+        self
     }
 
-    /// Load from YAML or TOML files
-    async fn load_from_files(&self) -> Result<TelemetryConfig, ConfigError> {
-        for config_dir in &self.config_dirs {
-            // Try YAML first
-            let yaml_path = config_dir.join("telemetry.yaml");
-            if yaml_path.exists() {
-                return self.load_yaml(&yaml_path).await;
-            }
+    /// Load configuration from files, environment and command-line arguments
+    ///
+    /// The merged configuration is checked with `TelemetryConfig::validate`.
+    pub async fn load(&self) -> Result<TelemetryConfig, ConfigError> {
+        // Try to load from YAML or TOML files, falling back to defaults
+        let config = self.load_from_files().await.unwrap_or_default();
+        self.apply_overrides(config).await
+    }
 
-            let yaml_alt_path = config_dir.join("telemetry.yml");
-            if yaml_alt_path.exists() {
-                return self.load_yaml(&yaml_alt_path).await;
+    /// Load configuration and keep watching the discovered config file
+    ///
+    /// Returns the initial configuration and a receiver that is updated each
+    /// time the file changes, with environment and command-line overrides
+    /// re-applied. A reloaded file that fails to parse is logged and ignored,
+    /// keeping the last good configuration. Watching stops once every
+    /// receiver has been dropped.
+    pub async fn watch(
+        &self,
+    ) -> Result<(TelemetryConfig, watch::Receiver<TelemetryConfig>), ConfigError> {
+        let path = self.find_config_file().ok_or_else(|| {
+            ConfigError::FileNotFound(
+                "No telemetry.yaml, telemetry.toml or telemetry.json found".to_string(),
+            )
+        })?;
+        let initial = self.load().await?;
+        let (tx, rx) = watch::channel(initial.clone());
+
+        // Watch the directory rather than the file, since editors often
+        // replace a file by renaming a new one over it
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = event_tx.send(event);
+        })
+        .map_err(|e| ConfigError::Watch(e.to_string()))?;
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        notify::Watcher::watch(&mut watcher, &dir, notify::RecursiveMode::NonRecursive)
+            .map_err(|e| ConfigError::Watch(e.to_string()))?;
+
+        let loader = self.clone();
+        tokio::spawn(async move {
+            let _watcher = watcher;
+            loop {
+                let event: notify::Result<notify::Event> = tokio::select! {
+                    _ = tx.closed() => break,
+                    event = event_rx.recv() => match event {
+                        Some(event) => event,
+                        None => break,
+                    },
+                };
+                match event {
+                    Ok(event)
+                        if !event.kind.is_access()
+                            && event
+                                .paths
+                                .iter()
+                                .any(|p| p.file_name() == path.file_name()) => {}
+                    Ok(_) => continue,
+                    Err(e) => {
+                        tracing::warn!("Config watcher error: {}", e);
+                        continue;
+                    }
+                }
+
+                // Coalesce the burst of events a single save produces
+                tokio::time::sleep(RELOAD_DEBOUNCE).await;
+                while event_rx.try_recv().is_ok() {}
+
+                match loader.reload(&path).await {
+                    Ok(config) => {
+                        tracing::info!("Reloaded configuration from {}", path.display());
+                        tx.send_replace(config);
+                    }
+                    Err(e) => tracing::warn!(
+                        "Ignoring invalid config reload from {}: {}",
+                        path.display(),
+                        e
+                    ),
+                }
             }
+        });
 
-            // Try TOML
-            let toml_path = config_dir.join("telemetry.toml");
-            if toml_path.exists() {
-                return self.load_toml(&toml_path).await;
-            }
+        Ok((initial, rx))
+    }
+
+    /// Re-read `path` and apply environment and command-line overrides
+    async fn reload(&self, path: &Path) -> Result<TelemetryConfig, ConfigError> {
+        let config = self.load_path(path).await?;
+        self.apply_overrides(config).await
+    }
+
+    /// Override file values with environment variables, then command-line
+    /// flags, and validate the result
+    async fn apply_overrides(
+        &self,
+        config: TelemetryConfig,
+    ) -> Result<TelemetryConfig, ConfigError> {
+        let config = self.apply_env_overrides(config).await;
+        let config = self.apply_arg_overrides(config)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Find the first config file in the search directories
+    fn find_config_file(&self) -> Option<PathBuf> {
+        self.config_dirs.iter().find_map(|config_dir| {
+            [
+                "telemetry.yaml",
+                "telemetry.yml",
+                "telemetry.toml",
+                "telemetry.json",
+            ]
+            .iter()
+            .map(|name| config_dir.join(name))
+            .find(|path| path.exists())
+        })
+    }
+
+    /// Load YAML or TOML files
+    async fn load_from_files(&self) -> Result<TelemetryConfig, ConfigError> {
+        match self.find_config_file() {
+            Some(path) => self.load_path(&path).await,
+            None => Err(ConfigError::FileNotFound(
+                "No telemetry.yaml, telemetry.toml or telemetry.json found".to_string(),
+            )),
         }
+    }
 
-        Err(ConfigError::FileNotFound(
-            "No telemetry.yaml or telemetry.toml found".to_string(),
-        ))
+    /// Load a config file, choosing the format by extension
+    async fn load_path(&self, path: &Path) -> Result<TelemetryConfig, ConfigError> {
+        match ConfigFormat::from_path(path) {
+            Some(ConfigFormat::Toml) => self.load_toml(path).await,
+            Some(ConfigFormat::Json) => self.load_json(path).await,
+            _ => self.load_yaml(path).await,
+        }
     }
 
     /// Load YAML configuration file
     async fn load_yaml(&self, path: &Path) -> Result<TelemetryConfig, ConfigError> {
         let content = tokio::fs::read_to_string(path)
             .await
-            .map_err(|e| ConfigError::Io(e))?;
+            .map_err(ConfigError::Io)?;
 
         serde_yaml::from_str(&content)
             .map_err(|e| ConfigError::ParseError(format!("YAML deserialization error: {}", e)))
@@ -134,72 +480,172 @@ impl ConfigLoader {
     async fn load_toml(&self, path: &Path) -> Result<TelemetryConfig, ConfigError> {
         let content = tokio::fs::read_to_string(path)
             .await
-            .map_err(|e| ConfigError::Io(e))?;
+            .map_err(ConfigError::Io)?;
 
         toml::from_str(&content)
             .map_err(|e| ConfigError::ParseError(format!("TOML parse error: {}", e)))
     }
 
+    /// Load JSON configuration file
+    async fn load_json(&self, path: &Path) -> Result<TelemetryConfig, ConfigError> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(ConfigError::Io)?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| ConfigError::ParseError(format!("JSON parse error: {}", e)))
+    }
+
     /// Apply environment variable overrides
-    /// Supports: TELEMETRY_PIPELINE_BATCH_SIZE, TELEMETRY_LOG_LEVEL, etc.
+    /// Supports the `TELEMETRY_*` variables listed in the module docs.
     async fn apply_env_overrides(&self, mut config: TelemetryConfig) -> TelemetryConfig {
         // Pipeline overrides
-        if let Ok(batch_size) = std::env::var("TELEMETRY_PIPELINE_BATCH_SIZE") {
+        if let Some(batch_size) = self.env_var("TELEMETRY_PIPELINE_BATCH_SIZE") {
             if let Ok(size) = batch_size.parse::<usize>() {
                 config.pipeline.batch_size = size;
             }
         }
 
-        if let Ok(timeout) = std::env::var("TELEMETRY_PIPELINE_BATCH_TIMEOUT_SECS") {
+        if let Some(timeout) = self.env_var("TELEMETRY_PIPELINE_BATCH_TIMEOUT_SECS") {
             if let Ok(secs) = timeout.parse::<u64>() {
                 config.pipeline.batch_timeout_secs = secs;
             }
         }
 
-        if let Ok(compression) = std::env::var("TELEMETRY_PIPELINE_ENABLE_COMPRESSION") {
+        if let Some(compression) = self.env_var("TELEMETRY_PIPELINE_ENABLE_COMPRESSION") {
             config.pipeline.enable_compression = compression.to_lowercase() == "true";
         }
 
-        if let Ok(resilience) = std::env::var("TELEMETRY_PIPELINE_ENABLE_RESILIENCE") {
+        if let Some(resilience) = self.env_var("TELEMETRY_PIPELINE_ENABLE_RESILIENCE") {
             config.pipeline.enable_resilience = resilience.to_lowercase() == "true";
         }
 
-        if let Ok(capacity) = std::env::var("TELEMETRY_PIPELINE_CHANNEL_CAPACITY") {
+        if let Some(capacity) = self.env_var("TELEMETRY_PIPELINE_CHANNEL_CAPACITY") {
             if let Ok(cap) = capacity.parse::<usize>() {
                 config.pipeline.channel_capacity = cap;
             }
         }
 
         // Resilience overrides
-        if let Ok(retries) = std::env::var("TELEMETRY_RESILIENCE_MAX_RETRIES") {
+        if let Some(retries) = self.env_var("TELEMETRY_RESILIENCE_MAX_RETRIES") {
             if let Ok(r) = retries.parse::<u32>() {
                 config.resilience.max_retries = r;
             }
         }
 
-        if let Ok(threshold) = std::env::var("TELEMETRY_RESILIENCE_FAILURE_THRESHOLD") {
+        if let Some(initial) = self.env_var("TELEMETRY_RESILIENCE_INITIAL_BACKOFF_MS") {
+            if let Ok(ms) = initial.parse::<u64>() {
+                config.resilience.initial_backoff_ms = ms;
+            }
+        }
+
+        if let Some(max) = self.env_var("TELEMETRY_RESILIENCE_MAX_BACKOFF_MS") {
+            if let Ok(ms) = max.parse::<u64>() {
+                config.resilience.max_backoff_ms = ms;
+            }
+        }
+
+        if let Some(multiplier) = self.env_var("TELEMETRY_RESILIENCE_BACKOFF_MULTIPLIER") {
+            if let Ok(m) = multiplier.parse::<f64>() {
+                config.resilience.backoff_multiplier = m;
+            }
+        }
+
+        if let Some(threshold) = self.env_var("TELEMETRY_RESILIENCE_FAILURE_THRESHOLD") {
             if let Ok(t) = threshold.parse::<u32>() {
                 config.resilience.failure_threshold = t;
             }
         }
 
-        if let Ok(buffer) = std::env::var("TELEMETRY_RESILIENCE_BUFFER_SIZE") {
+        if let Some(timeout) = self.env_var("TELEMETRY_RESILIENCE_HALF_OPEN_TIMEOUT_SECS") {
+            if let Ok(secs) = timeout.parse::<u64>() {
+                config.resilience.half_open_timeout_secs = secs;
+            }
+        }
+
+        if let Some(buffer) = self.env_var("TELEMETRY_RESILIENCE_BUFFER_SIZE") {
             if let Ok(size) = buffer.parse::<usize>() {
                 config.resilience.buffer_size = size;
             }
         }
 
         // App overrides
-        if let Ok(name) = std::env::var("TELEMETRY_APP_NAME") {
+        if let Some(name) = self.env_var("TELEMETRY_APP_NAME") {
             config.app_name = name;
         }
 
-        if let Ok(level) = std::env::var("TELEMETRY_LOG_LEVEL") {
+        if let Some(level) = self.env_var("TELEMETRY_LOG_LEVEL") {
             config.log_level = level;
         }
 
         config
     }
+
+    /// Look up an environment variable, from the injected variables if any
+    fn env_var(&self, name: &str) -> Option<String> {
+        match self.env {
+            Some(ref vars) => vars.get(name).cloned(),
+            None => std::env::var(name).ok(),
+        }
+    }
+
+    /// Apply command-line flag overrides
+    ///
+    /// Unlike environment variables, an unknown `--telemetry-*` flag or an
+    /// unparseable value is an error so that typos at launch are not ignored.
+    fn apply_arg_overrides(
+        &self,
+        mut config: TelemetryConfig,
+    ) -> Result<TelemetryConfig, ConfigError> {
+        let mut args = self.args.iter();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--telemetry-") else {
+                continue;
+            };
+            let (name, value) = match flag.split_once('=') {
+                Some((name, value)) => (name, value.to_string()),
+                None => {
+                    let value = args.next().ok_or_else(|| {
+                        ConfigError::Invalid(format!("missing value for {}", arg))
+                    })?;
+                    (flag, value.clone())
+                }
+            };
+
+            match name {
+                "batch-size" => config.pipeline.batch_size = parse_arg(name, &value)?,
+                "batch-timeout-secs" => {
+                    config.pipeline.batch_timeout_secs = parse_arg(name, &value)?
+                }
+                "enable-compression" => {
+                    config.pipeline.enable_compression = parse_arg(name, &value)?
+                }
+                "enable-resilience" => config.pipeline.enable_resilience = parse_arg(name, &value)?,
+                "channel-capacity" => config.pipeline.channel_capacity = parse_arg(name, &value)?,
+                "max-retries" => config.resilience.max_retries = parse_arg(name, &value)?,
+                "failure-threshold" => {
+                    config.resilience.failure_threshold = parse_arg(name, &value)?
+                }
+                "buffer-size" => config.resilience.buffer_size = parse_arg(name, &value)?,
+                "app-name" => config.app_name = value,
+                "log-level" => config.log_level = value,
+                _ => {
+                    return Err(ConfigError::Invalid(format!(
+                        "unknown flag --telemetry-{}",
+                        name
+                    )))
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Parse the value of a `--telemetry-<name>` flag
+fn parse_arg<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, ConfigError> {
+    value.parse().map_err(|_| {
+        ConfigError::Invalid(format!("invalid value for --telemetry-{}: {}", name, value))
+    })
 }
 
 impl Default for ConfigLoader {
@@ -231,4 +677,196 @@ mod tests {
         let loader = ConfigLoader::new().with_config_dir("/custom/path");
         assert_eq!(loader.config_dirs[0], PathBuf::from("/custom/path"));
     }
+
+    #[tokio::test]
+    async fn test_cli_args_override_env_and_file() {
+        let config_dir = PathBuf::from("target/test_output/config_cli_args");
+        tokio::fs::create_dir_all(&config_dir).await.unwrap();
+        let yaml = r#"
+pipeline:
+  channel_capacity: 100
+  batch_size: 10
+  batch_timeout_secs: 5
+  enable_compression: false
+  enable_resilience: true
+resilience:
+  max_retries: 3
+  initial_backoff_ms: 100
+  max_backoff_ms: 1000
+  backoff_multiplier: 2.0
+  failure_threshold: 5
+  half_open_timeout_secs: 30
+  buffer_size: 100
+log_level: warn
+"#;
+        tokio::fs::write(config_dir.join("telemetry.yaml"), yaml)
+            .await
+            .unwrap();
+
+        let args: Vec<String> = [
+            "app",
+            "--telemetry-log-level=debug",
+            "--telemetry-batch-size",
+            "25",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let config = ConfigLoader::new()
+            .with_config_dir(&config_dir)
+            .with_env([("TELEMETRY_LOG_LEVEL", "error")])
+            .with_args(&args)
+            .load()
+            .await
+            .unwrap();
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.pipeline.batch_size, 25);
+        assert_eq!(config.resilience.max_retries, 3);
+
+        let bad = ConfigLoader::new()
+            .with_config_dir(&config_dir)
+            .with_args(&["--telemetry-batch-size=lots".to_string()])
+            .load()
+            .await;
+        assert!(matches!(bad, Err(ConfigError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_env_overrides_resilience_backoff() {
+        let config_dir = PathBuf::from("target/test_output/config_env_backoff");
+        let _ = tokio::fs::remove_dir_all(&config_dir).await;
+        TelemetryConfig::default()
+            .save_to(&config_dir.join("telemetry.yaml"), ConfigFormat::Auto)
+            .unwrap();
+
+        let config = ConfigLoader::new()
+            .with_config_dir(&config_dir)
+            .with_env([
+                ("TELEMETRY_RESILIENCE_INITIAL_BACKOFF_MS", "50"),
+                ("TELEMETRY_RESILIENCE_MAX_BACKOFF_MS", "500"),
+                ("TELEMETRY_RESILIENCE_BACKOFF_MULTIPLIER", "fast"),
+                ("TELEMETRY_RESILIENCE_HALF_OPEN_TIMEOUT_SECS", "7"),
+            ])
+            .load()
+            .await
+            .unwrap();
+
+        assert_eq!(config.resilience.initial_backoff_ms, 50);
+        assert_eq!(config.resilience.max_backoff_ms, 500);
+        // Unparseable values keep the previous setting
+        assert_eq!(
+            config.resilience.backoff_multiplier,
+            ResilienceConfig::default().backoff_multiplier
+        );
+        assert_eq!(config.resilience.half_open_timeout_secs, 7);
+    }
+
+    #[test]
+    fn test_validate_names_offending_field() {
+        assert!(TelemetryConfig::default().validate().is_ok());
+
+        type Breaker = fn(&mut TelemetryConfig);
+        let cases: [(&str, Breaker); 5] = [
+            ("pipeline.batch_size", |c| c.pipeline.batch_size = 0),
+            ("pipeline.channel_capacity", |c| {
+                c.pipeline.channel_capacity = c.pipeline.batch_size - 1
+            }),
+            ("resilience.backoff_multiplier", |c| {
+                c.resilience.backoff_multiplier = 1.0
+            }),
+            ("resilience.max_backoff_ms", |c| {
+                c.resilience.max_backoff_ms = c.resilience.initial_backoff_ms - 1
+            }),
+            ("log_level", |c| c.log_level = "verbose".to_string()),
+        ];
+        for (field, break_config) in cases {
+            let mut config = TelemetryConfig::default();
+            break_config(&mut config);
+            match config.validate() {
+                Err(ConfigError::Invalid(message)) => {
+                    assert!(message.starts_with(field), "{}: {}", field, message)
+                }
+                other => panic!("{}: expected Invalid, got {:?}", field, other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_to_round_trips_every_format() {
+        let mut config = TelemetryConfig {
+            app_name: "saved".to_string(),
+            log_level: "debug".to_string(),
+            transports: vec![TransportSpec::Serial {
+                out_path: Some(PathBuf::from("out/serial.log")),
+                sanitize_non_finite: true,
+            }],
+            ..Default::default()
+        };
+        config.pipeline.batch_size = 7;
+        config.resilience.backoff_multiplier = 1.5;
+
+        for (index, name) in ["telemetry.yaml", "telemetry.toml", "telemetry.json"]
+            .iter()
+            .enumerate()
+        {
+            let config_dir = PathBuf::from(format!("target/test_output/config_save_{}", index));
+            let _ = tokio::fs::remove_dir_all(&config_dir).await;
+            config
+                .save_to(&config_dir.join(name), ConfigFormat::Auto)
+                .unwrap();
+
+            let loaded = ConfigLoader::new()
+                .with_config_dir(&config_dir)
+                .with_env(HashMap::<String, String>::new())
+                .load()
+                .await
+                .unwrap();
+            assert_eq!(loaded, config, "{}", name);
+        }
+
+        let unknown = config.save_to(
+            Path::new("target/test_output/config.ini"),
+            ConfigFormat::Auto,
+        );
+        assert!(matches!(unknown, Err(ConfigError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_watch_reloads_and_keeps_last_good_config() {
+        let config_dir = PathBuf::from("target/test_output/config_watch");
+        tokio::fs::create_dir_all(&config_dir).await.unwrap();
+        let path = config_dir.join("telemetry.toml");
+        let toml_with_batch = |batch_size: usize| {
+            format!(
+                "[pipeline]\nchannel_capacity = 100\nbatch_size = {}\nbatch_timeout_secs = 5\n\
+                 enable_compression = false\nenable_resilience = true\n\n\
+                 [resilience]\nmax_retries = 3\ninitial_backoff_ms = 100\nmax_backoff_ms = 1000\n\
+                 backoff_multiplier = 2.0\nfailure_threshold = 5\nhalf_open_timeout_secs = 30\n\
+                 buffer_size = 100\n",
+                batch_size
+            )
+        };
+        tokio::fs::write(&path, toml_with_batch(10)).await.unwrap();
+
+        let (initial, mut rx) = ConfigLoader::new()
+            .with_config_dir(&config_dir)
+            .watch()
+            .await
+            .unwrap();
+        assert_eq!(initial.pipeline.batch_size, 10);
+
+        tokio::fs::write(&path, toml_with_batch(42)).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), rx.changed())
+            .await
+            .expect("no reload after edit")
+            .unwrap();
+        assert_eq!(rx.borrow_and_update().pipeline.batch_size, 42);
+
+        tokio::fs::write(&path, "pipeline = [not valid")
+            .await
+            .unwrap();
+        tokio::time::sleep(RELOAD_DEBOUNCE * 5).await;
+        assert!(!rx.has_changed().unwrap());
+        assert_eq!(rx.borrow().pipeline.batch_size, 42);
+    }
 }
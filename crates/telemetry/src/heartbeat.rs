@@ -0,0 +1,98 @@
+//! Heartbeats for idle devices
+//!
+//! An idle device sends nothing, which downstream systems cannot tell apart
+//! from a dead one. A `HeartbeatGenerator` produces a minimal packet (see
+//! `TelemetryPacket::heartbeat`) whenever no real telemetry has been seen for
+//! a configured interval. `StreamingPipeline` runs one when
+//! `PipelineConfig::heartbeat` is set.
+
+use crate::TelemetryPacket;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Metadata key marking a packet as a heartbeat
+pub const HEARTBEAT_KEY: &str = "heartbeat";
+/// Metadata key carrying the device id of a heartbeat
+pub const DEVICE_ID_KEY: &str = "device_id";
+
+/// Pipeline heartbeat settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeartbeatConfig {
+    /// Device id carried by each heartbeat
+    pub device_id: String,
+    /// Quiet time after which a heartbeat is sent (milliseconds)
+    pub interval_ms: u64,
+}
+
+/// Emits a heartbeat packet after each quiet interval
+#[derive(Debug)]
+pub struct HeartbeatGenerator {
+    device_id: String,
+    interval: Duration,
+    next_sequence: u64,
+    last_activity: Instant,
+}
+
+impl HeartbeatGenerator {
+    /// Create a generator for `device_id` with the given quiet interval
+    pub fn new(device_id: impl Into<String>, interval: Duration) -> Self {
+        Self {
+            device_id: device_id.into(),
+            interval,
+            next_sequence: 0,
+            last_activity: Instant::now(),
+        }
+    }
+
+    /// Create a generator from pipeline settings
+    pub fn from_config(config: &HeartbeatConfig) -> Self {
+        Self::new(
+            config.device_id.clone(),
+            Duration::from_millis(config.interval_ms),
+        )
+    }
+
+    /// Record that real telemetry was seen, postponing the next heartbeat
+    pub fn record_activity(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// Time left until a heartbeat is due
+    pub fn time_until_due(&self) -> Duration {
+        self.interval.saturating_sub(self.last_activity.elapsed())
+    }
+
+    /// Return a heartbeat if the quiet interval has elapsed, restarting it
+    pub fn poll(&mut self) -> Option<TelemetryPacket> {
+        if !self.time_until_due().is_zero() {
+            return None;
+        }
+        let packet = TelemetryPacket::heartbeat(&self.device_id, self.next_sequence);
+        self.next_sequence += 1;
+        self.last_activity = Instant::now();
+        Some(packet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_heartbeat_only_after_quiet_interval() {
+        let mut generator = HeartbeatGenerator::new("dev-1", Duration::from_millis(50));
+        assert!(generator.poll().is_none());
+
+        tokio::time::advance(Duration::from_millis(20)).await;
+        generator.record_activity();
+        tokio::time::advance(Duration::from_millis(49)).await;
+        assert!(generator.poll().is_none());
+
+        tokio::time::advance(Duration::from_millis(1)).await;
+        let heartbeat = generator.poll().unwrap();
+        assert!(heartbeat.is_heartbeat());
+        assert_eq!(heartbeat.metadata[DEVICE_ID_KEY], "dev-1");
+        assert!(generator.poll().is_none());
+    }
+}
@@ -0,0 +1,212 @@
+//! Pluggable persistence for telemetry history
+//!
+//! A `HistoryStore` receives every packet generated by a `TelemetryCollector`
+//! and can be queried by time range, decoupling retention from RAM.
+
+use crate::types::Timestamp;
+use crate::TelemetryPacket;
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, RwLock};
+
+/// Error type for history store operations
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Storage backend for telemetry history
+#[async_trait]
+pub trait HistoryStore: Send + Sync {
+    /// Append a packet to the history
+    async fn append(&self, packet: &TelemetryPacket) -> Result<(), HistoryError>;
+
+    /// Get all packets whose timestamp falls within `range` (end exclusive)
+    async fn query(&self, range: Range<Timestamp>) -> Result<Vec<TelemetryPacket>, HistoryError>;
+}
+
+/// Number of packets a `MemoryHistoryStore` keeps by default
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100;
+
+/// In-memory history store keeping the most recent packets
+///
+/// Once `capacity` packets are stored, each append evicts the oldest one.
+pub struct MemoryHistoryStore {
+    packets: RwLock<VecDeque<TelemetryPacket>>,
+    capacity: usize,
+}
+
+impl Default for MemoryHistoryStore {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+impl MemoryHistoryStore {
+    /// Create an empty store keeping `DEFAULT_HISTORY_CAPACITY` packets
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty store keeping at most `capacity` packets
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            packets: RwLock::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Maximum number of packets kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[async_trait]
+impl HistoryStore for MemoryHistoryStore {
+    async fn append(&self, packet: &TelemetryPacket) -> Result<(), HistoryError> {
+        let mut packets = self.packets.write().await;
+        packets.push_back(packet.clone());
+        while packets.len() > self.capacity {
+            packets.pop_front();
+        }
+        Ok(())
+    }
+
+    async fn query(&self, range: Range<Timestamp>) -> Result<Vec<TelemetryPacket>, HistoryError> {
+        let packets = self.packets.read().await;
+        Ok(packets
+            .iter()
+            .filter(|p| range.contains(&p.timestamp))
+            .cloned()
+            .collect())
+    }
+}
+
+/// File-backed history store writing one JSON packet per line
+pub struct FileHistoryStore {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl FileHistoryStore {
+    /// Create a store appending to `path`, creating parent directories as needed
+    pub async fn new(path: impl AsRef<Path>) -> Result<Self, HistoryError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        Ok(Self {
+            path,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// Get the backing file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl HistoryStore for FileHistoryStore {
+    async fn append(&self, packet: &TelemetryPacket) -> Result<(), HistoryError> {
+        let mut line = serde_json::to_vec(packet)?;
+        line.push(b'\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(&line).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    async fn query(&self, range: Range<Timestamp>) -> Result<Vec<TelemetryPacket>, HistoryError> {
+        let content = match fs::read_to_string(&self.path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut packets = Vec::new();
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            let packet = TelemetryPacket::from_json(line)?;
+            if range.contains(&packet.timestamp) {
+                packets.push(packet);
+            }
+        }
+        Ok(packets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn test_file_history_store_query_range() {
+        let path = PathBuf::from("target/test_output/history_store.log");
+        let _ = fs::remove_file(&path).await;
+        let store = FileHistoryStore::new(&path).await.unwrap();
+
+        let base = Utc::now();
+        for i in 0..5 {
+            let mut packet = TelemetryPacket::new(i);
+            packet.timestamp = base + Duration::seconds(i as i64 * 10);
+            store.append(&packet).await.unwrap();
+        }
+
+        let result = store
+            .query(base + Duration::seconds(10)..base + Duration::seconds(40))
+            .await
+            .unwrap();
+        let sequences: Vec<u64> = result.iter().map(|p| p.sequence).collect();
+        assert_eq!(sequences, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_memory_history_store() {
+        let store = MemoryHistoryStore::new();
+        let packet = TelemetryPacket::new(1);
+        store.append(&packet).await.unwrap();
+
+        let start = packet.timestamp;
+        let result = store
+            .query(start..start + Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_memory_history_store_evicts_oldest_at_capacity() {
+        let store = MemoryHistoryStore::with_capacity(3);
+        let base = Utc::now();
+        for i in 0..5 {
+            let mut packet = TelemetryPacket::new(i);
+            packet.timestamp = base + Duration::seconds(i as i64);
+            store.append(&packet).await.unwrap();
+        }
+
+        let result = store
+            .query(base..base + Duration::seconds(10))
+            .await
+            .unwrap();
+        let sequences: Vec<u64> = result.iter().map(|p| p.sequence).collect();
+        assert_eq!(sequences, vec![2, 3, 4]);
+    }
+}
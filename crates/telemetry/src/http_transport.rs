@@ -0,0 +1,187 @@
+#![cfg(feature = "http_transport")]
+
+//! HTTP POST transport using `reqwest`.
+//!
+//! Each packet is POSTed as a JSON body to a configurable ingest URL, with
+//! an optional bearer token. Network failures and non-2xx responses are
+//! reported as `TransportError`s so the pipeline's resilience layer can
+//! retry or buffer them.
+
+use crate::TelemetryPacket;
+use async_trait::async_trait;
+use std::time::Duration;
+
+use super::{Transport, TransportError};
+
+/// Configuration for HTTP transport
+#[derive(Clone, Debug)]
+pub struct HttpConfig {
+    /// Ingest endpoint packets are POSTed to
+    pub url: String,
+    /// Per-request timeout in milliseconds
+    pub timeout_ms: u64,
+    /// Bearer token sent in the `Authorization` header, if any
+    pub bearer_token: Option<String>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://127.0.0.1:3030/telemetry".to_string(),
+            timeout_ms: 5_000,
+            bearer_token: None,
+        }
+    }
+}
+
+/// Transport POSTing each packet as JSON to an HTTP endpoint
+pub struct HttpTransport {
+    config: HttpConfig,
+    client: reqwest::Client,
+}
+
+impl HttpTransport {
+    /// Create a transport for `config`
+    pub fn new(config: HttpConfig) -> Result<Self, TransportError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .map_err(|e| TransportError::Other(format!("HTTP client setup failed: {}", e)))?;
+        Ok(Self { config, client })
+    }
+
+    /// Get the transport configuration
+    pub fn config(&self) -> &HttpConfig {
+        &self.config
+    }
+
+    async fn post(&self, json: Vec<u8>) -> Result<(), TransportError> {
+        let mut request = self
+            .client
+            .post(&self.config.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(json);
+        if let Some(ref token) = self.config.bearer_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| TransportError::Other(format!("HTTP POST failed: {}", e)))?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(TransportError::HttpStatus(status.as_u16()))
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+        self.post(serde_json::to_vec(packet)?).await
+    }
+
+    async fn send_bytes(
+        &self,
+        _packet: &TelemetryPacket,
+        json: &[u8],
+    ) -> Result<(), TransportError> {
+        self.post(json.to_vec()).await
+    }
+
+    async fn send_batch_bytes(
+        &self,
+        _packets: &[TelemetryPacket],
+        encoded: &[Vec<u8>],
+    ) -> Result<(), TransportError> {
+        for json in encoded {
+            self.post(json.clone()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Accept one request, reply with `status` and return the raw request text
+    async fn serve_once(status: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/ingest", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut chunk).await.unwrap();
+                request.extend_from_slice(&chunk[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let reply = format!(
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            );
+            socket.write_all(reply.as_bytes()).await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_posts_json_with_bearer_token() {
+        let (url, server) = serve_once("200 OK").await;
+        let transport = HttpTransport::new(HttpConfig {
+            url,
+            bearer_token: Some("secret".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        transport.send(&TelemetryPacket::new(42)).await.unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /ingest "));
+        assert!(request.contains("authorization: Bearer secret"));
+        assert!(request.contains("\"sequence\":42"));
+    }
+
+    #[tokio::test]
+    async fn test_non_success_status_is_an_error() {
+        let (url, server) = serve_once("503 Service Unavailable").await;
+        let transport = HttpTransport::new(HttpConfig {
+            url,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let result = transport.send(&TelemetryPacket::new(1)).await;
+        assert!(matches!(result, Err(TransportError::HttpStatus(503))));
+        server.await.unwrap();
+    }
+}
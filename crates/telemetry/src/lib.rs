@@ -3,31 +3,73 @@
 //! This crate handles logging, metrics, observability, and telemetry schema
 //! for the microservices application.
 
+pub mod aggregator;
+pub mod chain;
 pub mod collector;
 pub mod config;
+pub mod heartbeat;
+pub mod history;
 pub mod logger;
 pub mod metrics;
+pub mod partial;
+pub mod producer;
 pub mod resilience;
+pub mod source;
+pub mod spill;
 pub mod streaming;
 pub mod transports;
 pub mod types;
+pub mod units;
 
+#[cfg(feature = "http_transport")]
+pub mod http_transport;
 #[cfg(feature = "mqtt_real")]
 pub mod mqtt_real;
+#[cfg(feature = "otel")]
+pub mod otel;
+#[cfg(feature = "serial_real")]
+pub mod serial_real;
 
-pub use collector::TelemetryCollector;
-pub use config::{ConfigError, ConfigLoader, TelemetryConfig};
+pub use aggregator::HealthAggregator;
+pub use chain::{verify_chain, HashChain, CHAIN_HASH_KEY};
+pub use collector::{ReadingStats, TelemetryCollector, DEFAULT_READING_CAPACITY};
+pub use config::{
+    ConfigError, ConfigFormat, ConfigLoader, MqttBrokerSpec, TelemetryConfig, TransportSpec,
+};
+pub use heartbeat::{HeartbeatConfig, HeartbeatGenerator};
+pub use history::{
+    FileHistoryStore, HistoryError, HistoryStore, MemoryHistoryStore, DEFAULT_HISTORY_CAPACITY,
+};
 pub use logger::{LogLevel, Logger};
-pub use metrics::Metrics;
+pub use metrics::{HistogramSummary, Metrics};
+pub use partial::PartialTelemetry;
+pub use producer::ProducerHandle;
 pub use resilience::{
-    CircuitBreaker, CircuitState, OfflineBuffer, ResilienceConfig, RetryStrategy,
+    AutoReconnect, CircuitBreaker, CircuitState, OfflineBuffer, OverflowPolicy, ResilienceConfig,
+    RetryStrategy,
+};
+pub use source::{CollectorSource, MockSource, TelemetrySource};
+pub use spill::PersistentOfflineBuffer;
+pub use streaming::{
+    BatchReport, DeliveryPolicy, EncodedBatch, PipelineBuilder, PipelineConfig, PipelineMetrics,
+    PipelineState, StreamingPipeline, TransportMetrics, TransportOutcome,
+};
+pub use transports::{
+    DownsampleMode, DownsampleTransport, FanOutTransport, HashChainTransport, MemoryTransport,
+    MqttTransport, QueueFullPolicy, RotatingFileTransport, SerialTransport, Transport,
+    TransportError, UdpTransport, DEFAULT_UDP_MAX_PAYLOAD,
 };
-pub use streaming::{PipelineConfig, StreamingPipeline};
-pub use transports::{MqttTransport, SerialTransport, Transport, TransportError};
 pub use types::{
-    ComponentId, DiagnosticEntry, DiagnosticLevel, DiagnosticsReport, HealthStatus, SensorData,
-    SensorReading, SystemHealth, TelemetryPacket, Timestamp,
+    ComponentId, ContextLimits, DiagnosticCatalog, DiagnosticDefinition, DiagnosticEntry,
+    DiagnosticLevel, DiagnosticsReport, HealthStatus, PrecisionConfig, SensorData, SensorReading,
+    SystemHealth, TelemetryPacket, TelemetryPacketBuilder, Timestamp, UnsupportedSchemaVersion,
+    DEFAULT_DIAGNOSTICS_CAPACITY, SCHEMA_VERSION,
 };
+pub use units::{Dimension, Quantity, Unit, UnitError};
 
+#[cfg(feature = "http_transport")]
+pub use http_transport::{HttpConfig, HttpTransport};
 #[cfg(feature = "mqtt_real")]
 pub use mqtt_real::{MqttConfig, MqttError, RealMqttTransport};
+#[cfg(feature = "serial_real")]
+pub use serial_real::{RealSerialTransport, SerialConfig, SerialError};
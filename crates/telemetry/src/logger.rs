@@ -1,13 +1,31 @@
 //! Logging utilities
 
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
 /// Simple logger implementation
-#[derive(Debug)]
+///
+/// Messages below the configured level are suppressed; the rest are written
+/// to the logger's sink (stdout by default) as
+/// `<RFC3339 timestamp> [<level>] <message>`, flushing after every line.
 pub struct Logger {
-    #[allow(dead_code)]
-    level: LogLevel,
+    level: AtomicU8,
+    sink: Mutex<Box<dyn Write + Send>>,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl std::fmt::Debug for Logger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Logger")
+            .field("level", &self.level())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Message severity, ordered from least (`Debug`) to most (`Error`) severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
     Debug,
     Info,
@@ -15,12 +33,145 @@ pub enum LogLevel {
     Error,
 }
 
+impl LogLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Debug,
+            1 => Self::Info,
+            2 => Self::Warning,
+            _ => Self::Error,
+        }
+    }
+}
+
 impl Logger {
+    /// Create a logger printing to stdout
     pub fn new(level: LogLevel) -> Self {
-        Self { level }
+        Self::to_writer(level, Box::new(std::io::stdout()))
+    }
+
+    /// Create a logger appending to the file at `path`, creating it if needed
+    pub fn to_file(level: LogLevel, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::to_writer(level, Box::new(file)))
+    }
+
+    /// Create a logger writing to any sink
+    pub fn to_writer(level: LogLevel, sink: Box<dyn Write + Send>) -> Self {
+        Self {
+            level: AtomicU8::new(level as u8),
+            sink: Mutex::new(sink),
+        }
+    }
+
+    /// Get the minimum level that is logged
+    pub fn level(&self) -> LogLevel {
+        LogLevel::from_u8(self.level.load(Ordering::Relaxed))
+    }
+
+    /// Change the minimum level that is logged
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level as u8, Ordering::Relaxed);
+    }
+
+    /// Check whether a message at `level` would be logged
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        level >= self.level()
     }
 
     pub fn log(&self, level: LogLevel, message: &str) {
-        println!("[{:?}] {}", level, message);
+        if !self.enabled(level) {
+            return;
+        }
+        let line = format_line(level, message);
+        // A poisoned lock only means another thread panicked mid-write
+        let mut sink = self.sink.lock().unwrap_or_else(|e| e.into_inner());
+        let result = writeln!(sink, "{}", line).and_then(|_| sink.flush());
+        if let Err(e) = result {
+            eprintln!("Logger write failed: {} ({})", e, line);
+        }
+    }
+}
+
+/// Format one log line with an RFC3339 timestamp and the level
+fn format_line(level: LogLevel, message: &str) -> String {
+    format!(
+        "{} [{:?}] {}",
+        chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        level,
+        message
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_filtering_and_runtime_change() {
+        let logger = Logger::new(LogLevel::Error);
+        assert!(!logger.enabled(LogLevel::Info));
+        assert!(!logger.enabled(LogLevel::Warning));
+        assert!(logger.enabled(LogLevel::Error));
+
+        logger.set_level(LogLevel::Debug);
+        assert_eq!(logger.level(), LogLevel::Debug);
+        assert!(logger.enabled(LogLevel::Debug));
+    }
+
+    /// Writer whose output stays inspectable after being boxed
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_writer_sink_receives_filtered_lines() {
+        let buffer = SharedBuffer::default();
+        let logger = Logger::to_writer(LogLevel::Info, Box::new(buffer.clone()));
+        logger.log(LogLevel::Debug, "hidden");
+        logger.log(LogLevel::Info, "shown");
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.ends_with("[Info] shown\n"));
+    }
+
+    #[test]
+    fn test_file_sink_appends() {
+        let path = Path::new("target/test_output/logger/app.log");
+        let _ = std::fs::remove_file(path);
+        Logger::to_file(LogLevel::Debug, path)
+            .unwrap()
+            .log(LogLevel::Info, "first");
+        Logger::to_file(LogLevel::Debug, path)
+            .unwrap()
+            .log(LogLevel::Error, "second");
+
+        let content = std::fs::read_to_string(path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("[Info] first"));
+        assert!(lines[1].ends_with("[Error] second"));
+    }
+
+    #[test]
+    fn test_line_has_timestamp_and_level() {
+        let line = format_line(LogLevel::Warning, "disk almost full");
+        let (timestamp, rest) = line.split_once(' ').unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(timestamp).is_ok());
+        assert_eq!(rest, "[Warning] disk almost full");
     }
 }
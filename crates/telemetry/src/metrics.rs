@@ -1,37 +1,442 @@
 //! Metrics collection and reporting
+//!
+//! `Metrics::to_prometheus` renders everything in the Prometheus text
+//! exposition format; `demo_receiver` serves it on `GET /metrics`.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+
+/// Summary of the values recorded under one histogram name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramSummary {
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Default for HistogramSummary {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl HistogramSummary {
+    fn record(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Average of the recorded values
+    pub fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
 
 /// Simple metrics collector
-#[derive(Debug)]
+///
+/// Tracks three kinds of metric, each in its own namespace: monotonic
+/// counters (`increment`), gauges holding the latest value (`set_gauge`) and
+/// histograms summarising a distribution (`record_value`).
+///
+/// All methods take `&self`, so a `Metrics` can be shared through an `Arc`.
+/// Updating an existing counter or gauge only takes a shared read lock plus
+/// an atomic operation; the write lock is needed just to add a new name.
+#[derive(Debug, Default)]
 pub struct Metrics {
-    counters: HashMap<String, u64>,
+    counters: RwLock<HashMap<String, AtomicU64>>,
+    /// Gauge values stored as `f64::to_bits`
+    gauges: RwLock<HashMap<String, AtomicU64>>,
+    histograms: RwLock<HashMap<String, Mutex<HistogramSummary>>>,
 }
 
 impl Metrics {
     pub fn new() -> Self {
-        Self {
-            counters: HashMap::new(),
-        }
+        Self::default()
     }
 
-    pub fn increment(&mut self, name: &str) {
-        *self.counters.entry(name.to_string()).or_insert(0) += 1;
+    pub fn increment(&self, name: &str) {
+        update(&self.counters, name, |counter| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
     }
 
     pub fn get(&self, name: &str) -> Option<u64> {
-        self.counters.get(name).copied()
+        read(&self.counters)
+            .get(name)
+            .map(|counter| counter.load(Ordering::Relaxed))
+    }
+
+    /// Set a gauge to its current value, e.g. a queue depth
+    pub fn set_gauge(&self, name: &str, value: f64) {
+        update(&self.gauges, name, |gauge| {
+            gauge.store(value.to_bits(), Ordering::Relaxed);
+        });
+    }
+
+    pub fn get_gauge(&self, name: &str) -> Option<f64> {
+        read(&self.gauges)
+            .get(name)
+            .map(|gauge| f64::from_bits(gauge.load(Ordering::Relaxed)))
+    }
+
+    /// Add an observation to a histogram, e.g. a latency
+    pub fn record_value(&self, name: &str, value: f64) {
+        update(&self.histograms, name, |histogram| {
+            lock(histogram).record(value);
+        });
+    }
+
+    pub fn get_histogram(&self, name: &str) -> Option<HistogramSummary> {
+        read(&self.histograms).get(name).map(|h| *lock(h))
+    }
+
+    /// List the names of all counters, gauges and histograms, sorted
+    /// alphabetically (a name used by several kinds is listed once)
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .counters()
+            .into_iter()
+            .map(|(name, _)| name)
+            .chain(self.gauges().into_iter().map(|(name, _)| name))
+            .chain(self.histograms().into_iter().map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Iterate over a snapshot of all counters as `(name, value)` pairs in
+    /// arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (String, u64)> {
+        self.counters().into_iter()
+    }
+
+    /// Iterate over a snapshot of all gauges as `(name, value)` pairs in
+    /// arbitrary order
+    pub fn iter_gauges(&self) -> impl Iterator<Item = (String, f64)> {
+        self.gauges().into_iter()
+    }
+
+    /// Iterate over a snapshot of all histograms as `(name, summary)` pairs
+    /// in arbitrary order
+    pub fn iter_histograms(&self) -> impl Iterator<Item = (String, HistogramSummary)> {
+        self.histograms().into_iter()
     }
 
     pub fn report(&self) {
-        for (name, value) in &self.counters {
+        for (name, value) in self.counters() {
             println!("Metric {}: {}", name, value);
         }
+        for (name, value) in self.gauges() {
+            println!("Gauge {}: {}", name, value);
+        }
+        for (name, h) in self.histograms() {
+            println!(
+                "Histogram {}: count={} sum={} min={} max={} mean={}",
+                name,
+                h.count,
+                h.sum,
+                h.min,
+                h.max,
+                h.mean()
+            );
+        }
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    ///
+    /// Names are sanitized to `[a-zA-Z_:][a-zA-Z0-9_:]*` by replacing other
+    /// characters with `_`. Counters and gauges map directly; each histogram
+    /// becomes a `summary` with `_count` and `_sum` samples plus `_min` and
+    /// `_max` gauges.
+    ///
+    /// Every exported name is unique. When a sanitized name is already taken,
+    /// e.g. by `a.b` and `a_b`, by a counter and gauge sharing a name, or by a
+    /// gauge `x_min` and a histogram `x`, the later metric gets the first free
+    /// `_2`, `_3`, ... suffix. Counters claim names first, then gauges, then
+    /// histograms, each in name order.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut taken = HashSet::new();
+        for (name, value) in self.counters() {
+            let name = unique_name(&mut taken, prometheus_name(&name), &[]);
+            out.push_str(&format!("# TYPE {name} counter\n{name} {value}\n"));
+        }
+        for (name, value) in self.gauges() {
+            let name = unique_name(&mut taken, prometheus_name(&name), &[]);
+            let value = prometheus_value(value);
+            out.push_str(&format!("# TYPE {name} gauge\n{name} {value}\n"));
+        }
+        for (name, h) in self.histograms() {
+            let name = unique_name(
+                &mut taken,
+                prometheus_name(&name),
+                &["_count", "_sum", "_min", "_max"],
+            );
+            out.push_str(&format!(
+                "# TYPE {name} summary\n{name}_count {}\n{name}_sum {}\n",
+                h.count,
+                prometheus_value(h.sum)
+            ));
+            for (suffix, value) in [("min", h.min), ("max", h.max)] {
+                out.push_str(&format!(
+                    "# TYPE {name}_{suffix} gauge\n{name}_{suffix} {}\n",
+                    prometheus_value(value)
+                ));
+            }
+        }
+        out
+    }
+
+    /// Snapshot of the counters, sorted by name
+    fn counters(&self) -> Vec<(String, u64)> {
+        snapshot(&self.counters, |c| c.load(Ordering::Relaxed))
+    }
+
+    /// Snapshot of the gauges, sorted by name
+    fn gauges(&self) -> Vec<(String, f64)> {
+        snapshot(&self.gauges, |g| f64::from_bits(g.load(Ordering::Relaxed)))
+    }
+
+    /// Snapshot of the histograms, sorted by name
+    fn histograms(&self) -> Vec<(String, HistogramSummary)> {
+        snapshot(&self.histograms, |h| *lock(h))
     }
 }
 
-impl Default for Metrics {
-    fn default() -> Self {
-        Self::new()
+/// Replace characters Prometheus does not allow in metric names
+fn prometheus_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !sanitized.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == ':') {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// Claim `name`, plus `name` followed by each of `suffixes`, in `taken`
+///
+/// If any of those names is already taken, `_2`, `_3`, ... is appended to
+/// `name` until all of them are free.
+fn unique_name(taken: &mut HashSet<String>, name: String, suffixes: &[&str]) -> String {
+    let is_free = |taken: &HashSet<String>, candidate: &str| {
+        !taken.contains(candidate)
+            && suffixes
+                .iter()
+                .all(|suffix| !taken.contains(&format!("{candidate}{suffix}")))
+    };
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while !is_free(taken, &candidate) {
+        candidate = format!("{name}_{n}");
+        n += 1;
+    }
+    for suffix in suffixes {
+        taken.insert(format!("{candidate}{suffix}"));
+    }
+    taken.insert(candidate.clone());
+    candidate
+}
+
+/// Format a sample value, spelling infinities the way Prometheus expects
+fn prometheus_value(value: f64) -> String {
+    if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Read-lock `map`, recovering from poisoning (values stay consistent
+/// because every update is a single atomic or locked operation)
+fn read<V>(map: &RwLock<HashMap<String, V>>) -> std::sync::RwLockReadGuard<'_, HashMap<String, V>> {
+    map.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn lock(histogram: &Mutex<HistogramSummary>) -> std::sync::MutexGuard<'_, HistogramSummary> {
+    histogram.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Apply `f` to the entry for `name`, creating it first if needed
+fn update<V: Default>(map: &RwLock<HashMap<String, V>>, name: &str, f: impl FnOnce(&V)) {
+    if let Some(value) = read(map).get(name) {
+        f(value);
+        return;
+    }
+    let mut map = map.write().unwrap_or_else(|e| e.into_inner());
+    f(map.entry(name.to_string()).or_default());
+}
+
+/// Copy every entry of `map` out through `value`, sorted by name
+fn snapshot<V, T>(map: &RwLock<HashMap<String, V>>, value: impl Fn(&V) -> T) -> Vec<(String, T)> {
+    let mut entries: Vec<(String, T)> = read(map)
+        .iter()
+        .map(|(name, v)| (name.clone(), value(v)))
+        .collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_introspection() {
+        let metrics = Metrics::new();
+        metrics.increment("packets_sent");
+        metrics.increment("packets_sent");
+        metrics.increment("batches_sent");
+        metrics.increment("errors");
+
+        assert_eq!(
+            metrics.names(),
+            vec!["batches_sent", "errors", "packets_sent"]
+        );
+
+        let mut pairs: Vec<(String, u64)> = metrics.iter().collect();
+        pairs.sort_unstable();
+        assert_eq!(
+            pairs,
+            vec![
+                ("batches_sent".to_string(), 1),
+                ("errors".to_string(), 1),
+                ("packets_sent".to_string(), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_concurrent_increments() {
+        let metrics = std::sync::Arc::new(Metrics::new());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let metrics = metrics.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        metrics.increment("ticks");
+                        metrics.record_value("latency_ms", 1.0);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(metrics.get("ticks"), Some(8000));
+        assert_eq!(metrics.get_histogram("latency_ms").unwrap().count, 8000);
+    }
+
+    #[test]
+    fn test_gauges_and_histograms() {
+        let metrics = Metrics::new();
+        metrics.set_gauge("queue_depth", 12.0);
+        metrics.set_gauge("queue_depth", 3.0);
+        assert_eq!(metrics.get_gauge("queue_depth"), Some(3.0));
+        assert_eq!(metrics.get_gauge("missing"), None);
+
+        for latency in [4.0, 1.0, 7.0] {
+            metrics.record_value("send_latency_ms", latency);
+        }
+        let summary = metrics.get_histogram("send_latency_ms").unwrap();
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.sum, 12.0);
+        assert_eq!(summary.min, 1.0);
+        assert_eq!(summary.max, 7.0);
+        assert_eq!(summary.mean(), 4.0);
+
+        // Separate namespaces: gauges and histograms are not counters, but
+        // every kind is listed by name
+        assert_eq!(metrics.get("queue_depth"), None);
+        assert_eq!(metrics.iter().count(), 0);
+        assert_eq!(metrics.names(), vec!["queue_depth", "send_latency_ms"]);
+        assert_eq!(
+            metrics.iter_gauges().collect::<Vec<_>>(),
+            vec![("queue_depth".to_string(), 3.0)]
+        );
+        assert_eq!(
+            metrics
+                .iter_histograms()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            vec!["send_latency_ms"]
+        );
+    }
+
+    #[test]
+    fn test_prometheus_export() {
+        let metrics = Metrics::new();
+        metrics.increment("packets.sent");
+        metrics.set_gauge("queue-depth", 3.5);
+        metrics.record_value("latency_ms", 2.0);
+        metrics.record_value("latency_ms", 6.0);
+        metrics.set_gauge("9lives", f64::INFINITY);
+
+        assert_eq!(
+            metrics.to_prometheus(),
+            "# TYPE packets_sent counter\n\
+             packets_sent 1\n\
+             # TYPE _9lives gauge\n\
+             _9lives +Inf\n\
+             # TYPE queue_depth gauge\n\
+             queue_depth 3.5\n\
+             # TYPE latency_ms summary\n\
+             latency_ms_count 2\n\
+             latency_ms_sum 8\n\
+             # TYPE latency_ms_min gauge\n\
+             latency_ms_min 2\n\
+             # TYPE latency_ms_max gauge\n\
+             latency_ms_max 6\n"
+        );
+    }
+
+    #[test]
+    fn test_prometheus_export_disambiguates_colliding_names() {
+        let metrics = Metrics::new();
+        metrics.increment("a.b");
+        metrics.increment("a_b");
+        metrics.increment("a_b");
+        metrics.set_gauge("a_b", 1.0);
+        metrics.set_gauge("x_min", 0.5);
+        metrics.record_value("x", 3.0);
+
+        let output = metrics.to_prometheus();
+        let families: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("# TYPE "))
+            .collect();
+        assert_eq!(
+            families,
+            vec![
+                "a_b counter",
+                "a_b_2 counter",
+                "a_b_3 gauge",
+                "x_min gauge",
+                "x_2 summary",
+                "x_2_min gauge",
+                "x_2_max gauge",
+            ]
+        );
+        assert!(output.contains("\na_b 1\n"));
+        assert!(output.contains("\na_b_2 2\n"));
+        assert!(output.contains("\nx_2_count 1\n"));
     }
 }
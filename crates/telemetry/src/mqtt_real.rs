@@ -7,18 +7,22 @@
 //! - TLS/mTLS support
 //! - Configurable QoS
 //! - Automatic reconnection
+//! - Optional command topic subscription for inbound control messages
+//! - Optional Last Will and Testament, which the broker publishes if the
+//!   client disconnects without a clean `DISCONNECT` (crash, network loss,
+//!   missed keep-alives)
 
 use crate::TelemetryPacket;
 use async_trait::async_trait;
 use backoff::future::retry;
 use backoff::ExponentialBackoff;
-use rumqttc::{AsyncClient, MqttOptions, QoS, TlsConfiguration};
+use rumqttc::{AsyncClient, LastWill, MqttOptions, QoS, TlsConfiguration};
 use serde_json;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
-use tokio::sync::Mutex;
+use tokio::sync::{mpsc, Mutex};
 
 use super::Transport;
 
@@ -50,6 +54,9 @@ pub struct MqttConfig {
     pub client_id: String,
     /// Topic to publish telemetry to
     pub topic: String,
+    /// Topic filter to receive commands on (`+`/`#` wildcards allowed),
+    /// subscribed on every (re)connect
+    pub command_topic: Option<String>,
     /// QoS level (0, 1, or 2)
     pub qos: u8,
     /// Keep alive interval in seconds
@@ -64,6 +71,15 @@ pub struct MqttConfig {
     pub client_key_path: Option<String>,
     /// Maximum reconnection attempts (0 = infinite)
     pub max_reconnect_attempts: u32,
+    /// Topic the broker publishes the will to on an unexpected disconnect
+    /// (no will is registered when `None`)
+    pub lwt_topic: Option<String>,
+    /// Will message payload
+    pub lwt_payload: Vec<u8>,
+    /// Will QoS level (0, 1, or 2)
+    pub lwt_qos: u8,
+    /// Whether the broker retains the will message
+    pub lwt_retain: bool,
 }
 
 impl Default for MqttConfig {
@@ -73,6 +89,7 @@ impl Default for MqttConfig {
             port: 1883,
             client_id: format!("rust-telemetry-{}", std::process::id()),
             topic: "telemetry/system".to_string(),
+            command_topic: None,
             qos: 1,
             keep_alive_secs: 60,
             use_tls: false,
@@ -80,16 +97,27 @@ impl Default for MqttConfig {
             client_cert_path: None,
             client_key_path: None,
             max_reconnect_attempts: 0, // infinite retries
+            lwt_topic: None,
+            lwt_payload: Vec::new(),
+            lwt_qos: 1,
+            lwt_retain: false,
         }
     }
 }
 
+/// Queued inbound command payloads per receiver before new ones are dropped
+const INBOUND_CAPACITY: usize = 64;
+
+/// Receivers of inbound command payloads, see `RealMqttTransport::inbound`
+type InboundSubscribers = Arc<std::sync::Mutex<Vec<mpsc::Sender<Vec<u8>>>>>;
+
 /// Production MQTT transport with reconnection and retry logic
 pub struct RealMqttTransport {
     config: MqttConfig,
     client: Arc<Mutex<Option<AsyncClient>>>,
     connected: Arc<AtomicBool>,
     rx_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    inbound: InboundSubscribers,
 }
 
 impl RealMqttTransport {
@@ -100,12 +128,25 @@ impl RealMqttTransport {
             client: Arc::new(Mutex::new(None)),
             connected: Arc::new(AtomicBool::new(false)),
             rx_handle: Arc::new(Mutex::new(None)),
+            inbound: Arc::new(std::sync::Mutex::new(Vec::new())),
         };
 
         transport.connect().await?;
         Ok(transport)
     }
 
+    /// Receive payloads published to `MqttConfig::command_topic`
+    ///
+    /// Each call returns a new receiver that gets every command arriving from
+    /// then on. A receiver that falls `INBOUND_CAPACITY` payloads behind
+    /// misses newer ones until it catches up. Without a command topic the
+    /// receiver never yields anything.
+    pub fn inbound(&self) -> mpsc::Receiver<Vec<u8>> {
+        let (tx, rx) = mpsc::channel(INBOUND_CAPACITY);
+        self.inbound.lock().unwrap().push(tx);
+        rx
+    }
+
     /// Connect to MQTT broker with retry logic
     async fn connect(&self) -> Result<(), MqttError> {
         let backoff = ExponentialBackoff {
@@ -117,11 +158,15 @@ impl RealMqttTransport {
         let client_arc = self.client.clone();
         let connected_arc = self.connected.clone();
         let rx_handle_arc = self.rx_handle.clone();
+        let inbound_arc = self.inbound.clone();
 
         retry(backoff, || async {
             let mut mqtt_opts =
                 MqttOptions::new(config.client_id.clone(), config.host.clone(), config.port);
             mqtt_opts.set_keep_alive(Duration::from_secs(config.keep_alive_secs));
+            if let Some(will) = Self::last_will(&config) {
+                mqtt_opts.set_last_will(will);
+            }
 
             // Configure TLS if needed
             if config.use_tls {
@@ -129,7 +174,8 @@ impl RealMqttTransport {
                     Ok(tls_config) => {
                         mqtt_opts.set_transport(rumqttc::Transport::Tls(tls_config));
                     }
-                    Err(e) => return Err(e),
+                    // Retrying cannot fix a bad certificate setup
+                    Err(e) => return Err(backoff::Error::permanent(e)),
                 }
             }
 
@@ -137,6 +183,10 @@ impl RealMqttTransport {
 
             // Spawn event loop handler
             let connected = connected_arc.clone();
+            let inbound = inbound_arc.clone();
+            let subscriber = client.clone();
+            let command_topic = config.command_topic.clone();
+            let qos = qos_level(config.qos);
             let client_handle = tokio::spawn(async move {
                 loop {
                     match eventloop.poll().await {
@@ -145,6 +195,24 @@ impl RealMqttTransport {
                             match notification {
                                 Event::Incoming(rumqttc::Incoming::ConnAck(_)) => {
                                     connected.store(true, Ordering::SeqCst);
+                                    // Subscriptions do not survive a reconnect with a
+                                    // clean session, so renew them on every ConnAck.
+                                    // `try_subscribe` because awaiting the request
+                                    // queue here would stall the event loop feeding it.
+                                    if let Some(ref topic) = command_topic {
+                                        if let Err(e) = subscriber.try_subscribe(topic, qos) {
+                                            tracing::warn!(
+                                                "MQTT subscribe to {} failed: {}",
+                                                topic,
+                                                e
+                                            );
+                                        }
+                                    }
+                                }
+                                Event::Incoming(rumqttc::Incoming::Publish(publish))
+                                    if is_command(command_topic.as_deref(), &publish.topic) =>
+                                {
+                                    deliver_inbound(&inbound, publish.payload.to_vec());
                                 }
                                 Event::Incoming(rumqttc::Incoming::Disconnect) => {
                                     connected.store(false, Ordering::SeqCst);
@@ -173,6 +241,17 @@ impl RealMqttTransport {
         Ok(())
     }
 
+    /// Build the configured Last Will and Testament, if any
+    fn last_will(config: &MqttConfig) -> Option<LastWill> {
+        let topic = config.lwt_topic.as_ref()?;
+        Some(LastWill::new(
+            topic,
+            config.lwt_payload.clone(),
+            qos_level(config.lwt_qos),
+            config.lwt_retain,
+        ))
+    }
+
     /// Configure TLS/mTLS
     async fn configure_tls(config: &MqttConfig) -> Result<TlsConfiguration, MqttError> {
         use std::fs;
@@ -202,11 +281,7 @@ impl RealMqttTransport {
         Ok(TlsConfiguration::Simple {
             ca: ca_cert,
             alpn: None,
-            client_auth: if client_cert.is_some() && client_key.is_some() {
-                Some((client_cert.unwrap(), client_key.unwrap()))
-            } else {
-                None
-            },
+            client_auth: client_cert.zip(client_key),
         })
     }
 
@@ -219,8 +294,41 @@ impl RealMqttTransport {
     }
 }
 
+/// Map a configured QoS number to `QoS`, treating unknown values as 1
+fn qos_level(qos: u8) -> QoS {
+    match qos {
+        0 => QoS::AtMostOnce,
+        1 => QoS::AtLeastOnce,
+        2 => QoS::ExactlyOnce,
+        _ => QoS::AtLeastOnce,
+    }
+}
+
+/// Check whether a publish on `topic` matches the command topic filter,
+/// which may contain `+` and `#` wildcards
+fn is_command(command_topic: Option<&str>, topic: &str) -> bool {
+    command_topic.is_some_and(|filter| rumqttc::matches(topic, filter))
+}
+
+/// Hand a command payload to every live receiver, forgetting dropped ones
+fn deliver_inbound(subscribers: &std::sync::Mutex<Vec<mpsc::Sender<Vec<u8>>>>, payload: Vec<u8>) {
+    let mut subscribers = subscribers.lock().unwrap();
+    subscribers.retain(|tx| match tx.try_send(payload.clone()) {
+        Ok(()) => true,
+        Err(mpsc::error::TrySendError::Full(_)) => {
+            tracing::warn!("Inbound MQTT command receiver is full, dropping command");
+            true
+        }
+        Err(mpsc::error::TrySendError::Closed(_)) => false,
+    });
+}
+
 #[async_trait]
 impl Transport for RealMqttTransport {
+    fn name(&self) -> &str {
+        "mqtt_real"
+    }
+
     async fn send(&self, packet: &TelemetryPacket) -> Result<(), super::TransportError> {
         self.ensure_connected()
             .await
@@ -230,12 +338,7 @@ impl Transport for RealMqttTransport {
 
         let client = self.client.lock().await;
         if let Some(ref c) = *client {
-            let qos = match self.config.qos {
-                0 => QoS::AtMostOnce,
-                1 => QoS::AtLeastOnce,
-                2 => QoS::ExactlyOnce,
-                _ => QoS::AtLeastOnce,
-            };
+            let qos = qos_level(self.config.qos);
 
             c.publish(self.config.topic.clone(), qos, false, json)
                 .await
@@ -259,5 +362,46 @@ mod tests {
         assert_eq!(config.host, "localhost");
         assert_eq!(config.port, 1883);
         assert_eq!(config.qos, 1);
+        assert!(config.command_topic.is_none());
+        assert!(RealMqttTransport::last_will(&config).is_none());
+    }
+
+    #[test]
+    fn test_last_will_from_config() {
+        let config = MqttConfig {
+            lwt_topic: Some("devices/dev-1/status".to_string()),
+            lwt_payload: b"offline".to_vec(),
+            lwt_qos: 2,
+            lwt_retain: true,
+            ..Default::default()
+        };
+        let will = RealMqttTransport::last_will(&config).unwrap();
+        assert_eq!(will.topic, "devices/dev-1/status");
+        assert_eq!(&will.message[..], b"offline");
+        assert_eq!(will.qos, QoS::ExactlyOnce);
+        assert!(will.retain);
+    }
+
+    #[test]
+    fn test_command_topic_wildcards() {
+        assert!(is_command(Some("devices/dev-1/cmd"), "devices/dev-1/cmd"));
+        assert!(is_command(Some("devices/+/cmd"), "devices/dev-1/cmd"));
+        assert!(is_command(Some("cmd/#"), "cmd/motor/speed"));
+        assert!(!is_command(Some("devices/+/cmd"), "devices/dev-1/status"));
+        assert!(!is_command(None, "devices/dev-1/cmd"));
+    }
+
+    #[tokio::test]
+    async fn test_deliver_inbound_fans_out_and_prunes_closed() {
+        let subscribers = std::sync::Mutex::new(Vec::new());
+        let (tx_a, mut rx_a) = mpsc::channel(INBOUND_CAPACITY);
+        let (tx_b, rx_b) = mpsc::channel(INBOUND_CAPACITY);
+        subscribers.lock().unwrap().extend([tx_a, tx_b]);
+        drop(rx_b);
+
+        deliver_inbound(&subscribers, b"{\"MotorSpeed\":0.5}".to_vec());
+
+        assert_eq!(rx_a.recv().await.unwrap(), b"{\"MotorSpeed\":0.5}");
+        assert_eq!(subscribers.lock().unwrap().len(), 1);
     }
 }
@@ -0,0 +1,30 @@
+//! OpenTelemetry export for pipeline spans
+//!
+//! The streaming pipeline emits `tracing` spans for each batch (`send_batch`),
+//! its compression (`compress_batch`), assembly (`assemble_batch`) and every
+//! per-transport send (`transport_send`), with packet counts and sizes as
+//! structured fields. This module wires those spans into an OpenTelemetry
+//! tracer so they reach a distributed tracing backend.
+//!
+//! # Example
+//! ```ignore
+//! use tracing_subscriber::layer::SubscriberExt;
+//!
+//! let tracer = provider.tracer("telemetry");
+//! let subscriber = tracing_subscriber::registry().with(telemetry::otel::layer(tracer));
+//! tracing::subscriber::set_global_default(subscriber)?;
+//! ```
+
+use opentelemetry::trace::Tracer;
+use tracing::Subscriber;
+use tracing_opentelemetry::{OpenTelemetryLayer, PreSampledTracer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Build a `tracing-subscriber` layer exporting spans through `tracer`
+pub fn layer<S, T>(tracer: T) -> OpenTelemetryLayer<S, T>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    T: Tracer + PreSampledTracer + 'static,
+{
+    tracing_opentelemetry::layer().with_tracer(tracer)
+}
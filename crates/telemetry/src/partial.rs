@@ -0,0 +1,159 @@
+//! Partial telemetry updates
+//!
+//! Sending a full `TelemetryPacket` when only the sensor readings changed
+//! wastes bandwidth at high rates. A `PartialTelemetry` carries only the
+//! sections that changed; unchanged sections are omitted from the wire
+//! format and the receiver rebuilds the full state by merging each partial
+//! onto the last full packet it knows.
+
+use crate::types::{DiagnosticsReport, SensorReading, SystemHealth, Timestamp, SCHEMA_VERSION};
+use crate::TelemetryPacket;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Telemetry update in which unchanged sections are omitted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialTelemetry {
+    /// Packet sequence number
+    pub sequence: u64,
+    /// Timestamp of the update
+    pub timestamp: Timestamp,
+    /// Health snapshot, if it changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<SystemHealth>,
+    /// Sensor readings, if they changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensor_readings: Option<Vec<SensorReading>>,
+    /// Diagnostics snapshot, if it changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<DiagnosticsReport>,
+    /// Metadata, if it changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl PartialTelemetry {
+    /// Create an update carrying every section of `packet`
+    pub fn full(packet: TelemetryPacket) -> Self {
+        Self {
+            sequence: packet.sequence,
+            timestamp: packet.timestamp,
+            health: Some(packet.health),
+            sensor_readings: Some(packet.sensor_readings),
+            diagnostics: Some(packet.diagnostics),
+            metadata: Some(packet.metadata),
+        }
+    }
+
+    /// Create an update carrying only sensor readings
+    pub fn readings_only(sequence: u64, sensor_readings: Vec<SensorReading>) -> Self {
+        Self {
+            sensor_readings: Some(sensor_readings),
+            ..Self::empty(sequence)
+        }
+    }
+
+    /// Create an update carrying only a health snapshot
+    pub fn health_only(sequence: u64, health: SystemHealth) -> Self {
+        Self {
+            health: Some(health),
+            ..Self::empty(sequence)
+        }
+    }
+
+    fn empty(sequence: u64) -> Self {
+        Self {
+            sequence,
+            timestamp: chrono::Utc::now(),
+            health: None,
+            sensor_readings: None,
+            diagnostics: None,
+            metadata: None,
+        }
+    }
+
+    /// Check whether every section is present
+    pub fn is_full(&self) -> bool {
+        self.health.is_some()
+            && self.sensor_readings.is_some()
+            && self.diagnostics.is_some()
+            && self.metadata.is_some()
+    }
+
+    /// Convert to a packet if every section is present
+    pub fn into_packet(self) -> Option<TelemetryPacket> {
+        Some(TelemetryPacket {
+            sequence: self.sequence,
+            schema_version: SCHEMA_VERSION,
+            timestamp: self.timestamp,
+            health: self.health?,
+            sensor_readings: self.sensor_readings?,
+            diagnostics: self.diagnostics?,
+            metadata: self.metadata?,
+        })
+    }
+
+    /// Rebuild the full state by applying this update on top of `base`
+    ///
+    /// Sections present in the update replace those of `base`; omitted
+    /// sections are carried over unchanged.
+    pub fn merge_onto(self, base: &TelemetryPacket) -> TelemetryPacket {
+        TelemetryPacket {
+            sequence: self.sequence,
+            schema_version: SCHEMA_VERSION,
+            timestamp: self.timestamp,
+            health: self.health.unwrap_or_else(|| base.health.clone()),
+            sensor_readings: self
+                .sensor_readings
+                .unwrap_or_else(|| base.sensor_readings.clone()),
+            diagnostics: self.diagnostics.unwrap_or_else(|| base.diagnostics.clone()),
+            metadata: self.metadata.unwrap_or_else(|| base.metadata.clone()),
+        }
+    }
+}
+
+impl From<TelemetryPacket> for PartialTelemetry {
+    fn from(packet: TelemetryPacket) -> Self {
+        Self::full(packet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{HealthStatus, SensorData};
+
+    #[test]
+    fn test_readings_only_partial_carries_prior_health() {
+        let mut health = SystemHealth::new();
+        health.degraded_components = 1;
+        health.recalculate_status();
+        let base = TelemetryPacket::builder()
+            .sequence(1)
+            .health(health)
+            .build();
+
+        // Sender side: only the readings changed
+        let reading = SensorReading::new(
+            "temp-01".to_string(),
+            "Temperature".to_string(),
+            SensorData::Temperature {
+                value: 23.0,
+                unit: "°C".to_string(),
+            },
+            2,
+        );
+        let partial = PartialTelemetry::readings_only(2, vec![reading]);
+        assert!(!partial.is_full());
+        let json = serde_json::to_string(&partial).unwrap();
+        assert!(!json.contains("\"health\""));
+
+        // Receiver side: merge onto the last full packet
+        let received: PartialTelemetry = serde_json::from_str(&json).unwrap();
+        let state = received.merge_onto(&base);
+        assert_eq!(state.sequence, 2);
+        assert_eq!(state.health.status, HealthStatus::Degraded);
+        assert_eq!(state.sensor_readings.len(), 1);
+        assert_eq!(state.sensor_readings[0].component_id, "temp-01");
+    }
+}
@@ -0,0 +1,190 @@
+//! Cancellable packet producer
+//!
+//! `ProducerHandle` runs a packet-generating loop on a background task and
+//! feeds a pipeline sender. The loop checks a `CancellationToken` while
+//! sending and while sleeping, so it stops promptly on request instead of
+//! spinning until some external deadline. It also stops on its own when the
+//! generator is exhausted or the receiving side of the channel goes away.
+//!
+//! Packets come either from a closure or from any `TelemetrySource`.
+//!
+//! # Example
+//! ```ignore
+//! let producer = ProducerHandle::spawn(pipeline.get_sender(), Duration::from_millis(100), |seq| {
+//!     Some(TelemetryPacket::new(seq))
+//! });
+//! // ... later
+//! let produced = producer.stop().await;
+//! ```
+
+use crate::source::{FnSource, TelemetrySource};
+use crate::TelemetryPacket;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::Sender;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Handle to a background packet producer
+pub struct ProducerHandle {
+    token: CancellationToken,
+    produced: Arc<AtomicU64>,
+    handle: JoinHandle<u64>,
+}
+
+impl ProducerHandle {
+    /// Spawn a producer sending one packet per `interval`
+    ///
+    /// `make_packet` receives the zero-based sequence number and returns the
+    /// packet to send, or `None` to end production.
+    pub fn spawn<F>(sender: Sender<TelemetryPacket>, interval: Duration, make_packet: F) -> Self
+    where
+        F: FnMut(u64) -> Option<TelemetryPacket> + Send + 'static,
+    {
+        Self::spawn_with_token(sender, interval, CancellationToken::new(), make_packet)
+    }
+
+    /// Spawn a producer that also stops when `token` (e.g. a shared shutdown token) is cancelled
+    pub fn spawn_with_token<F>(
+        sender: Sender<TelemetryPacket>,
+        interval: Duration,
+        token: CancellationToken,
+        make_packet: F,
+    ) -> Self
+    where
+        F: FnMut(u64) -> Option<TelemetryPacket> + Send + 'static,
+    {
+        Self::spawn_source(sender, interval, token, FnSource::new(make_packet))
+    }
+
+    /// Spawn a producer pulling one packet per `interval` from `source`
+    pub fn spawn_source<S>(
+        sender: Sender<TelemetryPacket>,
+        interval: Duration,
+        token: CancellationToken,
+        mut source: S,
+    ) -> Self
+    where
+        S: TelemetrySource + 'static,
+    {
+        let produced = Arc::new(AtomicU64::new(0));
+        let task_token = token.clone();
+        let task_produced = produced.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut count: u64 = 0;
+            loop {
+                let packet = tokio::select! {
+                    biased;
+                    _ = task_token.cancelled() => break,
+                    packet = source.next() => match packet {
+                        Some(packet) => packet,
+                        None => break,
+                    },
+                };
+                tokio::select! {
+                    biased;
+                    _ = task_token.cancelled() => break,
+                    result = sender.send(packet) => {
+                        if result.is_err() {
+                            tracing::warn!("Producer channel closed after {} packets", count);
+                            break;
+                        }
+                    }
+                }
+                count += 1;
+                task_produced.store(count, Ordering::SeqCst);
+
+                tokio::select! {
+                    biased;
+                    _ = task_token.cancelled() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+            }
+            count
+        });
+
+        Self {
+            token,
+            produced,
+            handle,
+        }
+    }
+
+    /// Number of packets successfully sent so far
+    pub fn produced(&self) -> u64 {
+        self.produced.load(Ordering::SeqCst)
+    }
+
+    /// Get the token controlling this producer
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Check whether the producer loop has exited
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Request a stop and wait for the loop to exit, returning the packet count
+    pub async fn stop(self) -> u64 {
+        self.token.cancel();
+        self.join().await
+    }
+
+    /// Wait for the loop to exit on its own, returning the packet count
+    pub async fn join(self) -> u64 {
+        match self.handle.await {
+            Ok(count) => count,
+            Err(e) => {
+                tracing::error!("Producer task failed: {}", e);
+                self.produced.load(Ordering::SeqCst)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_producer_stops_promptly_on_cancel() {
+        let (tx, mut rx) = mpsc::channel(1024);
+        let producer = ProducerHandle::spawn(tx, Duration::from_millis(10), |seq| {
+            Some(TelemetryPacket::new(seq))
+        });
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Time is paused, so the timeout can only fire if the producer keeps
+        // sleeping through its interval instead of reacting to the cancel
+        let produced = tokio::time::timeout(Duration::from_millis(5), producer.stop())
+            .await
+            .expect("producer did not stop promptly");
+        assert!(produced > 0);
+
+        let mut received = 0;
+        while rx.try_recv().is_ok() {
+            received += 1;
+        }
+        assert_eq!(received, produced);
+    }
+
+    #[tokio::test]
+    async fn test_producer_ends_when_generator_exhausted() {
+        let (tx, mut rx) = mpsc::channel(16);
+        let producer = ProducerHandle::spawn(tx, Duration::from_millis(1), |seq| {
+            (seq < 3).then(|| TelemetryPacket::new(seq))
+        });
+
+        assert_eq!(producer.join().await, 3);
+        let mut received = Vec::new();
+        while let Ok(packet) = rx.try_recv() {
+            received.push(packet.sequence);
+        }
+        assert_eq!(received, vec![0, 1, 2]);
+    }
+}
@@ -1,15 +1,24 @@
 //! Resilience layer for telemetry pipeline
 //!
-//! Provides retry logic, offline buffering, and circuit breaker pattern
-//! to ensure reliable delivery even under adverse conditions.
+//! Provides retry logic, offline buffering, circuit breaker pattern and
+//! self-healing transport wrappers to ensure reliable delivery even under
+//! adverse conditions.
 
+use crate::transports::{Transport, TransportError};
+use crate::types::{DiagnosticEntry, DiagnosticLevel};
 use crate::TelemetryPacket;
+use async_trait::async_trait;
+use backoff::backoff::Backoff;
+use backoff::ExponentialBackoffBuilder;
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 /// Resilience error types
 #[derive(Error, Debug)]
@@ -20,6 +29,8 @@ pub enum ResilienceError {
     BufferFull,
     #[error("Retry exhausted: {0}")]
     RetryExhausted(String),
+    #[error("Spill file error: {0}")]
+    Spill(#[from] std::io::Error),
 }
 
 /// Circuit breaker state
@@ -34,7 +45,7 @@ pub enum CircuitState {
 }
 
 /// Configuration for resilience layer
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ResilienceConfig {
     /// Maximum number of retry attempts
     pub max_retries: u32,
@@ -50,6 +61,21 @@ pub struct ResilienceConfig {
     pub half_open_timeout_secs: u64,
     /// Offline buffer size (max packets to buffer)
     pub buffer_size: usize,
+    /// What the offline buffer does when full
+    #[serde(default)]
+    pub overflow_policy: OverflowPolicy,
+    /// Randomize retry delays so many clients don't retry in lockstep
+    ///
+    /// `RetryStrategy` uses "full jitter": each delay is drawn uniformly from
+    /// `[0, current_backoff]` while the backoff itself keeps growing
+    /// exponentially. `AutoReconnect` applies the backoff crate's ±50%
+    /// randomization instead. Disable for deterministic timing in tests.
+    #[serde(default = "default_jitter")]
+    pub jitter: bool,
+}
+
+fn default_jitter() -> bool {
+    true
 }
 
 impl Default for ResilienceConfig {
@@ -62,6 +88,8 @@ impl Default for ResilienceConfig {
             failure_threshold: 5,
             half_open_timeout_secs: 30,
             buffer_size: 1000,
+            overflow_policy: OverflowPolicy::default(),
+            jitter: default_jitter(),
         }
     }
 }
@@ -157,39 +185,110 @@ impl CircuitBreaker {
     }
 }
 
+/// Callback invoked when the offline buffer crosses its high-water mark
+type HighWaterCallback = Box<dyn Fn(DiagnosticEntry) + Send + Sync>;
+
+/// What an `OfflineBuffer` does with a push when it is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OverflowPolicy {
+    /// Reject the new packet with `BufferFull`
+    #[default]
+    RejectNew,
+    /// Evict the oldest packet to make room, keeping the freshest data
+    DropOldest,
+}
+
 /// Offline buffer for storing packets when transport is unavailable
+///
+/// An optional high-water mark raises an early warning before the buffer is
+/// full and starts dropping packets. The warning fires once each time usage
+/// crosses the mark and re-arms when usage falls back below it.
 pub struct OfflineBuffer {
-    packets: Arc<RwLock<Vec<TelemetryPacket>>>,
+    packets: Arc<RwLock<VecDeque<TelemetryPacket>>>,
     max_size: usize,
+    policy: OverflowPolicy,
+    dropped: AtomicU64,
+    high_water_mark: Option<f32>,
+    on_high_water: Option<HighWaterCallback>,
+    above_high_water: AtomicBool,
 }
 
 impl OfflineBuffer {
     /// Create a new offline buffer
     pub fn new(max_size: usize) -> Self {
         Self {
-            packets: Arc::new(RwLock::new(Vec::with_capacity(max_size))),
+            packets: Arc::new(RwLock::new(VecDeque::with_capacity(max_size))),
             max_size,
+            policy: OverflowPolicy::default(),
+            dropped: AtomicU64::new(0),
+            high_water_mark: None,
+            on_high_water: None,
+            above_high_water: AtomicBool::new(false),
         }
     }
 
+    /// Emit a warning diagnostic when usage reaches `fraction` (0.0-1.0) of capacity
+    pub fn with_high_water_mark<F>(mut self, fraction: f32, callback: F) -> Self
+    where
+        F: Fn(DiagnosticEntry) + Send + Sync + 'static,
+    {
+        self.high_water_mark = Some(fraction.clamp(0.0, 1.0));
+        self.on_high_water = Some(Box::new(callback));
+        self
+    }
+
+    /// Choose what happens when a packet is pushed into a full buffer
+    pub fn with_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Get the overflow policy
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Number of packets lost to overflow, whether rejected or evicted
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::SeqCst)
+    }
+
     /// Add a packet to the buffer
+    ///
+    /// When full, `RejectNew` fails with `BufferFull` while `DropOldest`
+    /// evicts the front packet and succeeds; both count towards
+    /// `dropped_count`. A zero-capacity buffer rejects every packet.
     pub async fn push(&self, packet: TelemetryPacket) -> Result<(), ResilienceError> {
         let mut packets = self.packets.write().await;
         if packets.len() >= self.max_size {
-            return Err(ResilienceError::BufferFull);
+            self.dropped.fetch_add(1, Ordering::SeqCst);
+            if self.policy == OverflowPolicy::RejectNew || self.max_size == 0 {
+                return Err(ResilienceError::BufferFull);
+            }
+            packets.pop_front();
         }
-        packets.push(packet);
+        packets.push_back(packet);
+        let len = packets.len();
+        drop(packets);
+
+        self.update_high_water(len);
         Ok(())
     }
 
     /// Get and remove the next packet from the buffer
     pub async fn pop(&self) -> Option<TelemetryPacket> {
         let mut packets = self.packets.write().await;
-        if packets.is_empty() {
-            None
-        } else {
-            Some(packets.remove(0))
-        }
+        let packet = packets.pop_front()?;
+        let len = packets.len();
+        drop(packets);
+
+        self.update_high_water(len);
+        Some(packet)
+    }
+
+    /// Get a copy of the next packet without removing it
+    pub async fn peek(&self) -> Option<TelemetryPacket> {
+        self.packets.read().await.front().cloned()
     }
 
     /// Get current buffer size
@@ -197,10 +296,120 @@ impl OfflineBuffer {
         self.packets.read().await.len()
     }
 
+    /// Check whether the buffer is empty
+    pub async fn is_empty(&self) -> bool {
+        self.packets.read().await.is_empty()
+    }
+
+    /// Get the fraction of capacity in use (0.0-1.0)
+    pub async fn usage_fraction(&self) -> f32 {
+        self.fraction_of(self.len().await)
+    }
+
     /// Get all packets and clear buffer
     pub async fn drain(&self) -> Vec<TelemetryPacket> {
         let mut packets = self.packets.write().await;
-        packets.drain(..).collect()
+        let drained = packets.drain(..).collect();
+        drop(packets);
+
+        self.update_high_water(0);
+        drained
+    }
+
+    /// Drain the buffer through `transport`, returning the number of packets delivered
+    ///
+    /// Packets that fail to send are put back at the front of the buffer in
+    /// their original order. If packets pushed during the flush leave the
+    /// buffer over capacity, the excess is discarded according to the
+    /// `OverflowPolicy` and counted in `dropped_count`. An error is returned
+    /// only when packets failed and none could be delivered.
+    pub async fn flush_to(&self, transport: &dyn Transport) -> Result<usize, TransportError> {
+        let pending = self.drain().await;
+        let mut delivered = 0;
+        let mut failed = VecDeque::new();
+        let mut first_error = None;
+
+        for packet in pending {
+            match transport.send(&packet).await {
+                Ok(()) => delivered += 1,
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                    failed.push_back(packet);
+                }
+            }
+        }
+
+        if !failed.is_empty() {
+            let mut packets = self.packets.write().await;
+            failed.append(&mut packets);
+            let excess = failed.len().saturating_sub(self.max_size);
+            if excess > 0 {
+                self.dropped.fetch_add(excess as u64, Ordering::SeqCst);
+                if self.policy == OverflowPolicy::DropOldest {
+                    tracing::warn!(
+                        "Offline buffer over capacity after flush, dropping {} oldest packets",
+                        excess
+                    );
+                    failed.drain(..excess);
+                } else {
+                    tracing::warn!(
+                        "Offline buffer over capacity after flush, dropping {} newest packets",
+                        excess
+                    );
+                    failed.truncate(self.max_size);
+                }
+            }
+            *packets = failed;
+            let len = packets.len();
+            drop(packets);
+            self.update_high_water(len);
+        }
+
+        match first_error {
+            Some(e) if delivered == 0 => Err(e),
+            _ => Ok(delivered),
+        }
+    }
+
+    fn fraction_of(&self, len: usize) -> f32 {
+        if self.max_size == 0 {
+            1.0
+        } else {
+            len as f32 / self.max_size as f32
+        }
+    }
+
+    /// Fire the high-water callback on an upward crossing, re-arm on a downward one
+    fn update_high_water(&self, len: usize) {
+        let Some(mark) = self.high_water_mark else {
+            return;
+        };
+        let usage = self.fraction_of(len);
+
+        if usage < mark {
+            self.above_high_water.store(false, Ordering::SeqCst);
+            return;
+        }
+
+        if !self.above_high_water.swap(true, Ordering::SeqCst) {
+            tracing::warn!(
+                "Offline buffer usage {:.0}% crossed high-water mark {:.0}%",
+                usage * 100.0,
+                mark * 100.0
+            );
+            if let Some(ref callback) = self.on_high_water {
+                callback(
+                    DiagnosticEntry::new(
+                        DiagnosticLevel::Warning,
+                        "offline_buffer".to_string(),
+                        "Offline buffer crossed high-water mark",
+                    )
+                    .with_code("BUFFER_HIGH_WATER")
+                    .with_context("buffered".to_string(), len.to_string())
+                    .with_context("capacity".to_string(), self.max_size.to_string()),
+                );
+            }
+        }
     }
 }
 
@@ -225,7 +434,7 @@ impl RetryStrategy {
         let mut current_backoff = self.config.initial_backoff_ms;
 
         loop {
-            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f())) {
+            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(&mut f)) {
                 Ok(result) => return Ok(result),
                 Err(_) => {
                     attempt += 1;
@@ -234,12 +443,189 @@ impl RetryStrategy {
                             "Max retries exceeded".to_string(),
                         ));
                     }
-                    tokio::time::sleep(Duration::from_millis(current_backoff)).await;
-                    current_backoff = std::cmp::min(
-                        (current_backoff as f64 * self.config.backoff_multiplier) as u64,
-                        self.config.max_backoff_ms,
-                    );
+                    tokio::time::sleep(self.retry_delay(current_backoff)).await;
+                    current_backoff = self.next_backoff(current_backoff);
+                }
+            }
+        }
+    }
+
+    /// Run a fallible async operation, retrying each `Err` with exponential backoff
+    ///
+    /// `f` is called once plus up to `max_retries` more times. Returns the
+    /// first `Ok` value, or `RetryExhausted` carrying the last error once the
+    /// retries are used up.
+    pub async fn execute<F, Fut, T, E>(&self, mut f: F) -> Result<T, ResilienceError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        let mut current_backoff = self.config.initial_backoff_ms;
+        let mut retries = 0;
+
+        loop {
+            match f().await {
+                Ok(value) => return Ok(value),
+                Err(e) if retries >= self.config.max_retries => {
+                    return Err(ResilienceError::RetryExhausted(format!(
+                        "{} attempts failed, last error: {}",
+                        retries + 1,
+                        e
+                    )));
+                }
+                Err(e) => {
+                    retries += 1;
+                    tracing::debug!("Attempt {} failed, retrying: {}", retries, e);
+                    tokio::time::sleep(self.retry_delay(current_backoff)).await;
+                    current_backoff = self.next_backoff(current_backoff);
+                }
+            }
+        }
+    }
+
+    /// Delay before the next attempt, with full jitter applied if enabled
+    fn retry_delay(&self, backoff_ms: u64) -> Duration {
+        if self.config.jitter {
+            Duration::from_millis(rand::Rng::gen_range(
+                &mut rand::thread_rng(),
+                0..=backoff_ms,
+            ))
+        } else {
+            Duration::from_millis(backoff_ms)
+        }
+    }
+
+    fn next_backoff(&self, current_ms: u64) -> u64 {
+        std::cmp::min(
+            (current_ms as f64 * self.config.backoff_multiplier) as u64,
+            self.config.max_backoff_ms,
+        )
+    }
+}
+
+/// Factory used by [`AutoReconnect`] to build a fresh inner transport
+type TransportFactory<T> =
+    Box<dyn Fn() -> BoxFuture<'static, Result<T, TransportError>> + Send + Sync>;
+
+/// Self-healing wrapper that rebuilds its inner transport after repeated failures
+///
+/// After `failure_threshold` consecutive send failures the inner transport is
+/// dropped and recreated through the user-provided factory. Rebuild attempts
+/// are spaced with jittered exponential backoff (bounded by `max_retries`),
+/// and the failed packet is resent once through the new transport.
+///
+/// Rebuilds are single-flight: when several concurrent sends fail, one of
+/// them rebuilds the transport while the others wait and then resend
+/// through the new transport instead of rebuilding it again.
+pub struct AutoReconnect<T: Transport> {
+    inner: RwLock<T>,
+    factory: TransportFactory<T>,
+    config: ResilienceConfig,
+    consecutive_failures: AtomicU32,
+    reconnect_count: AtomicU32,
+    /// Held for the duration of a rebuild
+    reconnecting: Mutex<()>,
+}
+
+impl<T: Transport> AutoReconnect<T> {
+    /// Build the initial transport with `factory` and wrap it
+    pub async fn new<F, Fut>(factory: F, config: ResilienceConfig) -> Result<Self, TransportError>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, TransportError>> + Send + 'static,
+    {
+        let factory: TransportFactory<T> = Box::new(move || Box::pin(factory()));
+        let inner = factory().await?;
+
+        Ok(Self {
+            inner: RwLock::new(inner),
+            factory,
+            config,
+            consecutive_failures: AtomicU32::new(0),
+            reconnect_count: AtomicU32::new(0),
+            reconnecting: Mutex::new(()),
+        })
+    }
+
+    /// Number of times the inner transport has been rebuilt
+    pub fn reconnect_count(&self) -> u32 {
+        self.reconnect_count.load(Ordering::SeqCst)
+    }
+
+    /// Rebuild the inner transport unless another send has already done so
+    /// since `generation` (the `reconnect_count` seen before sending)
+    async fn reconnect_once(&self, generation: u32) -> Result<(), TransportError> {
+        let _guard = self.reconnecting.lock().await;
+        if self.reconnect_count() != generation {
+            return Ok(());
+        }
+        self.reconnect().await
+    }
+
+    /// Tear down the inner transport and rebuild it with jittered backoff
+    async fn reconnect(&self) -> Result<(), TransportError> {
+        let mut backoff = ExponentialBackoffBuilder::new()
+            .with_initial_interval(Duration::from_millis(self.config.initial_backoff_ms))
+            .with_max_interval(Duration::from_millis(self.config.max_backoff_ms))
+            .with_multiplier(self.config.backoff_multiplier)
+            .with_randomization_factor(if self.config.jitter { 0.5 } else { 0.0 })
+            .with_max_elapsed_time(None)
+            .build();
+        let max_attempts = self.config.max_retries.max(1);
+        let mut last_error = TransportError::Closed;
+
+        for attempt in 1..=max_attempts {
+            match (self.factory)().await {
+                Ok(transport) => {
+                    *self.inner.write().await = transport;
+                    self.consecutive_failures.store(0, Ordering::SeqCst);
+                    self.reconnect_count.fetch_add(1, Ordering::SeqCst);
+                    tracing::info!("Transport rebuilt after {} attempt(s)", attempt);
+                    return Ok(());
+                }
+                Err(e) => {
+                    tracing::warn!("Transport rebuild attempt {} failed: {}", attempt, e);
+                    last_error = e;
+                    if attempt < max_attempts {
+                        if let Some(delay) = backoff.next_backoff() {
+                            tokio::time::sleep(delay).await;
+                        }
+                    }
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+#[async_trait]
+impl<T: Transport> Transport for AutoReconnect<T> {
+    fn name(&self) -> &str {
+        "auto_reconnect"
+    }
+
+    async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+        let generation = self.reconnect_count();
+        let result = self.inner.read().await.send(packet).await;
+        match result {
+            Ok(()) => {
+                self.consecutive_failures.store(0, Ordering::SeqCst);
+                Ok(())
+            }
+            Err(e) => {
+                let failures = self.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1;
+                if failures < self.config.failure_threshold {
+                    return Err(e);
                 }
+                tracing::warn!(
+                    "Transport failed {} times in a row, rebuilding: {}",
+                    failures,
+                    e
+                );
+                self.reconnect_once(generation).await?;
+                self.inner.read().await.send(packet).await
             }
         }
     }
@@ -272,6 +658,70 @@ mod tests {
         assert_eq!(breaker.state().await, CircuitState::Closed);
     }
 
+    fn fast_retry_config(max_retries: u32) -> ResilienceConfig {
+        ResilienceConfig {
+            max_retries,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 4,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_execute_recovers_from_errors() {
+        let strategy = RetryStrategy::new(fast_retry_config(3));
+        let calls = AtomicU32::new(0);
+
+        let result = strategy
+            .execute(|| async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err(TransportError::Closed),
+                    n => Ok(n),
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_execute_exhausts_after_max_retries() {
+        let strategy = RetryStrategy::new(fast_retry_config(2));
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = strategy
+            .execute(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(TransportError::Other("link down".to_string()))
+            })
+            .await;
+
+        match result {
+            Err(ResilienceError::RetryExhausted(message)) => {
+                assert!(message.contains("link down"), "{}", message)
+            }
+            other => panic!("expected RetryExhausted, got {:?}", other.map(|_| ())),
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_retry_delay_full_jitter() {
+        // Jitter is on by default
+        assert!(ResilienceConfig::default().jitter);
+        let jittered = RetryStrategy::new(ResilienceConfig::default());
+        let delays: Vec<Duration> = (0..200).map(|_| jittered.retry_delay(100)).collect();
+        assert!(delays.iter().all(|d| *d <= Duration::from_millis(100)));
+        assert!(delays.iter().any(|d| *d != delays[0]));
+
+        let fixed = RetryStrategy::new(ResilienceConfig {
+            jitter: false,
+            ..Default::default()
+        });
+        assert_eq!(fixed.retry_delay(100), Duration::from_millis(100));
+    }
+
     #[tokio::test]
     async fn test_offline_buffer() {
         let buffer = OfflineBuffer::new(3);
@@ -285,6 +735,81 @@ mod tests {
         assert_eq!(buffer.len().await, 0);
     }
 
+    #[tokio::test]
+    async fn test_offline_buffer_overflow_policies() {
+        let reject = OfflineBuffer::new(2);
+        for seq in 0..2 {
+            reject.push(TelemetryPacket::new(seq)).await.unwrap();
+        }
+        assert!(matches!(
+            reject.push(TelemetryPacket::new(2)).await,
+            Err(ResilienceError::BufferFull)
+        ));
+        assert_eq!(reject.dropped_count(), 1);
+        assert_eq!(reject.peek().await.unwrap().sequence, 0);
+
+        let drop_oldest = OfflineBuffer::new(2).with_policy(OverflowPolicy::DropOldest);
+        for seq in 0..5 {
+            drop_oldest.push(TelemetryPacket::new(seq)).await.unwrap();
+        }
+        assert_eq!(drop_oldest.dropped_count(), 3);
+        let kept: Vec<u64> = drop_oldest
+            .drain()
+            .await
+            .iter()
+            .map(|p| p.sequence)
+            .collect();
+        assert_eq!(kept, vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_offline_buffer_is_empty_and_peek() {
+        let buffer = OfflineBuffer::new(3);
+        assert!(buffer.is_empty().await);
+        assert!(buffer.peek().await.is_none());
+
+        buffer.push(TelemetryPacket::new(1)).await.unwrap();
+        buffer.push(TelemetryPacket::new(2)).await.unwrap();
+        assert!(!buffer.is_empty().await);
+
+        // Peeking repeatedly returns the front without consuming it
+        assert_eq!(buffer.peek().await.unwrap().sequence, 1);
+        assert_eq!(buffer.peek().await.unwrap().sequence, 1);
+        assert_eq!(buffer.len().await, 2);
+
+        assert_eq!(buffer.pop().await.unwrap().sequence, 1);
+        assert_eq!(buffer.peek().await.unwrap().sequence, 2);
+    }
+
+    #[tokio::test]
+    async fn test_offline_buffer_high_water_mark() {
+        let warnings = Arc::new(AtomicU32::new(0));
+        let counter = warnings.clone();
+        let buffer = OfflineBuffer::new(10).with_high_water_mark(0.5, move |entry| {
+            assert_eq!(entry.level, DiagnosticLevel::Warning);
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        for i in 0..4 {
+            buffer.push(TelemetryPacket::new(i)).await.unwrap();
+        }
+        assert_eq!(warnings.load(Ordering::SeqCst), 0);
+        assert!((buffer.usage_fraction().await - 0.4).abs() < f32::EPSILON);
+
+        // Crossing the mark fires once, staying above it does not fire again
+        for i in 4..8 {
+            buffer.push(TelemetryPacket::new(i)).await.unwrap();
+        }
+        assert_eq!(warnings.load(Ordering::SeqCst), 1);
+
+        // Dropping below the mark re-arms the warning for the next crossing
+        for _ in 0..4 {
+            buffer.pop().await;
+        }
+        buffer.push(TelemetryPacket::new(8)).await.unwrap();
+        assert_eq!(warnings.load(Ordering::SeqCst), 2);
+    }
+
     #[test]
     fn test_default_resilience_config() {
         let config = ResilienceConfig::default();
@@ -292,4 +817,166 @@ mod tests {
         assert_eq!(config.failure_threshold, 5);
         assert_eq!(config.buffer_size, 1000);
     }
+
+    /// Transport that either always fails or counts delivered packets
+    struct FlakyTransport {
+        healthy: bool,
+        delivered: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl Transport for FlakyTransport {
+        async fn send(&self, _packet: &TelemetryPacket) -> Result<(), TransportError> {
+            if self.healthy {
+                self.delivered.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            } else {
+                Err(TransportError::Other("link down".to_string()))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offline_buffer_flush_to() {
+        let buffer = OfflineBuffer::new(10);
+        for i in 0..4 {
+            buffer.push(TelemetryPacket::new(i)).await.unwrap();
+        }
+
+        let memory = crate::MemoryTransport::new();
+        assert_eq!(buffer.flush_to(&memory).await.unwrap(), 4);
+        assert_eq!(buffer.len().await, 0);
+        let sequences: Vec<u64> = memory.packets().await.iter().map(|p| p.sequence).collect();
+        assert_eq!(sequences, vec![0, 1, 2, 3]);
+
+        // A dead transport leaves everything buffered
+        buffer.push(TelemetryPacket::new(4)).await.unwrap();
+        let broken = FlakyTransport {
+            healthy: false,
+            delivered: Arc::new(AtomicU32::new(0)),
+        };
+        assert!(buffer.flush_to(&broken).await.is_err());
+        assert_eq!(buffer.len().await, 1);
+    }
+
+    /// Failing transport that refills the buffer it is flushed from
+    struct RefillingTransport {
+        buffer: Arc<OfflineBuffer>,
+    }
+
+    #[async_trait]
+    impl Transport for RefillingTransport {
+        async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+            let _ = self
+                .buffer
+                .push(TelemetryPacket::new(packet.sequence + 10))
+                .await;
+            Err(TransportError::Other("link down".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offline_buffer_flush_over_capacity_follows_policy() {
+        for (policy, kept) in [
+            (OverflowPolicy::DropOldest, vec![10, 11]),
+            (OverflowPolicy::RejectNew, vec![0, 1]),
+        ] {
+            let buffer = Arc::new(OfflineBuffer::new(2).with_policy(policy));
+            for seq in 0..2 {
+                buffer.push(TelemetryPacket::new(seq)).await.unwrap();
+            }
+            let transport = RefillingTransport {
+                buffer: buffer.clone(),
+            };
+
+            assert!(buffer.flush_to(&transport).await.is_err());
+            assert_eq!(buffer.dropped_count(), 2, "{:?}", policy);
+            let sequences: Vec<u64> = buffer.drain().await.iter().map(|p| p.sequence).collect();
+            assert_eq!(sequences, kept, "{:?}", policy);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auto_reconnect_rebuilds_flaky_transport() {
+        let builds = Arc::new(AtomicU32::new(0));
+        let delivered = Arc::new(AtomicU32::new(0));
+        let config = ResilienceConfig {
+            failure_threshold: 2,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            ..Default::default()
+        };
+
+        let factory_builds = builds.clone();
+        let factory_delivered = delivered.clone();
+        let transport = AutoReconnect::new(
+            move || {
+                // First build is broken, every rebuild after that is healthy
+                let healthy = factory_builds.fetch_add(1, Ordering::SeqCst) > 0;
+                let delivered = factory_delivered.clone();
+                async move { Ok(FlakyTransport { healthy, delivered }) }
+            },
+            config,
+        )
+        .await
+        .unwrap();
+
+        let packet = TelemetryPacket::new(1);
+        assert!(transport.send(&packet).await.is_err());
+        assert!(transport.send(&packet).await.is_ok());
+
+        assert_eq!(builds.load(Ordering::SeqCst), 2);
+        assert_eq!(transport.reconnect_count(), 1);
+        assert_eq!(delivered.load(Ordering::SeqCst), 1);
+
+        transport.send(&packet).await.unwrap();
+        assert_eq!(delivered.load(Ordering::SeqCst), 2);
+    }
+
+    /// Transport that fails slowly until rebuilt, so concurrent sends overlap
+    struct SlowFailingTransport {
+        healthy: bool,
+    }
+
+    #[async_trait]
+    impl Transport for SlowFailingTransport {
+        async fn send(&self, _packet: &TelemetryPacket) -> Result<(), TransportError> {
+            if self.healthy {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Err(TransportError::Other("link down".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auto_reconnect_rebuilds_once_for_concurrent_failures() {
+        let builds = Arc::new(AtomicU32::new(0));
+        let config = ResilienceConfig {
+            failure_threshold: 1,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            ..Default::default()
+        };
+
+        let factory_builds = builds.clone();
+        let transport = AutoReconnect::new(
+            move || {
+                let healthy = factory_builds.fetch_add(1, Ordering::SeqCst) > 0;
+                async move { Ok(SlowFailingTransport { healthy }) }
+            },
+            config,
+        )
+        .await
+        .unwrap();
+
+        let packet = TelemetryPacket::new(1);
+        let sends = (0..8).map(|_| transport.send(&packet));
+        for result in futures::future::join_all(sends).await {
+            result.unwrap();
+        }
+
+        assert_eq!(builds.load(Ordering::SeqCst), 2);
+        assert_eq!(transport.reconnect_count(), 1);
+    }
 }
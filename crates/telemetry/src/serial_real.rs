@@ -0,0 +1,215 @@
+#![cfg(feature = "serial_real")]
+
+//! Hardware serial/UART transport using `tokio-serial`.
+//!
+//! This module provides a UART transport with:
+//! - Configurable device path, baud rate and character framing
+//! - Newline-delimited JSON messages, one packet per line
+//! - Automatic reopening when the device disappears (e.g. USB unplug)
+
+use crate::TelemetryPacket;
+use async_trait::async_trait;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tokio_serial::{DataBits, Parity, SerialPortBuilderExt, SerialStream, StopBits};
+
+use super::{Transport, TransportError};
+
+/// Error type for serial port operations
+#[derive(Error, Debug)]
+pub enum SerialError {
+    #[error("Failed to open serial port {path}: {reason}")]
+    Open { path: String, reason: String },
+    #[error("Serial write error: {0}")]
+    Write(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl From<SerialError> for TransportError {
+    fn from(e: SerialError) -> Self {
+        match e {
+            SerialError::Write(e) => TransportError::Io(e),
+            SerialError::Serialization(e) => TransportError::Serialization(e),
+            other => TransportError::Other(other.to_string()),
+        }
+    }
+}
+
+/// Configuration for real serial transport
+#[derive(Clone, Debug)]
+pub struct SerialConfig {
+    /// Device path (e.g., "/dev/ttyUSB0" or "COM3")
+    pub device_path: String,
+    /// Baud rate (e.g., 115200)
+    pub baud_rate: u32,
+    /// Data bits per character
+    pub data_bits: DataBits,
+    /// Parity checking mode
+    pub parity: Parity,
+    /// Stop bits per character
+    pub stop_bits: StopBits,
+    /// Delay between attempts to reopen a lost device
+    pub reconnect_delay_ms: u64,
+    /// Maximum reopen attempts per send (0 = a single attempt)
+    pub max_reconnect_attempts: u32,
+}
+
+impl Default for SerialConfig {
+    fn default() -> Self {
+        Self {
+            device_path: "/dev/ttyUSB0".to_string(),
+            baud_rate: 115_200,
+            data_bits: DataBits::Eight,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+            reconnect_delay_ms: 500,
+            max_reconnect_attempts: 3,
+        }
+    }
+}
+
+/// UART transport writing newline-delimited JSON packets
+///
+/// A write failure closes the port; the next send tries to reopen it, so a
+/// device that disappears and comes back resumes without intervention.
+pub struct RealSerialTransport {
+    config: SerialConfig,
+    port: Mutex<Option<SerialStream>>,
+}
+
+impl RealSerialTransport {
+    /// Create a new serial transport and open the port
+    pub async fn new(config: SerialConfig) -> Result<Self, SerialError> {
+        let port = Self::open_port(&config)?;
+        Ok(Self {
+            config,
+            port: Mutex::new(Some(port)),
+        })
+    }
+
+    /// Get the transport configuration
+    pub fn config(&self) -> &SerialConfig {
+        &self.config
+    }
+
+    /// Check whether the port is currently open
+    pub async fn is_open(&self) -> bool {
+        self.port.lock().await.is_some()
+    }
+
+    fn open_port(config: &SerialConfig) -> Result<SerialStream, SerialError> {
+        tokio_serial::new(&config.device_path, config.baud_rate)
+            .data_bits(config.data_bits)
+            .parity(config.parity)
+            .stop_bits(config.stop_bits)
+            .open_native_async()
+            .map_err(|e| SerialError::Open {
+                path: config.device_path.clone(),
+                reason: e.to_string(),
+            })
+    }
+
+    /// Reopen the port, retrying with a fixed delay
+    async fn reopen(&self) -> Result<SerialStream, SerialError> {
+        let attempts = self.config.max_reconnect_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match Self::open_port(&self.config) {
+                Ok(port) => {
+                    tracing::info!(
+                        "Serial port {} reopened after {} attempt(s)",
+                        self.config.device_path,
+                        attempt
+                    );
+                    return Ok(port);
+                }
+                Err(e) if attempt >= attempts => return Err(e),
+                Err(e) => {
+                    tracing::warn!("Serial reopen attempt {} failed: {}", attempt, e);
+                    tokio::time::sleep(Duration::from_millis(self.config.reconnect_delay_ms)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Write one framed message, closing the port if the write fails
+    async fn write_line(&self, json: &[u8]) -> Result<(), SerialError> {
+        let mut line = Vec::with_capacity(json.len() + 1);
+        line.extend_from_slice(json);
+        line.push(b'\n');
+
+        let mut port = self.port.lock().await;
+        if port.is_none() {
+            *port = Some(self.reopen().await?);
+        }
+        let stream = port.as_mut().expect("port opened above");
+
+        let result = async {
+            stream.write_all(&line).await?;
+            stream.flush().await
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!(
+                "Serial write to {} failed, closing port: {}",
+                self.config.device_path,
+                e
+            );
+            *port = None;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Transport for RealSerialTransport {
+    fn name(&self) -> &str {
+        "serial_real"
+    }
+
+    async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+        let json = serde_json::to_vec(packet)?;
+        Ok(self.write_line(&json).await?)
+    }
+
+    async fn send_bytes(
+        &self,
+        _packet: &TelemetryPacket,
+        json: &[u8],
+    ) -> Result<(), TransportError> {
+        Ok(self.write_line(json).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = SerialConfig::default();
+        assert_eq!(config.baud_rate, 115_200);
+        assert_eq!(config.data_bits, DataBits::Eight);
+        assert_eq!(config.parity, Parity::None);
+    }
+
+    #[tokio::test]
+    async fn test_missing_device_reports_open_error() {
+        let config = SerialConfig {
+            device_path: "/dev/does-not-exist-serial".to_string(),
+            ..Default::default()
+        };
+        match RealSerialTransport::new(config).await {
+            Err(SerialError::Open { path, .. }) => {
+                assert_eq!(path, "/dev/does-not-exist-serial")
+            }
+            Err(e) => panic!("unexpected error {}", e),
+            Ok(_) => panic!("opened a nonexistent device"),
+        }
+    }
+}
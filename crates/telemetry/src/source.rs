@@ -0,0 +1,111 @@
+//! Pull-based telemetry sources
+//!
+//! A `TelemetrySource` produces packets on demand, letting a pipeline pull
+//! telemetry on a schedule (see `StreamingPipeline::pump_from`) instead of
+//! requiring callers to push through the sender themselves.
+
+use crate::{TelemetryCollector, TelemetryPacket};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Asynchronous source of telemetry packets
+#[async_trait]
+pub trait TelemetrySource: Send {
+    /// Produce the next packet, or `None` when the source is exhausted
+    async fn next(&mut self) -> Option<TelemetryPacket>;
+}
+
+#[async_trait]
+impl<S: TelemetrySource + ?Sized> TelemetrySource for Box<S> {
+    async fn next(&mut self) -> Option<TelemetryPacket> {
+        (**self).next().await
+    }
+}
+
+/// Source that snapshots a `TelemetryCollector` into a new packet on every pull
+pub struct CollectorSource {
+    collector: Arc<TelemetryCollector>,
+}
+
+impl CollectorSource {
+    /// Create a source backed by `collector`
+    pub fn new(collector: Arc<TelemetryCollector>) -> Self {
+        Self { collector }
+    }
+}
+
+#[async_trait]
+impl TelemetrySource for CollectorSource {
+    async fn next(&mut self) -> Option<TelemetryPacket> {
+        Some(self.collector.generate_packet().await)
+    }
+}
+
+/// Source emitting a fixed number of empty packets with increasing sequence numbers
+pub struct MockSource {
+    next_sequence: u64,
+    remaining: usize,
+}
+
+impl MockSource {
+    /// Create a source that emits `count` packets starting at sequence 0
+    pub fn new(count: usize) -> Self {
+        Self {
+            next_sequence: 0,
+            remaining: count,
+        }
+    }
+}
+
+#[async_trait]
+impl TelemetrySource for MockSource {
+    async fn next(&mut self) -> Option<TelemetryPacket> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let packet = TelemetryPacket::new(self.next_sequence);
+        self.next_sequence += 1;
+        Some(packet)
+    }
+}
+
+/// Adapter turning a packet-generating closure into a source
+pub(crate) struct FnSource<F> {
+    sequence: u64,
+    make_packet: F,
+}
+
+impl<F> FnSource<F> {
+    pub(crate) fn new(make_packet: F) -> Self {
+        Self {
+            sequence: 0,
+            make_packet,
+        }
+    }
+}
+
+#[async_trait]
+impl<F> TelemetrySource for FnSource<F>
+where
+    F: FnMut(u64) -> Option<TelemetryPacket> + Send,
+{
+    async fn next(&mut self) -> Option<TelemetryPacket> {
+        let packet = (self.make_packet)(self.sequence)?;
+        self.sequence += 1;
+        Some(packet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_collector_source_increments_sequence() {
+        let mut source = CollectorSource::new(Arc::new(TelemetryCollector::new()));
+        let first = source.next().await.unwrap();
+        let second = source.next().await.unwrap();
+        assert_eq!(second.sequence, first.sequence + 1);
+    }
+}
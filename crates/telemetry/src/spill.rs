@@ -0,0 +1,271 @@
+//! Offline buffering that survives restarts
+//!
+//! `PersistentOfflineBuffer` keeps the oldest packets in an in-memory
+//! `OfflineBuffer` and spills newer ones to a JSON-lines file once memory is
+//! full. As memory drains, spilled packets are read back in FIFO order. On
+//! startup any packets left in the file are replayed, so an outage that spans
+//! a restart loses nothing that reached the disk. Call `persist` before a
+//! graceful shutdown to move the in-memory portion to disk as well.
+
+use crate::resilience::{OfflineBuffer, ResilienceError};
+use crate::TelemetryPacket;
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Bookkeeping for the on-disk portion of the buffer
+struct SpillFile {
+    path: PathBuf,
+    max_packets: usize,
+    count: usize,
+}
+
+/// Offline buffer that spills to disk when memory is full
+///
+/// Holds at most `max_in_memory` packets in memory and `max_on_disk` in the
+/// spill file. `push`, `pop`, `len` and `drain` behave like those of
+/// `OfflineBuffer` across both portions.
+pub struct PersistentOfflineBuffer {
+    memory: OfflineBuffer,
+    max_in_memory: usize,
+    spill: Mutex<SpillFile>,
+}
+
+impl PersistentOfflineBuffer {
+    /// Open a buffer spilling to `path`, replaying packets left there
+    ///
+    /// Replayed packets beyond both capacities are discarded, newest first.
+    pub async fn open(
+        path: impl AsRef<Path>,
+        max_in_memory: usize,
+        max_on_disk: usize,
+    ) -> Result<Self, ResilienceError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let max_in_memory = max_in_memory.max(1);
+        let buffer = Self {
+            memory: OfflineBuffer::new(max_in_memory),
+            max_in_memory,
+            spill: Mutex::new(SpillFile {
+                path,
+                max_packets: max_on_disk,
+                count: 0,
+            }),
+        };
+
+        let mut spill = buffer.spill.lock().await;
+        let mut replayed = read_spilled(&spill.path).await?;
+        let capacity = max_in_memory + max_on_disk;
+        if replayed.len() > capacity {
+            tracing::warn!(
+                "Spill file holds {} packets, dropping {} newest over capacity",
+                replayed.len(),
+                replayed.len() - capacity
+            );
+            replayed.truncate(capacity);
+        }
+        if !replayed.is_empty() {
+            tracing::info!("Replaying {} spilled packets", replayed.len());
+        }
+        spill.count = replayed.len();
+        write_spilled(&spill.path, &replayed).await?;
+        buffer.refill(&mut spill).await?;
+        drop(spill);
+
+        Ok(buffer)
+    }
+
+    /// Add a packet, spilling to disk once memory is full
+    pub async fn push(&self, packet: TelemetryPacket) -> Result<(), ResilienceError> {
+        let mut spill = self.spill.lock().await;
+
+        // Anything already spilled is older, so stay on disk to keep FIFO order
+        if spill.count == 0 && self.memory.len().await < self.max_in_memory {
+            return self.memory.push(packet).await;
+        }
+        if spill.count >= spill.max_packets {
+            return Err(ResilienceError::BufferFull);
+        }
+
+        let mut line = serde_json::to_vec(&packet).map_err(std::io::Error::from)?;
+        line.push(b'\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&spill.path)
+            .await?;
+        file.write_all(&line).await?;
+        file.flush().await?;
+        spill.count += 1;
+        Ok(())
+    }
+
+    /// Get and remove the oldest packet
+    pub async fn pop(&self) -> Option<TelemetryPacket> {
+        let mut spill = self.spill.lock().await;
+        if let Err(e) = self.refill(&mut spill).await {
+            tracing::warn!("Failed to read spilled packets: {}", e);
+        }
+        let packet = self.memory.pop().await;
+        if let Err(e) = self.refill(&mut spill).await {
+            tracing::warn!("Failed to read spilled packets: {}", e);
+        }
+        packet
+    }
+
+    /// Get the number of buffered packets, in memory and on disk
+    pub async fn len(&self) -> usize {
+        let spill = self.spill.lock().await;
+        self.memory.len().await + spill.count
+    }
+
+    /// Check whether the buffer is empty
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    /// Get the number of packets currently on disk
+    pub async fn spilled_len(&self) -> usize {
+        self.spill.lock().await.count
+    }
+
+    /// Get all packets, oldest first, and clear both portions
+    pub async fn drain(&self) -> Result<Vec<TelemetryPacket>, ResilienceError> {
+        let mut spill = self.spill.lock().await;
+        let mut packets = self.memory.drain().await;
+        packets.extend(read_spilled(&spill.path).await?);
+        write_spilled(&spill.path, &[]).await?;
+        spill.count = 0;
+        Ok(packets)
+    }
+
+    /// Move the in-memory packets to the front of the spill file
+    ///
+    /// Call before a graceful shutdown so the next `open` replays everything.
+    /// The file may then hold up to both capacities combined, as `open` accepts.
+    pub async fn persist(&self) -> Result<(), ResilienceError> {
+        let mut spill = self.spill.lock().await;
+        let mut packets = self.memory.drain().await;
+        packets.extend(read_spilled(&spill.path).await?);
+        let capacity = self.max_in_memory + spill.max_packets;
+        if packets.len() > capacity {
+            tracing::warn!(
+                "Persisting offline buffer over capacity, dropping {} newest packets",
+                packets.len() - capacity
+            );
+            packets.truncate(capacity);
+        }
+        write_spilled(&spill.path, &packets).await?;
+        spill.count = packets.len();
+        Ok(())
+    }
+
+    /// Move spilled packets into memory once it has drained
+    async fn refill(&self, spill: &mut SpillFile) -> Result<(), ResilienceError> {
+        if spill.count == 0 || !self.memory.is_empty().await {
+            return Ok(());
+        }
+
+        let mut spilled = read_spilled(&spill.path).await?;
+        let take = spilled.len().min(self.max_in_memory);
+        let remaining = spilled.split_off(take);
+        for packet in spilled {
+            self.memory.push(packet).await?;
+        }
+        write_spilled(&spill.path, &remaining).await?;
+        spill.count = remaining.len();
+        Ok(())
+    }
+}
+
+/// Read every packet in a spill file, skipping lines that fail to parse
+async fn read_spilled(path: &Path) -> std::io::Result<Vec<TelemetryPacket>> {
+    let content = match fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut packets = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match TelemetryPacket::from_json(line) {
+            Ok(packet) => packets.push(packet),
+            Err(e) => tracing::warn!("Skipping corrupt spilled packet: {}", e),
+        }
+    }
+    Ok(packets)
+}
+
+/// Replace the spill file's contents, via a temporary file and rename
+async fn write_spilled(path: &Path, packets: &[TelemetryPacket]) -> std::io::Result<()> {
+    let mut content = Vec::new();
+    for packet in packets {
+        content.extend(serde_json::to_vec(packet)?);
+        content.push(b'\n');
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content).await?;
+    fs::rename(&tmp, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spills_in_order_and_replays_after_restart() {
+        let path = PathBuf::from("target/test_output/spill_replay.jsonl");
+        let _ = fs::remove_file(&path).await;
+
+        let buffer = PersistentOfflineBuffer::open(&path, 2, 3).await.unwrap();
+        for seq in 0..5 {
+            buffer.push(TelemetryPacket::new(seq)).await.unwrap();
+        }
+        assert!(matches!(
+            buffer.push(TelemetryPacket::new(5)).await,
+            Err(ResilienceError::BufferFull)
+        ));
+        assert_eq!(buffer.len().await, 5);
+        assert_eq!(buffer.spilled_len().await, 3);
+        assert_eq!(buffer.pop().await.unwrap().sequence, 0);
+
+        // Simulate a restart
+        buffer.persist().await.unwrap();
+        drop(buffer);
+        let buffer = PersistentOfflineBuffer::open(&path, 2, 3).await.unwrap();
+        assert_eq!(buffer.len().await, 4);
+
+        let mut sequences = Vec::new();
+        while let Some(packet) = buffer.pop().await {
+            sequences.push(packet.sequence);
+        }
+        assert_eq!(sequences, vec![1, 2, 3, 4]);
+        assert!(buffer.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_drain_returns_memory_then_disk() {
+        let path = PathBuf::from("target/test_output/spill_drain.jsonl");
+        let _ = fs::remove_file(&path).await;
+
+        let buffer = PersistentOfflineBuffer::open(&path, 1, 10).await.unwrap();
+        for seq in 0..4 {
+            buffer.push(TelemetryPacket::new(seq)).await.unwrap();
+        }
+
+        let drained: Vec<u64> = buffer
+            .drain()
+            .await
+            .unwrap()
+            .iter()
+            .map(|p| p.sequence)
+            .collect();
+        assert_eq!(drained, vec![0, 1, 2, 3]);
+        assert_eq!(buffer.len().await, 0);
+        assert!(read_spilled(&path).await.unwrap().is_empty());
+    }
+}
@@ -10,15 +10,26 @@
 //! - Offline buffering when transport unavailable
 //! - Circuit breaker pattern for cascading failure prevention
 
+use crate::config::{MqttBrokerSpec, TelemetryConfig, TransportSpec};
+use crate::heartbeat::{HeartbeatConfig, HeartbeatGenerator};
+use crate::producer::ProducerHandle;
 use crate::resilience::{CircuitBreaker, OfflineBuffer, ResilienceConfig};
-use crate::transports::{MqttTransport, SerialTransport, Transport, TransportError};
+use crate::source::TelemetrySource;
+use crate::transports::{
+    MemoryTransport, MqttTransport, RotatingFileTransport, SerialTransport, Transport,
+    TransportError, UdpTransport,
+};
+use crate::types::PrecisionConfig;
 use crate::TelemetryPacket;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::watch;
 use tokio::time::sleep;
+use tracing::Instrument;
 
 /// Error type for streaming pipeline operations
 #[derive(Error, Debug)]
@@ -34,7 +45,7 @@ pub enum StreamingError {
 }
 
 /// Streaming pipeline configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PipelineConfig {
     /// Max packets per batch before forced send
     pub batch_size: usize,
@@ -46,6 +57,142 @@ pub struct PipelineConfig {
     pub channel_capacity: usize,
     /// Enable resilience features (retry, buffering, circuit breaker)
     pub enable_resilience: bool,
+    /// How many transports must accept a batch for it to count as delivered
+    #[serde(default)]
+    pub delivery_policy: DeliveryPolicy,
+    /// Round sensor values before serialization to improve compression
+    #[serde(default)]
+    pub precision: Option<PrecisionConfig>,
+    /// Send heartbeats while no telemetry is flowing
+    #[serde(default)]
+    pub heartbeat: Option<HeartbeatConfig>,
+}
+
+/// Rule for deciding whether a batch sent to several transports was delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryPolicy {
+    /// Every transport must succeed
+    #[default]
+    AllOf,
+    /// At least one transport must succeed
+    AnyOf,
+}
+
+/// Result of sending a batch to a single transport
+#[derive(Debug)]
+pub struct TransportOutcome {
+    /// Position of the transport in the pipeline's transport list
+    pub index: usize,
+    /// Transport identifier
+    pub name: String,
+    /// Send result
+    pub result: Result<(), TransportError>,
+}
+
+/// Per-transport results of sending one batch, in transport order
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    /// One outcome per transport (empty if the batch was buffered without sending)
+    pub outcomes: Vec<TransportOutcome>,
+    /// Whether the batch satisfied the pipeline's `DeliveryPolicy`
+    pub delivered: bool,
+    /// Size of the (possibly compressed) batch payload, 0 if not sent
+    pub payload_bytes: usize,
+    /// Size of the batch's JSON before compression, 0 if not sent
+    pub uncompressed_bytes: usize,
+    /// Packets moved to the offline buffer
+    pub buffered: usize,
+    /// Packets lost because they were neither delivered nor buffered
+    pub dropped: usize,
+}
+
+impl BatchReport {
+    /// Iterate over the transports that failed
+    pub fn failures(&self) -> impl Iterator<Item = &TransportOutcome> {
+        self.outcomes.iter().filter(|o| o.result.is_err())
+    }
+}
+
+/// A batch serialized once and shared by every transport
+///
+/// Each packet is encoded to JSON exactly once. Those buffers are handed to
+/// every transport's `send_batch_bytes` and, with compression enabled, are
+/// streamed through gzip as one JSON array to size the compressed payload.
+#[derive(Debug, Clone, Default)]
+pub struct EncodedBatch {
+    /// `serde_json` encoding of each packet, in batch order
+    pub packets: Vec<Vec<u8>>,
+    /// Size of the batch as a JSON array
+    pub uncompressed_bytes: usize,
+    /// Size of the batch payload, after gzip if compression was requested
+    pub payload_bytes: usize,
+}
+
+impl EncodedBatch {
+    /// Serialize every packet of `batch` once, optionally gzip-sizing the result
+    pub fn encode(batch: &[TelemetryPacket], compress: bool) -> Result<Self, StreamingError> {
+        let packets = batch
+            .iter()
+            .map(serde_json::to_vec)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| StreamingError::Transport(TransportError::Serialization(e)))?;
+        // Brackets plus a comma between consecutive packets
+        let uncompressed_bytes =
+            packets.iter().map(Vec::len).sum::<usize>() + packets.len().saturating_sub(1) + 2;
+
+        let payload_bytes = if compress {
+            let span = tracing::debug_span!(
+                "compress_batch",
+                uncompressed_bytes,
+                compressed_bytes = tracing::field::Empty,
+            );
+            let _entered = span.enter();
+            let compressed = gzip_array_len(&packets)
+                .map_err(|e| StreamingError::CompressionFailed(e.to_string()))?;
+            span.record("compressed_bytes", compressed);
+            compressed
+        } else {
+            uncompressed_bytes
+        };
+
+        Ok(Self {
+            packets,
+            uncompressed_bytes,
+            payload_bytes,
+        })
+    }
+}
+
+/// Gzip `packets` as a JSON array, returning only the compressed length
+fn gzip_array_len(packets: &[Vec<u8>]) -> std::io::Result<usize> {
+    use flate2::Compression;
+    use std::io::Write;
+
+    /// Sink that counts the bytes written to it
+    struct ByteCounter(usize);
+
+    impl Write for ByteCounter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut encoder = flate2::write::GzEncoder::new(ByteCounter(0), Compression::default());
+    encoder.write_all(b"[")?;
+    for (index, json) in packets.iter().enumerate() {
+        if index > 0 {
+            encoder.write_all(b",")?;
+        }
+        encoder.write_all(json)?;
+    }
+    encoder.write_all(b"]")?;
+    Ok(encoder.finish()?.0)
 }
 
 impl Default for PipelineConfig {
@@ -56,10 +203,184 @@ impl Default for PipelineConfig {
             enable_compression: true,
             channel_capacity: 256,
             enable_resilience: true,
+            delivery_policy: DeliveryPolicy::default(),
+            precision: None,
+            heartbeat: None,
         }
     }
 }
 
+/// Snapshot of a pipeline's throughput counters, see `StreamingPipeline::metrics`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct PipelineMetrics {
+    /// Packets taken off the pipeline's queue
+    pub packets_received: u64,
+    /// Batches delivered according to the `DeliveryPolicy`
+    pub batches_sent: u64,
+    /// Payload bytes of delivered batches, after compression when enabled
+    pub bytes_sent: u64,
+    /// `bytes_sent` over the uncompressed JSON size of the same batches
+    /// (1.0 if none)
+    pub compression_ratio_avg: f32,
+    /// Packets moved to the offline buffer
+    pub packets_buffered: u64,
+    /// Packets lost because they were neither delivered nor buffered
+    pub packets_dropped: u64,
+}
+
+/// Send counters for one transport, see `StreamingPipeline::transport_metrics`
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct TransportMetrics {
+    /// Transport identifier (see `Transport::name`)
+    pub name: String,
+    /// Batches the transport accepted
+    pub batches_sent: u64,
+    /// Batches the transport failed to send
+    pub send_failures: u64,
+}
+
+/// Atomic counters behind `PipelineMetrics`, shared with the pipeline task
+#[derive(Debug, Default)]
+struct PipelineCounters {
+    packets_received: AtomicU64,
+    batches_sent: AtomicU64,
+    bytes_sent: AtomicU64,
+    uncompressed_bytes: AtomicU64,
+    packets_buffered: AtomicU64,
+    packets_dropped: AtomicU64,
+    /// Per-transport counters, indexed like the pipeline's transport list
+    transports: std::sync::Mutex<Vec<TransportMetrics>>,
+}
+
+impl PipelineCounters {
+    fn record_received(&self) {
+        self.packets_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_batch(&self, packet_count: usize, result: &Result<BatchReport, StreamingError>) {
+        let Ok(report) = result else {
+            self.packets_dropped
+                .fetch_add(packet_count as u64, Ordering::Relaxed);
+            return;
+        };
+        if report.delivered && !report.outcomes.is_empty() {
+            self.batches_sent.fetch_add(1, Ordering::Relaxed);
+        }
+        if !report.outcomes.is_empty() {
+            let mut transports = self.transports.lock().unwrap();
+            for outcome in &report.outcomes {
+                if transports.len() <= outcome.index {
+                    transports.resize_with(outcome.index + 1, Default::default);
+                }
+                let metrics = &mut transports[outcome.index];
+                if metrics.name != outcome.name {
+                    metrics.name = outcome.name.clone();
+                }
+                if outcome.result.is_ok() {
+                    metrics.batches_sent += 1;
+                } else {
+                    metrics.send_failures += 1;
+                }
+            }
+        }
+        if report.delivered {
+            self.bytes_sent
+                .fetch_add(report.payload_bytes as u64, Ordering::Relaxed);
+            self.uncompressed_bytes
+                .fetch_add(report.uncompressed_bytes as u64, Ordering::Relaxed);
+        }
+        self.packets_buffered
+            .fetch_add(report.buffered as u64, Ordering::Relaxed);
+        self.packets_dropped
+            .fetch_add(report.dropped as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> PipelineMetrics {
+        let bytes_sent = self.bytes_sent.load(Ordering::Relaxed);
+        let uncompressed = self.uncompressed_bytes.load(Ordering::Relaxed);
+        PipelineMetrics {
+            packets_received: self.packets_received.load(Ordering::Relaxed),
+            batches_sent: self.batches_sent.load(Ordering::Relaxed),
+            bytes_sent,
+            compression_ratio_avg: if uncompressed == 0 {
+                1.0
+            } else {
+                bytes_sent as f32 / uncompressed as f32
+            },
+            packets_buffered: self.packets_buffered.load(Ordering::Relaxed),
+            packets_dropped: self.packets_dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Delivery health of a running pipeline, as seen by its background task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PipelineState {
+    /// Batches are being delivered
+    Running,
+    /// The most recent batch could not be delivered
+    Degraded,
+    /// Delivery has failed for `failure_threshold` consecutive batches
+    Failed,
+}
+
+/// Tracks consecutive undelivered batches and publishes the resulting state
+struct PipelineStateTracker {
+    tx: watch::Sender<PipelineState>,
+    failed_after: u32,
+    consecutive_undelivered: u32,
+    counters: Arc<PipelineCounters>,
+}
+
+impl PipelineStateTracker {
+    fn new(
+        tx: watch::Sender<PipelineState>,
+        failed_after: u32,
+        counters: Arc<PipelineCounters>,
+    ) -> Self {
+        Self {
+            tx,
+            failed_after: failed_after.max(1),
+            consecutive_undelivered: 0,
+            counters,
+        }
+    }
+
+    /// Record the outcome of one batch send of `packet_count` packets
+    fn observe(&mut self, packet_count: usize, result: Result<BatchReport, StreamingError>) {
+        self.counters.record_batch(packet_count, &result);
+        let delivered = match result {
+            Ok(report) => report.delivered,
+            Err(e) => {
+                tracing::error!("Pipeline batch send error: {}", e);
+                false
+            }
+        };
+
+        let state = if delivered {
+            self.consecutive_undelivered = 0;
+            PipelineState::Running
+        } else {
+            self.consecutive_undelivered += 1;
+            if self.consecutive_undelivered >= self.failed_after {
+                PipelineState::Failed
+            } else {
+                PipelineState::Degraded
+            }
+        };
+
+        self.tx.send_if_modified(|current| {
+            if *current == state {
+                false
+            } else {
+                tracing::warn!("Pipeline state changed: {:?} -> {:?}", current, state);
+                *current = state;
+                true
+            }
+        });
+    }
+}
+
 /// Compressed batch metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompressedBatch {
@@ -90,14 +411,140 @@ pub enum PipelineTransport {
     Mqtt(MqttTransport),
     /// Serial/UART adapter
     Serial(SerialTransport),
+    /// HTTP POST adapter
+    #[cfg(feature = "http_transport")]
+    Http(crate::http_transport::HttpTransport),
+    /// Fire-and-forget UDP datagrams
+    Udp(UdpTransport),
+    /// In-memory recorder (tests and local inspection)
+    Memory(MemoryTransport),
+    /// Any other transport implementation
+    Custom(Box<dyn Transport>),
 }
 
 impl PipelineTransport {
+    /// Construct the transport described by `spec`
+    pub async fn from_spec(spec: &TransportSpec) -> Result<Self, TransportError> {
+        match spec {
+            TransportSpec::Mqtt {
+                broker: Some(broker),
+                ..
+            } => Self::connect_broker(broker).await,
+            TransportSpec::Mqtt {
+                out_path,
+                sanitize_non_finite,
+                broker: None,
+            } => Ok(Self::Mqtt(
+                MqttTransport::new(out_path.clone())
+                    .await?
+                    .with_sanitize_non_finite(*sanitize_non_finite),
+            )),
+            TransportSpec::Serial {
+                out_path,
+                sanitize_non_finite,
+            } => Ok(Self::Serial(
+                SerialTransport::new(out_path.clone())
+                    .await?
+                    .with_sanitize_non_finite(*sanitize_non_finite),
+            )),
+            TransportSpec::File {
+                path,
+                max_bytes,
+                max_files,
+                sanitize_non_finite,
+            } => Ok(Self::Custom(Box::new(
+                RotatingFileTransport::new(path.clone(), max_bytes.unwrap_or(u64::MAX), *max_files)
+                    .await?
+                    .with_sanitize_non_finite(*sanitize_non_finite),
+            ))),
+        }
+    }
+
+    /// Connect a real MQTT client to `broker`
+    #[cfg(feature = "mqtt_real")]
+    async fn connect_broker(broker: &MqttBrokerSpec) -> Result<Self, TransportError> {
+        let defaults = crate::MqttConfig::default();
+        let config = crate::MqttConfig {
+            host: broker.host.clone(),
+            port: broker.port,
+            client_id: broker.client_id.clone().unwrap_or(defaults.client_id),
+            topic: broker.topic.clone(),
+            qos: broker.qos,
+            use_tls: broker.use_tls,
+            ..Default::default()
+        };
+        let transport = crate::RealMqttTransport::new(config)
+            .await
+            .map_err(|e| TransportError::Other(e.to_string()))?;
+        Ok(Self::Custom(Box::new(transport)))
+    }
+
+    /// Broker connections need the `mqtt_real` feature
+    #[cfg(not(feature = "mqtt_real"))]
+    async fn connect_broker(broker: &MqttBrokerSpec) -> Result<Self, TransportError> {
+        Err(TransportError::Other(format!(
+            "MQTT broker {}:{} requires the mqtt_real feature",
+            broker.host, broker.port
+        )))
+    }
+
     /// Send a packet through this transport
     pub async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
         match self {
             Self::Mqtt(t) => t.send(packet).await,
             Self::Serial(t) => t.send(packet).await,
+            #[cfg(feature = "http_transport")]
+            Self::Http(t) => t.send(packet).await,
+            Self::Udp(t) => t.send(packet).await,
+            Self::Memory(t) => t.send(packet).await,
+            Self::Custom(t) => t.send(packet).await,
+        }
+    }
+
+    /// Send a packet using its pre-serialized JSON encoding (see `Transport::send_bytes`)
+    pub async fn send_bytes(
+        &self,
+        packet: &TelemetryPacket,
+        json: &[u8],
+    ) -> Result<(), TransportError> {
+        match self {
+            Self::Mqtt(t) => t.send_bytes(packet, json).await,
+            Self::Serial(t) => t.send_bytes(packet, json).await,
+            #[cfg(feature = "http_transport")]
+            Self::Http(t) => t.send_bytes(packet, json).await,
+            Self::Udp(t) => t.send_bytes(packet, json).await,
+            Self::Memory(t) => t.send_bytes(packet, json).await,
+            Self::Custom(t) => t.send_bytes(packet, json).await,
+        }
+    }
+
+    /// Send a batch using per-packet pre-serialized JSON (see `Transport::send_batch_bytes`)
+    pub async fn send_batch_bytes(
+        &self,
+        packets: &[TelemetryPacket],
+        encoded: &[Vec<u8>],
+    ) -> Result<(), TransportError> {
+        match self {
+            Self::Mqtt(t) => t.send_batch_bytes(packets, encoded).await,
+            Self::Serial(t) => t.send_batch_bytes(packets, encoded).await,
+            #[cfg(feature = "http_transport")]
+            Self::Http(t) => t.send_batch_bytes(packets, encoded).await,
+            Self::Udp(t) => t.send_batch_bytes(packets, encoded).await,
+            Self::Memory(t) => t.send_batch_bytes(packets, encoded).await,
+            Self::Custom(t) => t.send_batch_bytes(packets, encoded).await,
+        }
+    }
+
+    /// Short identifier used to attribute send results (see `Transport::name`)
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Mqtt(t) => t.name(),
+            Self::Serial(t) => t.name(),
+            #[cfg(feature = "http_transport")]
+            Self::Http(t) => t.name(),
+            Self::Udp(t) => t.name(),
+            Self::Memory(t) => t.name(),
+            Self::Custom(t) => t.name(),
         }
     }
 }
@@ -116,6 +563,8 @@ pub struct StreamingPipeline {
     tx: Sender<TelemetryPacket>,
     _config: PipelineConfig,
     _task_handle: Arc<tokio::task::JoinHandle<()>>,
+    state_rx: watch::Receiver<PipelineState>,
+    counters: Arc<PipelineCounters>,
     /// Resilience components (optional)
     pub circuit_breaker: Option<Arc<CircuitBreaker>>,
     pub offline_buffer: Option<Arc<OfflineBuffer>>,
@@ -126,22 +575,41 @@ impl StreamingPipeline {
     pub async fn new(
         config: PipelineConfig,
         transports: Vec<PipelineTransport>,
+    ) -> Result<Self, StreamingError> {
+        Self::with_resilience_config(config, ResilienceConfig::default(), transports).await
+    }
+
+    /// Create a new streaming pipeline using an explicit resilience configuration.
+    pub async fn with_resilience_config(
+        config: PipelineConfig,
+        resilience_config: ResilienceConfig,
+        transports: Vec<PipelineTransport>,
     ) -> Result<Self, StreamingError> {
         let (tx, rx) = mpsc::channel(config.channel_capacity);
 
         // Initialize resilience components if enabled
         let (circuit_breaker, offline_buffer) = if config.enable_resilience {
-            let resilience_config = ResilienceConfig::default();
             let cb = Arc::new(CircuitBreaker::new(
                 resilience_config.failure_threshold,
                 resilience_config.half_open_timeout_secs,
             ));
-            let ob = Arc::new(OfflineBuffer::new(resilience_config.buffer_size));
+            let ob = Arc::new(
+                OfflineBuffer::new(resilience_config.buffer_size)
+                    .with_policy(resilience_config.overflow_policy),
+            );
             (Some(cb), Some(ob))
         } else {
             (None, None)
         };
 
+        let (state_tx, state_rx) = watch::channel(PipelineState::Running);
+        let counters = Arc::new(PipelineCounters::default());
+        let state = PipelineStateTracker::new(
+            state_tx,
+            resilience_config.failure_threshold,
+            counters.clone(),
+        );
+
         let pipeline_config = config.clone();
         let handle = tokio::spawn(Self::run_pipeline(
             rx,
@@ -149,12 +617,15 @@ impl StreamingPipeline {
             transports,
             circuit_breaker.clone(),
             offline_buffer.clone(),
+            state,
         ));
 
         Ok(Self {
             tx,
             _config: config,
             _task_handle: Arc::new(handle),
+            state_rx,
+            counters,
             circuit_breaker,
             offline_buffer,
         })
@@ -166,6 +637,42 @@ impl StreamingPipeline {
         self.tx.clone()
     }
 
+    /// Watch the pipeline's delivery state
+    ///
+    /// The state becomes `Degraded` when a batch is not delivered and `Failed`
+    /// after `failure_threshold` consecutive undelivered batches; it returns to
+    /// `Running` on the next delivered batch.
+    pub fn watch_state(&self) -> watch::Receiver<PipelineState> {
+        self.state_rx.clone()
+    }
+
+    /// Snapshot the pipeline's throughput counters
+    pub fn metrics(&self) -> PipelineMetrics {
+        self.counters.snapshot()
+    }
+
+    /// Snapshot the send counters of each transport, in transport order
+    ///
+    /// Transports appear once the first batch has been sent through them.
+    pub fn transport_metrics(&self) -> Vec<TransportMetrics> {
+        self.counters.transports.lock().unwrap().clone()
+    }
+
+    /// Pull a packet from `source` every `interval` and feed it into the pipeline.
+    ///
+    /// Production stops when the source is exhausted or the returned handle is stopped.
+    pub fn pump_from<S>(&self, source: S, interval: Duration) -> ProducerHandle
+    where
+        S: TelemetrySource + 'static,
+    {
+        ProducerHandle::spawn_source(
+            self.get_sender(),
+            interval,
+            tokio_util::sync::CancellationToken::new(),
+            source,
+        )
+    }
+
     /// Main pipeline task: batch, compress, send with resilience.
     async fn run_pipeline(
         mut rx: Receiver<TelemetryPacket>,
@@ -173,10 +680,15 @@ impl StreamingPipeline {
         transports: Vec<PipelineTransport>,
         circuit_breaker: Option<Arc<CircuitBreaker>>,
         offline_buffer: Option<Arc<OfflineBuffer>>,
+        mut state: PipelineStateTracker,
     ) {
         let mut batch: Vec<TelemetryPacket> = Vec::with_capacity(config.batch_size);
         let mut batch_start = Instant::now();
         let timeout = Duration::from_secs(config.batch_timeout_secs);
+        let mut heartbeat = config
+            .heartbeat
+            .as_ref()
+            .map(HeartbeatGenerator::from_config);
 
         loop {
             let elapsed = batch_start.elapsed();
@@ -185,39 +697,45 @@ impl StreamingPipeline {
             } else {
                 Duration::from_secs(0)
             };
+            let heartbeat_due = heartbeat
+                .as_ref()
+                .map(HeartbeatGenerator::time_until_due)
+                .unwrap_or_default();
 
             tokio::select! {
                 Some(packet) = rx.recv() => {
+                    state.counters.record_received();
+                    if let Some(ref mut generator) = heartbeat {
+                        generator.record_activity();
+                    }
                     batch.push(packet);
                     if batch.len() >= config.batch_size {
-                        if let Err(e) = Self::send_batch(&batch, &config, &transports, &circuit_breaker, &offline_buffer).await {
-                            tracing::error!("Pipeline batch send error: {}", e);
-                        }
+                        state.observe(batch.len(), Self::send_batch(&batch, &config, &transports, &circuit_breaker, &offline_buffer).await);
                         batch.clear();
                         batch_start = Instant::now();
                     }
                 }
                 _ = sleep(remaining), if !batch.is_empty() => {
-                    if let Err(e) = Self::send_batch(&batch, &config, &transports, &circuit_breaker, &offline_buffer).await {
-                        tracing::error!("Pipeline batch send error: {}", e);
-                    }
+                    state.observe(batch.len(), Self::send_batch(&batch, &config, &transports, &circuit_breaker, &offline_buffer).await);
                     batch.clear();
                     batch_start = Instant::now();
                 }
+                _ = sleep(heartbeat_due), if heartbeat.is_some() && batch.is_empty() && !rx.is_closed() => {
+                    if let Some(packet) = heartbeat.as_mut().and_then(HeartbeatGenerator::poll) {
+                        state.observe(1, Self::send_batch(&[packet], &config, &transports, &circuit_breaker, &offline_buffer).await);
+                    }
+                }
                 else => {
                     while let Ok(packet) = rx.try_recv() {
+                        state.counters.record_received();
                         batch.push(packet);
                         if batch.len() >= config.batch_size {
-                            if let Err(e) = Self::send_batch(&batch, &config, &transports, &circuit_breaker, &offline_buffer).await {
-                                tracing::error!("Pipeline batch send error: {}", e);
-                            }
+                            state.observe(batch.len(), Self::send_batch(&batch, &config, &transports, &circuit_breaker, &offline_buffer).await);
                             batch.clear();
                         }
                     }
                     if !batch.is_empty() {
-                        if let Err(e) = Self::send_batch(&batch, &config, &transports, &circuit_breaker, &offline_buffer).await {
-                            tracing::error!("Pipeline final batch send error: {}", e);
-                        }
+                        state.observe(batch.len(), Self::send_batch(&batch, &config, &transports, &circuit_breaker, &offline_buffer).await);
                     }
                     break;
                 }
@@ -225,92 +743,117 @@ impl StreamingPipeline {
         }
     }
 
+    #[tracing::instrument(
+        name = "send_batch",
+        skip_all,
+        fields(
+            packet_count = batch.len(),
+            transport_count = transports.len(),
+            compressed = config.enable_compression,
+            payload_bytes = tracing::field::Empty,
+            delivered = tracing::field::Empty,
+        )
+    )]
     async fn send_batch(
         batch: &[TelemetryPacket],
         config: &PipelineConfig,
         transports: &[PipelineTransport],
         circuit_breaker: &Option<Arc<CircuitBreaker>>,
         offline_buffer: &Option<Arc<OfflineBuffer>>,
-    ) -> Result<(), StreamingError> {
+    ) -> Result<BatchReport, StreamingError> {
         if batch.is_empty() {
-            return Ok(());
+            return Ok(BatchReport {
+                delivered: true,
+                ..Default::default()
+            });
         }
 
-        let uncompressed_json = serde_json::to_string(batch)
-            .map_err(|e| StreamingError::Transport(TransportError::Serialization(e)))?;
-        let _uncompressed_size = uncompressed_json.len();
-
-        let _payload = if config.enable_compression {
-            use flate2::Compression;
-            use std::io::Write;
-
-            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), Compression::default());
-            encoder
-                .write_all(uncompressed_json.as_bytes())
-                .map_err(|e| StreamingError::CompressionFailed(e.to_string()))?;
-            encoder
-                .finish()
-                .map_err(|e| StreamingError::CompressionFailed(e.to_string()))?
-        } else {
-            uncompressed_json.into_bytes()
+        let rounded: Vec<TelemetryPacket>;
+        let batch = match config.precision {
+            Some(ref precision) => {
+                rounded = batch
+                    .iter()
+                    .cloned()
+                    .map(|mut packet| {
+                        precision.apply(&mut packet);
+                        packet
+                    })
+                    .collect();
+                &rounded[..]
+            }
+            None => batch,
         };
 
+        // Serialize each packet once, then share the bytes with every transport
+        let encoded = EncodedBatch::encode(batch, config.enable_compression)?;
+        let payload_bytes = encoded.payload_bytes;
+        tracing::Span::current().record("payload_bytes", payload_bytes);
+
         // Check circuit breaker before sending
         if let Some(ref cb) = circuit_breaker {
             cb.try_half_open().await;
             if cb.state().await == crate::resilience::CircuitState::Open {
                 // Circuit is open, buffer packets offline if possible
                 if let Some(ref ob) = offline_buffer {
-                    for packet in batch {
-                        ob.push(packet.clone()).await.ok(); // ignore buffer full
-                    }
+                    let buffered = Self::buffer_offline(ob, batch).await;
                     tracing::warn!(
                         "Circuit breaker open, buffered {} packets offline",
-                        batch.len()
+                        buffered
                     );
-                    return Ok(());
+                    return Ok(BatchReport {
+                        buffered,
+                        dropped: batch.len() - buffered,
+                        ..Default::default()
+                    });
                 }
             }
         }
 
-        // Send to all transports concurrently
+        let assembly = tracing::debug_span!("assemble_batch", packet_count = batch.len());
+        let assembly_guard = assembly.enter();
         let mut send_futures = Vec::new();
-        for transport in transports {
-            let packet = TelemetryPacket {
-                sequence: batch.first().map(|p| p.sequence).unwrap_or(0),
-                timestamp: chrono::Utc::now(),
-                health: batch.first().map(|p| p.health.clone()).unwrap_or_default(),
-                sensor_readings: batch
-                    .iter()
-                    .flat_map(|p| p.sensor_readings.clone())
-                    .collect(),
-                diagnostics: batch
-                    .first()
-                    .map(|p| p.diagnostics.clone())
-                    .unwrap_or_default(),
-            };
-            send_futures.push(transport.send(Box::leak(Box::new(packet))));
+        for (index, transport) in transports.iter().enumerate() {
+            let span = tracing::info_span!("transport_send", transport = transport.name(), index);
+            send_futures.push(
+                transport
+                    .send_batch_bytes(batch, &encoded.packets)
+                    .instrument(span),
+            );
         }
+        drop(assembly_guard);
 
         let results = futures::future::join_all(send_futures).await;
-        let mut all_succeeded = true;
-        for result in results {
-            if let Err(e) = result {
-                all_succeeded = false;
-                if let Some(ref cb) = circuit_breaker {
-                    cb.record_failure().await;
-                }
-                // Buffer failed packets if offline buffering enabled
-                if let Some(ref ob) = offline_buffer {
-                    for packet in batch {
-                        ob.push(packet.clone()).await.ok();
-                    }
-                }
-                tracing::warn!("Transport send failed: {}, buffered packets offline", e);
+        let outcomes: Vec<TransportOutcome> = transports
+            .iter()
+            .zip(results)
+            .enumerate()
+            .map(|(index, (transport, result))| TransportOutcome {
+                index,
+                name: transport.name().to_string(),
+                result,
+            })
+            .collect();
+
+        for outcome in outcomes.iter().filter(|o| o.result.is_err()) {
+            if let Err(ref e) = outcome.result {
+                tracing::warn!(
+                    "Transport {} ({}) send failed: {}",
+                    outcome.index,
+                    outcome.name,
+                    e
+                );
             }
         }
 
-        if all_succeeded {
+        let mut buffered = 0;
+        let succeeded = outcomes.iter().filter(|o| o.result.is_ok()).count();
+        let delivered = match config.delivery_policy {
+            DeliveryPolicy::AllOf => succeeded == outcomes.len(),
+            DeliveryPolicy::AnyOf => succeeded > 0,
+        };
+        tracing::Span::current().record("delivered", delivered);
+
+        if delivered {
             if let Some(ref cb) = circuit_breaker {
                 cb.record_success().await;
             }
@@ -318,20 +861,122 @@ impl StreamingPipeline {
             if let Some(ref ob) = offline_buffer {
                 while let Some(buffered_packet) = ob.pop().await {
                     for transport in transports {
-                        let _ = transport.send(&buffered_packet).await;
+                        if let Err(e) = transport.send(&buffered_packet).await {
+                            tracing::warn!(
+                                "Transport {} failed to resend buffered packet {}: {}",
+                                transport.name(),
+                                buffered_packet.sequence,
+                                e
+                            );
+                        }
                     }
                 }
             }
+        } else {
+            if let Some(ref cb) = circuit_breaker {
+                cb.record_failure().await;
+            }
+            // Buffer failed packets if offline buffering enabled
+            if let Some(ref ob) = offline_buffer {
+                buffered = Self::buffer_offline(ob, batch).await;
+                tracing::warn!("Batch not delivered, buffered {} packets offline", buffered);
+            }
         }
 
-        Ok(())
+        Ok(BatchReport {
+            outcomes,
+            delivered,
+            payload_bytes,
+            uncompressed_bytes: encoded.uncompressed_bytes,
+            buffered,
+            dropped: if delivered { 0 } else { batch.len() - buffered },
+        })
+    }
+
+    /// Push `batch` into the offline buffer, returning how many packets fit
+    async fn buffer_offline(buffer: &OfflineBuffer, batch: &[TelemetryPacket]) -> usize {
+        let mut buffered = 0;
+        for packet in batch {
+            if buffer.push(packet.clone()).await.is_ok() {
+                buffered += 1;
+            }
+        }
+        buffered
+    }
+}
+
+/// Declarative builder that assembles a `StreamingPipeline` and its transports.
+///
+/// # Example
+/// ```ignore
+/// let config = ConfigLoader::new().load().await?;
+/// let pipeline = PipelineBuilder::from_config(&config).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PipelineBuilder {
+    pipeline: PipelineConfig,
+    resilience: ResilienceConfig,
+    transports: Vec<TransportSpec>,
+}
+
+impl PipelineBuilder {
+    /// Create a builder with default pipeline and resilience settings and no transports
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the pipeline described by a loaded `TelemetryConfig`
+    pub async fn from_config(
+        config: &TelemetryConfig,
+    ) -> Result<StreamingPipeline, StreamingError> {
+        Self::new()
+            .pipeline_config(config.pipeline.clone())
+            .resilience_config(config.resilience.clone())
+            .transports(config.transports.iter().cloned())
+            .build()
+            .await
+    }
+
+    /// Set the pipeline configuration
+    pub fn pipeline_config(mut self, config: PipelineConfig) -> Self {
+        self.pipeline = config;
+        self
+    }
+
+    /// Set the resilience configuration
+    pub fn resilience_config(mut self, config: ResilienceConfig) -> Self {
+        self.resilience = config;
+        self
+    }
+
+    /// Add a transport
+    pub fn transport(mut self, spec: TransportSpec) -> Self {
+        self.transports.push(spec);
+        self
+    }
+
+    /// Add several transports
+    pub fn transports(mut self, specs: impl IntoIterator<Item = TransportSpec>) -> Self {
+        self.transports.extend(specs);
+        self
+    }
+
+    /// Construct the configured transports and start the pipeline
+    pub async fn build(self) -> Result<StreamingPipeline, StreamingError> {
+        let mut transports = Vec::with_capacity(self.transports.len());
+        for spec in &self.transports {
+            transports.push(PipelineTransport::from_spec(spec).await?);
+        }
+
+        StreamingPipeline::with_resilience_config(self.pipeline, self.resilience, transports).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{DiagnosticsReport, SystemHealth};
+    use crate::source::MockSource;
+    use crate::{DiagnosticsReport, SystemHealth, SCHEMA_VERSION};
     use std::path::PathBuf;
 
     #[tokio::test]
@@ -342,6 +987,7 @@ mod tests {
             enable_compression: false,
             enable_resilience: false,
             channel_capacity: 256,
+            ..Default::default()
         };
 
         let out = PathBuf::from("target/test_output/streaming_batch.log");
@@ -354,10 +1000,12 @@ mod tests {
         for i in 0..2 {
             let packet = TelemetryPacket {
                 sequence: i,
+                schema_version: SCHEMA_VERSION,
                 timestamp: chrono::Utc::now(),
                 health: SystemHealth::new(),
                 sensor_readings: vec![],
                 diagnostics: DiagnosticsReport::new(),
+                metadata: Default::default(),
             };
             sender.send(packet).await.unwrap();
         }
@@ -369,6 +1017,209 @@ mod tests {
         assert!(meta.len() > 0);
     }
 
+    #[tokio::test]
+    async fn test_pump_from_mock_source_reaches_transport() {
+        let config = PipelineConfig {
+            batch_size: 1,
+            batch_timeout_secs: 1,
+            enable_compression: false,
+            enable_resilience: false,
+            channel_capacity: 16,
+            ..Default::default()
+        };
+        let memory = MemoryTransport::new();
+        let pipeline =
+            StreamingPipeline::new(config, vec![PipelineTransport::Memory(memory.clone())])
+                .await
+                .unwrap();
+
+        let producer = pipeline.pump_from(MockSource::new(5), Duration::from_millis(1));
+        assert_eq!(producer.join().await, 5);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let sequences: Vec<u64> = memory.packets().await.iter().map(|p| p.sequence).collect();
+        assert_eq!(sequences, vec![0, 1, 2, 3, 4]);
+    }
+
+    struct FailingTransport;
+
+    #[async_trait::async_trait]
+    impl Transport for FailingTransport {
+        async fn send(&self, _packet: &TelemetryPacket) -> Result<(), TransportError> {
+            Err(TransportError::Other("link down".to_string()))
+        }
+
+        fn name(&self) -> &str {
+            "failing"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_attributes_results_per_policy() {
+        let memory = MemoryTransport::new();
+        let transports = vec![
+            PipelineTransport::Custom(Box::new(FailingTransport)),
+            PipelineTransport::Memory(memory.clone()),
+        ];
+        let batch = vec![TelemetryPacket::new(1), TelemetryPacket::new(2)];
+
+        for (policy, expect_delivered) in [
+            (DeliveryPolicy::AllOf, false),
+            (DeliveryPolicy::AnyOf, true),
+        ] {
+            let config = PipelineConfig {
+                enable_compression: false,
+                enable_resilience: false,
+                delivery_policy: policy,
+                ..Default::default()
+            };
+            let report = StreamingPipeline::send_batch(&batch, &config, &transports, &None, &None)
+                .await
+                .unwrap();
+
+            assert_eq!(report.delivered, expect_delivered, "{:?}", policy);
+            assert_eq!(report.outcomes.len(), 2);
+            assert_eq!(report.outcomes[0].index, 0);
+            assert_eq!(report.outcomes[0].name, "failing");
+            assert!(report.outcomes[0].result.is_err());
+            assert_eq!(report.outcomes[1].index, 1);
+            assert_eq!(report.outcomes[1].name, "memory");
+            assert!(report.outcomes[1].result.is_ok());
+
+            let failed: Vec<usize> = report.failures().map(|o| o.index).collect();
+            assert_eq!(failed, vec![0]);
+        }
+        // Both packets, once per policy
+        assert_eq!(memory.len().await, 4);
+    }
+
+    type SpanFields = std::collections::HashMap<String, String>;
+
+    /// Layer recording the name and fields of every span it sees
+    #[derive(Clone, Default)]
+    struct SpanCapture {
+        spans: Arc<std::sync::Mutex<Vec<(String, SpanFields)>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut SpanFields);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for SpanCapture
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut fields = SpanFields::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            self.spans
+                .lock()
+                .unwrap()
+                .push((attrs.metadata().name().to_string(), fields));
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let name = ctx.span(id).map(|s| s.name()).unwrap_or_default();
+            let mut spans = self.spans.lock().unwrap();
+            if let Some((_, fields)) = spans.iter_mut().rev().find(|(n, _)| n == name) {
+                values.record(&mut FieldVisitor(fields));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_emits_spans() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let capture = SpanCapture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let config = PipelineConfig {
+            enable_compression: true,
+            enable_resilience: false,
+            ..Default::default()
+        };
+        let transports = vec![PipelineTransport::Memory(MemoryTransport::new())];
+        let batch = vec![TelemetryPacket::new(1), TelemetryPacket::new(2)];
+        StreamingPipeline::send_batch(&batch, &config, &transports, &None, &None)
+            .await
+            .unwrap();
+
+        let spans = capture.spans.lock().unwrap();
+        let (_, fields) = spans
+            .iter()
+            .find(|(name, _)| name == "send_batch")
+            .expect("send_batch span emitted");
+        assert_eq!(fields["packet_count"], "2");
+        assert_eq!(fields["transport_count"], "1");
+        assert_eq!(fields["delivered"], "true");
+        assert!(fields.contains_key("payload_bytes"));
+
+        let names: Vec<&str> = spans.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(names.contains(&"compress_batch"));
+        assert!(names.contains(&"transport_send"));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_state_reports_failing_transports() {
+        let config = PipelineConfig {
+            batch_size: 1,
+            enable_compression: false,
+            enable_resilience: true,
+            ..Default::default()
+        };
+        let resilience = ResilienceConfig {
+            failure_threshold: 3,
+            ..Default::default()
+        };
+        let pipeline = StreamingPipeline::with_resilience_config(
+            config,
+            resilience,
+            vec![PipelineTransport::Custom(Box::new(FailingTransport))],
+        )
+        .await
+        .unwrap();
+        let mut state = pipeline.watch_state();
+        assert_eq!(*state.borrow(), PipelineState::Running);
+
+        let sender = pipeline.get_sender();
+        sender.send(TelemetryPacket::new(0)).await.unwrap();
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            state.wait_for(|s| *s == PipelineState::Degraded),
+        )
+        .await
+        .expect("pipeline did not report Degraded")
+        .unwrap();
+
+        for i in 1..3 {
+            sender.send(TelemetryPacket::new(i)).await.unwrap();
+        }
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            state.wait_for(|s| *s == PipelineState::Failed),
+        )
+        .await
+        .expect("pipeline did not report Failed")
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_compression_ratio() {
         let batch = CompressedBatch {
@@ -381,4 +1232,365 @@ mod tests {
         let ratio = batch.compression_ratio();
         assert!((ratio - 0.5).abs() < 0.01);
     }
+
+    #[tokio::test]
+    async fn test_pipeline_builder_from_config_file() {
+        let config_dir = PathBuf::from("target/test_output/builder_config");
+        let out = PathBuf::from("target/test_output/builder_serial.log");
+        let file_out = PathBuf::from("target/test_output/builder_file.log");
+        tokio::fs::create_dir_all(&config_dir).await.unwrap();
+        let _ = tokio::fs::remove_file(&out).await;
+        let _ = tokio::fs::remove_file(&file_out).await;
+
+        let yaml = format!(
+            r#"
+pipeline:
+  batch_size: 1
+  batch_timeout_secs: 1
+  enable_compression: false
+  channel_capacity: 16
+  enable_resilience: false
+resilience:
+  max_retries: 3
+  initial_backoff_ms: 100
+  max_backoff_ms: 1000
+  backoff_multiplier: 2.0
+  failure_threshold: 5
+  half_open_timeout_secs: 30
+  buffer_size: 100
+transports:
+  - kind: serial
+    out_path: {}
+  - kind: file
+    path: {}
+    max_bytes: 1048576
+"#,
+            out.display(),
+            file_out.display()
+        );
+        tokio::fs::write(config_dir.join("telemetry.yaml"), yaml)
+            .await
+            .unwrap();
+
+        let config = crate::ConfigLoader::new()
+            .with_config_dir(&config_dir)
+            .load()
+            .await
+            .unwrap();
+        assert_eq!(
+            config.transports,
+            vec![
+                TransportSpec::Serial {
+                    out_path: Some(out.clone()),
+                    sanitize_non_finite: false,
+                },
+                TransportSpec::File {
+                    path: file_out.clone(),
+                    max_bytes: Some(1_048_576),
+                    max_files: 5,
+                    sanitize_non_finite: false,
+                },
+            ]
+        );
+
+        let pipeline = PipelineBuilder::from_config(&config).await.unwrap();
+        pipeline
+            .get_sender()
+            .send(TelemetryPacket::new(7))
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        for path in [out, file_out] {
+            let content = tokio::fs::read_to_string(&path).await.unwrap();
+            assert!(content.contains("\"sequence\":7"), "{}", path.display());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mqtt_spec_with_broker_parameters() {
+        let yaml = "kind: mqtt\nbroker:\n  host: broker.local\n  qos: 2\n";
+        let spec: TransportSpec = serde_yaml::from_str(yaml).unwrap();
+        let TransportSpec::Mqtt {
+            broker: Some(ref broker),
+            ..
+        } = spec
+        else {
+            panic!("expected an MQTT spec with a broker, got {:?}", spec);
+        };
+        assert_eq!(broker.host, "broker.local");
+        assert_eq!(broker.port, 1883);
+        assert_eq!(broker.topic, "telemetry/system");
+        assert_eq!(broker.qos, 2);
+
+        #[cfg(not(feature = "mqtt_real"))]
+        {
+            let error = PipelineTransport::from_spec(&spec).await.err().unwrap();
+            assert!(error.to_string().contains("mqtt_real"), "{}", error);
+        }
+    }
+
+    /// Records the pre-serialized bytes handed to `send_batch_bytes`
+    #[derive(Clone, Default)]
+    struct BytesRecorder {
+        received: Arc<std::sync::Mutex<Vec<Vec<u8>>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for BytesRecorder {
+        async fn send(&self, _packet: &TelemetryPacket) -> Result<(), TransportError> {
+            panic!("send_batch should use send_batch_bytes");
+        }
+
+        async fn send_batch_bytes(
+            &self,
+            _packets: &[TelemetryPacket],
+            encoded: &[Vec<u8>],
+        ) -> Result<(), TransportError> {
+            self.received.lock().unwrap().extend_from_slice(encoded);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_shares_identical_bytes() {
+        let recorders = [
+            BytesRecorder::default(),
+            BytesRecorder::default(),
+            BytesRecorder::default(),
+        ];
+        let mut transports: Vec<PipelineTransport> = recorders
+            .iter()
+            .map(|r| PipelineTransport::Custom(Box::new(r.clone())))
+            .collect();
+        let out = PathBuf::from("target/test_output/serialize_once.log");
+        let _ = tokio::fs::remove_file(&out).await;
+        transports.push(PipelineTransport::Serial(
+            SerialTransport::new(Some(out.clone())).await.unwrap(),
+        ));
+
+        let config = PipelineConfig {
+            enable_compression: false,
+            enable_resilience: false,
+            ..Default::default()
+        };
+        let batch = vec![TelemetryPacket::new(1), TelemetryPacket::new(2)];
+        StreamingPipeline::send_batch(&batch, &config, &transports, &None, &None)
+            .await
+            .unwrap();
+
+        let first = recorders[0].received.lock().unwrap().clone();
+        assert_eq!(first.len(), 2);
+        for recorder in &recorders {
+            assert_eq!(*recorder.received.lock().unwrap(), first);
+        }
+        let decoded: TelemetryPacket = serde_json::from_slice(&first[1]).unwrap();
+        assert_eq!(decoded.sequence, 2);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let written = tokio::fs::read(&out).await.unwrap();
+        assert_eq!(
+            written,
+            [first[0].as_slice(), b"\n", first[1].as_slice(), b"\n"].concat()
+        );
+    }
+
+    #[test]
+    fn test_encoded_batch_serializes_each_packet_once() {
+        let batch: Vec<_> = (0..4).map(TelemetryPacket::new).collect();
+        let plain = EncodedBatch::encode(&batch, false).unwrap();
+        for (packet, json) in batch.iter().zip(&plain.packets) {
+            assert_eq!(*json, serde_json::to_vec(packet).unwrap());
+        }
+        let array = serde_json::to_vec(&batch).unwrap();
+        assert_eq!(plain.uncompressed_bytes, array.len());
+        assert_eq!(plain.payload_bytes, array.len());
+
+        let compressed = EncodedBatch::encode(&batch, true).unwrap();
+        assert_eq!(compressed.packets, plain.packets);
+        assert!(compressed.payload_bytes < compressed.uncompressed_bytes);
+        assert_eq!(
+            EncodedBatch::encode(&[], false).unwrap().uncompressed_bytes,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_forwards_every_packet() {
+        let memory = MemoryTransport::new();
+        let transports = vec![PipelineTransport::Memory(memory.clone())];
+        let config = PipelineConfig {
+            enable_compression: false,
+            enable_resilience: false,
+            ..Default::default()
+        };
+
+        let mut degraded = SystemHealth::new();
+        degraded.degraded_components = 1;
+        degraded.recalculate_status();
+        let batch = vec![
+            TelemetryPacket::new(1),
+            TelemetryPacket::builder()
+                .sequence(2)
+                .health(degraded)
+                .build(),
+        ];
+        StreamingPipeline::send_batch(&batch, &config, &transports, &None, &None)
+            .await
+            .unwrap();
+
+        // Each packet keeps its own sequence and health
+        let packets = memory.packets().await;
+        let sequences: Vec<_> = packets.iter().map(|p| p.sequence).collect();
+        assert_eq!(sequences, vec![1, 2]);
+        assert_eq!(packets[1].health.degraded_components, 1);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_metrics_track_throughput() {
+        let memory = MemoryTransport::new();
+        let config = PipelineConfig {
+            batch_size: 4,
+            enable_compression: true,
+            enable_resilience: false,
+            ..Default::default()
+        };
+        let pipeline =
+            StreamingPipeline::new(config, vec![PipelineTransport::Memory(memory.clone())])
+                .await
+                .unwrap();
+        assert_eq!(
+            pipeline.metrics(),
+            PipelineMetrics {
+                compression_ratio_avg: 1.0,
+                ..Default::default()
+            }
+        );
+
+        let sender = pipeline.get_sender();
+        for seq in 0..8 {
+            sender.send(TelemetryPacket::new(seq)).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let metrics = pipeline.metrics();
+        assert_eq!(metrics.packets_received, 8);
+        assert_eq!(metrics.batches_sent, 2);
+        // Similar packets compress well, so gzip must shrink the payload
+        let json: usize = memory
+            .packets()
+            .await
+            .chunks(4)
+            .map(|batch| serde_json::to_vec(batch).unwrap().len())
+            .sum();
+        assert!(metrics.bytes_sent > 0 && metrics.bytes_sent < json as u64);
+        assert!(
+            metrics.compression_ratio_avg < 0.5,
+            "ratio {}",
+            metrics.compression_ratio_avg
+        );
+        assert_eq!(metrics.packets_buffered, 0);
+        assert_eq!(metrics.packets_dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_metrics_count_drops() {
+        let config = PipelineConfig {
+            batch_size: 3,
+            enable_compression: false,
+            enable_resilience: false,
+            ..Default::default()
+        };
+        let pipeline = StreamingPipeline::new(
+            config,
+            vec![PipelineTransport::Custom(Box::new(FailingTransport))],
+        )
+        .await
+        .unwrap();
+
+        let sender = pipeline.get_sender();
+        for seq in 0..3 {
+            sender.send(TelemetryPacket::new(seq)).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let metrics = pipeline.metrics();
+        assert_eq!(metrics.packets_received, 3);
+        assert_eq!(metrics.batches_sent, 0);
+        assert_eq!(metrics.packets_dropped, 3);
+        assert_eq!(metrics.bytes_sent, 0);
+        assert_eq!(metrics.compression_ratio_avg, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_transport_metrics_attribute_failures_by_name() {
+        let config = PipelineConfig {
+            batch_size: 2,
+            enable_compression: false,
+            enable_resilience: false,
+            delivery_policy: DeliveryPolicy::AnyOf,
+            ..Default::default()
+        };
+        let pipeline = StreamingPipeline::new(
+            config,
+            vec![
+                PipelineTransport::Memory(MemoryTransport::new()),
+                PipelineTransport::Custom(Box::new(FailingTransport)),
+            ],
+        )
+        .await
+        .unwrap();
+        assert!(pipeline.transport_metrics().is_empty());
+
+        let sender = pipeline.get_sender();
+        for seq in 0..4 {
+            sender.send(TelemetryPacket::new(seq)).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let metrics = pipeline.transport_metrics();
+        assert_eq!(
+            metrics,
+            vec![
+                TransportMetrics {
+                    name: "memory".to_string(),
+                    batches_sent: 2,
+                    send_failures: 0,
+                },
+                TransportMetrics {
+                    name: "failing".to_string(),
+                    batches_sent: 0,
+                    send_failures: 2,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_sends_heartbeat_when_idle() {
+        let memory = MemoryTransport::new();
+        let config = PipelineConfig {
+            enable_compression: false,
+            enable_resilience: false,
+            heartbeat: Some(HeartbeatConfig {
+                device_id: "dev-7".to_string(),
+                interval_ms: 50,
+            }),
+            ..Default::default()
+        };
+        let _pipeline =
+            StreamingPipeline::new(config, vec![PipelineTransport::Memory(memory.clone())])
+                .await
+                .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(memory.is_empty().await);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let packets = memory.packets().await;
+        assert_eq!(packets.len(), 1);
+        assert!(packets[0].is_heartbeat());
+        assert_eq!(packets[0].metadata["device_id"], "dev-7");
+    }
 }
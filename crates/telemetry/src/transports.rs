@@ -1,12 +1,16 @@
+use crate::chain::HashChain;
 use crate::TelemetryPacket;
 use async_trait::async_trait;
 use serde_json;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::fs::{self, OpenOptions};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc::{self, Sender};
+use tokio::sync::RwLock;
 
 /// Error type for transport operations
 #[derive(Error, Debug)]
@@ -17,6 +21,10 @@ pub enum TransportError {
     Serialization(#[from] serde_json::Error),
     #[error("Transport closed or channel error")]
     Closed,
+    #[error("HTTP request rejected with status {0}")]
+    HttpStatus(u16),
+    #[error("Payload of {size} bytes exceeds the {max} byte limit")]
+    PayloadTooLarge { size: usize, max: usize },
     #[error("Other: {0}")]
     Other(String),
 }
@@ -25,23 +33,193 @@ pub enum TransportError {
 pub trait Transport: Send + Sync {
     /// Send a telemetry packet over this transport
     async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError>;
+
+    /// Short identifier used in logs and per-transport metrics
+    ///
+    /// Defaults to `"custom"`; implementations should override it so
+    /// operators can tell which sink is failing.
+    fn name(&self) -> &str {
+        "custom"
+    }
+
+    /// Send a packet whose JSON encoding has already been produced
+    ///
+    /// `json` must be the `serde_json` encoding of `packet`. Callers sending
+    /// the same packet to several transports serialize it once and share the
+    /// bytes; transports that write JSON use them directly. The default
+    /// implementation ignores `json` and calls `send`.
+    async fn send_bytes(
+        &self,
+        packet: &TelemetryPacket,
+        json: &[u8],
+    ) -> Result<(), TransportError> {
+        let _ = json;
+        self.send(packet).await
+    }
+
+    /// Send a batch of packets, in order
+    ///
+    /// The default implementation sends each packet with `send` and stops at
+    /// the first error. Transports that can deliver a batch in one operation
+    /// should override it.
+    async fn send_batch(&self, packets: &[TelemetryPacket]) -> Result<(), TransportError> {
+        for packet in packets {
+            self.send(packet).await?;
+        }
+        Ok(())
+    }
+
+    /// Send a batch whose per-packet JSON encodings have already been produced
+    ///
+    /// `encoded[i]` must be the `serde_json` encoding of `packets[i]`; see
+    /// `send_bytes`. The default implementation ignores `encoded` and calls
+    /// `send_batch`.
+    async fn send_batch_bytes(
+        &self,
+        packets: &[TelemetryPacket],
+        encoded: &[Vec<u8>],
+    ) -> Result<(), TransportError> {
+        let _ = encoded;
+        self.send_batch(packets).await
+    }
 }
 
-/// Simple MQTT transport adapter.
+/// Send each packet of a batch with its shared encoding via `send_bytes`
+async fn send_each_bytes<T: Transport + ?Sized>(
+    transport: &T,
+    packets: &[TelemetryPacket],
+    encoded: &[Vec<u8>],
+) -> Result<(), TransportError> {
+    for (packet, json) in packets.iter().zip(encoded) {
+        transport.send_bytes(packet, json).await?;
+    }
+    Ok(())
+}
+
+/// Serialize a packet to JSON, optionally zeroing non-finite floats first
+fn encode_packet(
+    packet: &TelemetryPacket,
+    sanitize_non_finite: bool,
+) -> Result<String, TransportError> {
+    if sanitize_non_finite {
+        let mut sanitized = packet.clone();
+        let replaced = sanitized.sanitize_non_finite();
+        if replaced > 0 {
+            tracing::warn!(
+                "Replaced {} non-finite value(s) in packet {}",
+                replaced,
+                packet.sequence
+            );
+        }
+        Ok(serde_json::to_string(&sanitized)?)
+    } else {
+        Ok(serde_json::to_string(packet)?)
+    }
+}
+
+/// Convert pre-serialized JSON into a writer line
+fn json_line(json: &[u8]) -> Result<String, TransportError> {
+    String::from_utf8(json.to_vec())
+        .map_err(|e| TransportError::Other(format!("pre-serialized JSON is not UTF-8: {}", e)))
+}
+
+/// Clears the shared liveness flag when the writer task exits for any reason
+struct WriterAliveGuard(Arc<AtomicBool>);
+
+impl Drop for WriterAliveGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Size-based rollover settings for a `FileWriter`
+#[derive(Debug, Clone, Copy)]
+struct Rotation {
+    max_bytes: u64,
+    max_files: usize,
+}
+
+impl Rotation {
+    /// Shift `path.N` to `path.N+1`, move `path` to `path.1` and open a fresh `path`
+    ///
+    /// Each step is a rename, so readers always see either the old or the new
+    /// file under every name. The oldest file past `max_files` is removed.
+    async fn rotate(&self, path: &Path) -> std::io::Result<tokio::fs::File> {
+        if self.max_files == 0 {
+            remove_if_exists(path).await?;
+        } else {
+            remove_if_exists(&rotated_path(path, self.max_files)).await?;
+            for index in (1..self.max_files).rev() {
+                rename_if_exists(&rotated_path(path, index), &rotated_path(path, index + 1))
+                    .await?;
+            }
+            rename_if_exists(path, &rotated_path(path, 1)).await?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+    }
+}
+
+/// Path of the `index`th rotated file, e.g. `telemetry.log.1`
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+async fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+async fn rename_if_exists(from: &Path, to: &Path) -> std::io::Result<()> {
+    match fs::rename(from, to).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// How long dropping a file transport blocks while its writer drains
+const DROP_FLUSH_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Background task appending lines to a file, owned by a file-backed transport.
 ///
-/// By default this adapter serializes `TelemetryPacket` to JSON and appends to a file
-/// `telemetry_out/mqtt_publish.log`. This provides an out-of-the-box, deterministic
-/// implementation for testing and local monitoring. Replace with a real MQTT client
-/// (e.g. `rumqttc`) behind a feature flag when needed.
-pub struct MqttTransport {
-    tx: Sender<String>,
-    _task_handle: Arc<tokio::task::JoinHandle<()>>,
+/// Closing the channel makes the writer drain every queued line and exit.
+/// `Drop` does this and then blocks for up to `DROP_FLUSH_TIMEOUT` until the
+/// writer has finished, so lines sent just before a transport is dropped
+/// still reach the file. The wait runs inside `block_in_place`, so the
+/// worker's other tasks move to another thread instead of stalling. It only
+/// happens on a multi-threaded runtime, where the writer can make progress
+/// meanwhile; elsewhere the drain is best-effort and `close` should be
+/// awaited instead.
+struct FileWriter {
+    label: &'static str,
+    tx: Option<Sender<String>>,
+    handle: tokio::task::JoinHandle<()>,
+    alive: Arc<AtomicBool>,
 }
 
-impl MqttTransport {
-    /// Create a new MQTT transport that writes JSON messages to `out_path`.
-    pub async fn new(out_path: Option<PathBuf>) -> Result<Self, TransportError> {
-        let path = out_path.unwrap_or_else(|| PathBuf::from("telemetry_out/mqtt_publish.log"));
+impl FileWriter {
+    /// Spawn a writer appending each received message as a line to `path`.
+    ///
+    /// The task exits on the first open or write error (e.g. disk full) and
+    /// clears its liveness flag, so transports can report `Closed` instead of
+    /// silently accepting messages that will never be written.
+    async fn spawn(label: &'static str, path: PathBuf) -> Result<Self, TransportError> {
+        Self::spawn_with_rotation(label, path, None).await
+    }
+
+    /// Spawn a writer that rolls `path` over according to `rotation`, if any
+    async fn spawn_with_rotation(
+        label: &'static str,
+        path: PathBuf,
+        rotation: Option<Rotation>,
+    ) -> Result<Self, TransportError> {
         let parent_dir = path
             .parent()
             .map(|p| p.to_path_buf())
@@ -49,53 +227,186 @@ impl MqttTransport {
         fs::create_dir_all(&parent_dir).await?;
 
         let (tx, mut rx) = mpsc::channel::<String>(256);
+        let alive = Arc::new(AtomicBool::new(true));
+        let guard = WriterAliveGuard(alive.clone());
 
-        // spawn background task to consume channel and write to file
-        let file_path = path.clone();
         let handle = tokio::spawn(async move {
+            let _guard = guard;
             // open in append mode
             let mut file = match OpenOptions::new()
                 .create(true)
                 .append(true)
-                .open(&file_path)
+                .open(&path)
                 .await
             {
                 Ok(f) => f,
                 Err(e) => {
-                    eprintln!(
-                        "MqttTransport failed to open file {}: {}",
-                        file_path.display(),
-                        e
-                    );
+                    eprintln!("{} failed to open file {}: {}", label, path.display(), e);
                     return;
                 }
             };
 
-            while let Some(msg) = rx.recv().await {
+            let mut size = match file.metadata().await {
+                Ok(meta) => meta.len(),
+                Err(_) => 0,
+            };
+
+            while let Some(mut msg) = rx.recv().await {
+                msg.push('\n');
+                if let Some(rotation) = rotation {
+                    let len = msg.len() as u64;
+                    if size > 0 && size + len > rotation.max_bytes {
+                        file = match rotation.rotate(&path).await {
+                            Ok(f) => f,
+                            Err(e) => {
+                                eprintln!("{} rotation error, stopping writer: {}", label, e);
+                                return;
+                            }
+                        };
+                        size = 0;
+                    }
+                    size += len;
+                }
                 if let Err(e) = file.write_all(msg.as_bytes()).await {
-                    eprintln!("MqttTransport write error: {}", e);
-                    // best-effort continue
+                    eprintln!("{} write error, stopping writer: {}", label, e);
+                    return;
                 }
-                if let Err(e) = file.write_all(b"\n").await {
-                    eprintln!("MqttTransport write newline error: {}", e);
+                if let Err(e) = file.flush().await {
+                    eprintln!("{} flush error, stopping writer: {}", label, e);
+                    return;
                 }
-                // flush occasionally
-                let _ = file.flush().await;
             }
         });
 
         Ok(Self {
-            tx,
-            _task_handle: Arc::new(handle),
+            label,
+            tx: Some(tx),
+            handle,
+            alive,
+        })
+    }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+    }
+
+    /// Queue a line for writing
+    async fn send(&self, line: String) -> Result<(), TransportError> {
+        if !self.is_alive() {
+            return Err(TransportError::Closed);
+        }
+        let tx = self.tx.as_ref().ok_or(TransportError::Closed)?;
+        tx.send(line).await.map_err(|_| TransportError::Closed)
+    }
+
+    /// Stop accepting lines and wait until every queued line is written
+    async fn close(&mut self) {
+        if self.tx.take().is_some() {
+            if let Err(e) = (&mut self.handle).await {
+                eprintln!("{} writer task failed: {}", self.label, e);
+            }
+        }
+    }
+}
+
+impl Drop for FileWriter {
+    fn drop(&mut self) {
+        // Dropping the sender lets the writer drain its queue and exit
+        if self.tx.take().is_none() {
+            return;
+        }
+        let multi_thread = tokio::runtime::Handle::try_current()
+            .map(|h| h.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread)
+            .unwrap_or(false);
+        if !multi_thread {
+            return;
+        }
+
+        let deadline = Instant::now() + DROP_FLUSH_TIMEOUT;
+        tokio::task::block_in_place(|| {
+            while self.is_alive() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        });
+        if self.is_alive() {
+            eprintln!(
+                "{} writer still flushing {:?} after drop, queued lines may be lost",
+                self.label, DROP_FLUSH_TIMEOUT
+            );
+        }
+    }
+}
+
+/// Simple MQTT transport adapter.
+///
+/// By default this adapter serializes `TelemetryPacket` to JSON and appends to a file
+/// `telemetry_out/mqtt_publish.log`. This provides an out-of-the-box, deterministic
+/// implementation for testing and local monitoring. Replace with a real MQTT client
+/// (e.g. `rumqttc`) behind a feature flag when needed.
+pub struct MqttTransport {
+    writer: FileWriter,
+    sanitize_non_finite: bool,
+}
+
+impl MqttTransport {
+    /// Create a new MQTT transport that writes JSON messages to `out_path`.
+    pub async fn new(out_path: Option<PathBuf>) -> Result<Self, TransportError> {
+        let path = out_path.unwrap_or_else(|| PathBuf::from("telemetry_out/mqtt_publish.log"));
+        let writer = FileWriter::spawn("MqttTransport", path).await?;
+
+        Ok(Self {
+            writer,
+            sanitize_non_finite: false,
         })
     }
+
+    /// Replace NaN/infinite floats with 0.0 before serializing packets
+    pub fn with_sanitize_non_finite(mut self, enabled: bool) -> Self {
+        self.sanitize_non_finite = enabled;
+        self
+    }
+
+    /// Check whether the background file writer is still running
+    pub fn is_writer_alive(&self) -> bool {
+        self.writer.is_alive()
+    }
+
+    /// Write every queued message and stop the background writer
+    ///
+    /// Later sends fail with `Closed`. Dropping the transport also drains the
+    /// queue, but only waits briefly and only on a multi-threaded runtime.
+    pub async fn close(&mut self) {
+        self.writer.close().await;
+    }
 }
 
 #[async_trait]
 impl Transport for MqttTransport {
+    fn name(&self) -> &str {
+        "mqtt"
+    }
+
     async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
-        let json = serde_json::to_string(packet)?;
-        self.tx.send(json).await.map_err(|_| TransportError::Closed)
+        let json = encode_packet(packet, self.sanitize_non_finite)?;
+        self.writer.send(json).await
+    }
+    async fn send_bytes(
+        &self,
+        packet: &TelemetryPacket,
+        json: &[u8],
+    ) -> Result<(), TransportError> {
+        // Shared bytes were encoded without sanitizing, so re-encode if asked to
+        if self.sanitize_non_finite {
+            return self.send(packet).await;
+        }
+        self.writer.send(json_line(json)?).await
+    }
+    async fn send_batch_bytes(
+        &self,
+        packets: &[TelemetryPacket],
+        encoded: &[Vec<u8>],
+    ) -> Result<(), TransportError> {
+        send_each_bytes(self, packets, encoded).await
     }
 }
 
@@ -105,71 +416,557 @@ impl Transport for MqttTransport {
 /// `telemetry_out/serial.log`. Replace with `tokio-serial` or another serial library
 /// behind a feature flag for real hardware.
 pub struct SerialTransport {
-    tx: Sender<String>,
-    _task_handle: Arc<tokio::task::JoinHandle<()>>,
+    writer: FileWriter,
+    sanitize_non_finite: bool,
 }
 
 impl SerialTransport {
     /// Create a new Serial transport that writes JSON messages to `out_path`.
     pub async fn new(out_path: Option<PathBuf>) -> Result<Self, TransportError> {
         let path = out_path.unwrap_or_else(|| PathBuf::from("telemetry_out/serial.log"));
-        let parent_dir = path
-            .parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| PathBuf::from("."));
-        fs::create_dir_all(&parent_dir).await?;
+        let writer = FileWriter::spawn("SerialTransport", path).await?;
 
-        let (tx, mut rx) = mpsc::channel::<String>(256);
-        let file_path = path.clone();
+        Ok(Self {
+            writer,
+            sanitize_non_finite: false,
+        })
+    }
 
-        let handle = tokio::spawn(async move {
-            let mut file = match OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(&file_path)
-                .await
-            {
-                Ok(f) => f,
-                Err(e) => {
-                    eprintln!(
-                        "SerialTransport failed to open file {}: {}",
-                        file_path.display(),
-                        e
+    /// Replace NaN/infinite floats with 0.0 before serializing packets
+    pub fn with_sanitize_non_finite(mut self, enabled: bool) -> Self {
+        self.sanitize_non_finite = enabled;
+        self
+    }
+
+    /// Check whether the background file writer is still running
+    pub fn is_writer_alive(&self) -> bool {
+        self.writer.is_alive()
+    }
+
+    /// Write every queued message and stop the background writer
+    ///
+    /// Later sends fail with `Closed`. Dropping the transport also drains the
+    /// queue, but only waits briefly and only on a multi-threaded runtime.
+    pub async fn close(&mut self) {
+        self.writer.close().await;
+    }
+}
+
+#[async_trait]
+impl Transport for SerialTransport {
+    fn name(&self) -> &str {
+        "serial"
+    }
+
+    async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+        let json = encode_packet(packet, self.sanitize_non_finite)?;
+        self.writer.send(json).await
+    }
+    async fn send_bytes(
+        &self,
+        packet: &TelemetryPacket,
+        json: &[u8],
+    ) -> Result<(), TransportError> {
+        // Shared bytes were encoded without sanitizing, so re-encode if asked to
+        if self.sanitize_non_finite {
+            return self.send(packet).await;
+        }
+        self.writer.send(json_line(json)?).await
+    }
+    async fn send_batch_bytes(
+        &self,
+        packets: &[TelemetryPacket],
+        encoded: &[Vec<u8>],
+    ) -> Result<(), TransportError> {
+        send_each_bytes(self, packets, encoded).await
+    }
+}
+
+/// File transport that rolls its log over once it reaches a size limit.
+///
+/// Packets are appended as JSON lines to the active file (e.g.
+/// `telemetry.log`). When the next line would push it past `max_bytes`, the
+/// active file becomes `telemetry.log.1`, older files shift up by one, and
+/// anything beyond `max_files` rotated files is deleted. A single line larger
+/// than `max_bytes` is still written, alone in its own file.
+pub struct RotatingFileTransport {
+    writer: FileWriter,
+    sanitize_non_finite: bool,
+}
+
+impl RotatingFileTransport {
+    /// Create a transport writing to `path`, keeping at most `max_files` rotated files
+    pub async fn new(
+        path: impl Into<PathBuf>,
+        max_bytes: u64,
+        max_files: usize,
+    ) -> Result<Self, TransportError> {
+        let rotation = Rotation {
+            max_bytes: max_bytes.max(1),
+            max_files,
+        };
+        let writer =
+            FileWriter::spawn_with_rotation("RotatingFileTransport", path.into(), Some(rotation))
+                .await?;
+
+        Ok(Self {
+            writer,
+            sanitize_non_finite: false,
+        })
+    }
+
+    /// Replace NaN/infinite floats with 0.0 before serializing packets
+    pub fn with_sanitize_non_finite(mut self, enabled: bool) -> Self {
+        self.sanitize_non_finite = enabled;
+        self
+    }
+
+    /// Check whether the background file writer is still running
+    pub fn is_writer_alive(&self) -> bool {
+        self.writer.is_alive()
+    }
+
+    /// Write every queued message and stop the background writer
+    pub async fn close(&mut self) {
+        self.writer.close().await;
+    }
+}
+
+#[async_trait]
+impl Transport for RotatingFileTransport {
+    fn name(&self) -> &str {
+        "rotating_file"
+    }
+
+    async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+        let json = encode_packet(packet, self.sanitize_non_finite)?;
+        self.writer.send(json).await
+    }
+    async fn send_bytes(
+        &self,
+        packet: &TelemetryPacket,
+        json: &[u8],
+    ) -> Result<(), TransportError> {
+        // Shared bytes were encoded without sanitizing, so re-encode if asked to
+        if self.sanitize_non_finite {
+            return self.send(packet).await;
+        }
+        self.writer.send(json_line(json)?).await
+    }
+    async fn send_batch_bytes(
+        &self,
+        packets: &[TelemetryPacket],
+        encoded: &[Vec<u8>],
+    ) -> Result<(), TransportError> {
+        send_each_bytes(self, packets, encoded).await
+    }
+}
+
+/// Largest UDP payload that fits a standard 1500 byte Ethernet MTU
+/// (1500 minus 20 bytes of IPv4 header and 8 bytes of UDP header)
+pub const DEFAULT_UDP_MAX_PAYLOAD: usize = 1472;
+
+/// Fire-and-forget transport sending each packet as one UDP datagram.
+///
+/// Packets are sent as JSON, one per datagram, with no acknowledgement or
+/// retransmission: a lost datagram is simply gone. Packets whose encoding is
+/// larger than the maximum payload (`DEFAULT_UDP_MAX_PAYLOAD` unless changed
+/// with `with_max_payload`) are not fragmented but rejected with
+/// `TransportError::PayloadTooLarge`, since IP fragments are dropped as a
+/// whole if any one of them is lost. Within a batch an oversized packet is
+/// skipped with a warning and counted in `oversized_count`, and the rest of
+/// the batch is still sent. Receivers should size their buffers to at least
+/// the configured maximum.
+pub struct UdpTransport {
+    socket: tokio::net::UdpSocket,
+    max_payload: usize,
+    oversized: AtomicU64,
+}
+
+impl UdpTransport {
+    /// Create a transport sending datagrams to `target`
+    pub async fn new(target: impl tokio::net::ToSocketAddrs) -> Result<Self, TransportError> {
+        let target = tokio::net::lookup_host(target)
+            .await?
+            .next()
+            .ok_or_else(|| {
+                TransportError::Other("UDP target did not resolve to an address".to_string())
+            })?;
+        let local = if target.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = tokio::net::UdpSocket::bind(local).await?;
+        socket.connect(target).await?;
+        Ok(Self {
+            socket,
+            max_payload: DEFAULT_UDP_MAX_PAYLOAD,
+            oversized: AtomicU64::new(0),
+        })
+    }
+
+    /// Set the largest datagram payload to send
+    pub fn with_max_payload(mut self, max_payload: usize) -> Self {
+        self.max_payload = max_payload;
+        self
+    }
+
+    /// Get the largest datagram payload this transport sends
+    pub fn max_payload(&self) -> usize {
+        self.max_payload
+    }
+
+    /// Get the number of packets skipped from batches for exceeding the
+    /// maximum payload
+    pub fn oversized_count(&self) -> u64 {
+        self.oversized.load(Ordering::Relaxed)
+    }
+
+    async fn send_datagram(&self, json: &[u8]) -> Result<(), TransportError> {
+        if json.len() > self.max_payload {
+            return Err(TransportError::PayloadTooLarge {
+                size: json.len(),
+                max: self.max_payload,
+            });
+        }
+        self.socket.send(json).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Transport for UdpTransport {
+    fn name(&self) -> &str {
+        "udp"
+    }
+
+    async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+        let json = serde_json::to_vec(packet)?;
+        self.send_datagram(&json).await
+    }
+    async fn send_bytes(
+        &self,
+        _packet: &TelemetryPacket,
+        json: &[u8],
+    ) -> Result<(), TransportError> {
+        self.send_datagram(json).await
+    }
+    async fn send_batch_bytes(
+        &self,
+        packets: &[TelemetryPacket],
+        encoded: &[Vec<u8>],
+    ) -> Result<(), TransportError> {
+        for (packet, json) in packets.iter().zip(encoded) {
+            match self.send_datagram(json).await {
+                Err(TransportError::PayloadTooLarge { size, max }) => {
+                    self.oversized.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!(
+                        "Skipping packet {}: {} bytes exceeds UDP payload limit of {}",
+                        packet.sequence,
+                        size,
+                        max
                     );
-                    return;
                 }
-            };
+                result => result?,
+            }
+        }
+        Ok(())
+    }
+}
 
-            while let Some(msg) = rx.recv().await {
-                if let Err(e) = file.write_all(msg.as_bytes()).await {
-                    eprintln!("SerialTransport write error: {}", e);
-                }
-                if let Err(e) = file.write_all(b"\n").await {
-                    eprintln!("SerialTransport write newline error: {}", e);
+/// In-memory transport that records every packet it is asked to send.
+///
+/// Clones share the same storage, so a test can keep one handle while the
+/// pipeline owns another and inspect what was delivered.
+#[derive(Clone, Default)]
+pub struct MemoryTransport {
+    packets: Arc<RwLock<Vec<TelemetryPacket>>>,
+}
+
+impl MemoryTransport {
+    /// Create an empty memory transport
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a copy of all packets sent so far
+    pub async fn packets(&self) -> Vec<TelemetryPacket> {
+        self.packets.read().await.clone()
+    }
+
+    /// Get the number of packets sent so far
+    pub async fn len(&self) -> usize {
+        self.packets.read().await.len()
+    }
+
+    /// Check whether no packets have been sent yet
+    pub async fn is_empty(&self) -> bool {
+        self.packets.read().await.is_empty()
+    }
+}
+
+#[async_trait]
+impl Transport for MemoryTransport {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+        self.packets.write().await.push(packet.clone());
+        Ok(())
+    }
+}
+
+/// Rule deciding which packets a `DownsampleTransport` forwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownsampleMode {
+    /// Forward the first packet and every Nth one after it
+    EveryNth(u64),
+    /// Forward at most one packet per time window
+    Window(Duration),
+}
+
+/// Transport wrapper forwarding only a subset of packets to an expensive sink.
+///
+/// Packets that are skipped are reported as sent successfully.
+pub struct DownsampleTransport<T: Transport> {
+    inner: T,
+    mode: DownsampleMode,
+    seen: AtomicU64,
+    last_forwarded: Mutex<Option<Instant>>,
+}
+
+impl<T: Transport> DownsampleTransport<T> {
+    /// Wrap `inner`, forwarding according to `mode`
+    pub fn new(inner: T, mode: DownsampleMode) -> Self {
+        Self {
+            inner,
+            mode,
+            seen: AtomicU64::new(0),
+            last_forwarded: Mutex::new(None),
+        }
+    }
+
+    /// Forward one packet out of every `n`
+    pub fn every_nth(inner: T, n: u64) -> Self {
+        Self::new(inner, DownsampleMode::EveryNth(n.max(1)))
+    }
+
+    /// Forward at most one packet per `window`
+    pub fn per_window(inner: T, window: Duration) -> Self {
+        Self::new(inner, DownsampleMode::Window(window))
+    }
+
+    /// Get the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    fn should_forward(&self) -> bool {
+        match self.mode {
+            DownsampleMode::EveryNth(n) => self
+                .seen
+                .fetch_add(1, Ordering::SeqCst)
+                .is_multiple_of(n.max(1)),
+            DownsampleMode::Window(window) => {
+                let mut last = self.last_forwarded.lock().unwrap();
+                let now = Instant::now();
+                match *last {
+                    Some(previous) if now.duration_since(previous) < window => false,
+                    _ => {
+                        *last = Some(now);
+                        true
+                    }
                 }
-                let _ = file.flush().await;
             }
-        });
+        }
+    }
+}
 
-        Ok(Self {
-            tx,
-            _task_handle: Arc::new(handle),
-        })
+#[async_trait]
+impl<T: Transport> Transport for DownsampleTransport<T> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+        if self.should_forward() {
+            self.inner.send(packet).await
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn send_bytes(
+        &self,
+        packet: &TelemetryPacket,
+        json: &[u8],
+    ) -> Result<(), TransportError> {
+        if self.should_forward() {
+            self.inner.send_bytes(packet, json).await
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Transport wrapper stamping each packet with a tamper-evident chain hash.
+///
+/// The chain only advances when the inner send succeeds, so the stored log
+/// verifies with `verify_chain` even if some sends failed.
+pub struct HashChainTransport<T: Transport> {
+    inner: T,
+    chain: tokio::sync::Mutex<HashChain>,
+}
+
+impl<T: Transport> HashChainTransport<T> {
+    /// Wrap `inner`, starting a new chain
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            chain: tokio::sync::Mutex::new(HashChain::new()),
+        }
+    }
+
+    /// Get the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
     }
 }
 
 #[async_trait]
-impl Transport for SerialTransport {
+impl<T: Transport> Transport for HashChainTransport<T> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
     async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
-        let json = serde_json::to_string(packet)?;
-        self.tx.send(json).await.map_err(|_| TransportError::Closed)
+        // Held across the send so packets are chained in the order written
+        let mut chain = self.chain.lock().await;
+        let mut next = *chain;
+        let mut linked = packet.clone();
+        next.link(&mut linked);
+        self.inner.send(&linked).await?;
+        *chain = next;
+        Ok(())
+    }
+}
+
+/// What a `FanOutTransport` does when a sink's queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueueFullPolicy {
+    /// Drop the packet for that sink only and count it
+    #[default]
+    Drop,
+    /// Wait for space in that sink's queue (a slow sink slows the sender)
+    Block,
+}
+
+/// One fan-out destination with its own queue and writer task
+struct FanOutSink {
+    tx: Sender<TelemetryPacket>,
+    dropped: AtomicU64,
+    task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for FanOutSink {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+    }
+}
+
+/// Transport duplicating every packet to several inner transports.
+///
+/// Each inner transport gets its own bounded queue drained by a dedicated
+/// task, so a slow sink only backs up its own queue. Inner send errors are
+/// logged by the sink task since `send` returns once packets are queued.
+/// Dropping the transport aborts the sink tasks, discarding packets still
+/// queued or in flight.
+pub struct FanOutTransport {
+    sinks: Vec<FanOutSink>,
+    policy: QueueFullPolicy,
+}
+
+impl FanOutTransport {
+    /// Fan out to `transports`, each with a queue of `queue_capacity` packets
+    pub fn new(
+        transports: Vec<Arc<dyn Transport>>,
+        queue_capacity: usize,
+        policy: QueueFullPolicy,
+    ) -> Self {
+        let sinks = transports
+            .into_iter()
+            .enumerate()
+            .map(|(index, transport)| {
+                let (tx, mut rx) = mpsc::channel::<TelemetryPacket>(queue_capacity.max(1));
+                let handle = tokio::spawn(async move {
+                    while let Some(packet) = rx.recv().await {
+                        if let Err(e) = transport.send(&packet).await {
+                            tracing::warn!(
+                                "Fan-out sink {} ({}) send failed: {}",
+                                index,
+                                transport.name(),
+                                e
+                            );
+                        }
+                    }
+                });
+                FanOutSink {
+                    tx,
+                    dropped: AtomicU64::new(0),
+                    task_handle: handle,
+                }
+            })
+            .collect();
+
+        Self { sinks, policy }
+    }
+
+    /// Get the number of packets dropped for each sink, in registration order
+    pub fn dropped_counts(&self) -> Vec<u64> {
+        self.sinks
+            .iter()
+            .map(|sink| sink.dropped.load(Ordering::SeqCst))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Transport for FanOutTransport {
+    fn name(&self) -> &str {
+        "fan_out"
+    }
+
+    async fn send(&self, packet: &TelemetryPacket) -> Result<(), TransportError> {
+        let mut closed = false;
+        for sink in &self.sinks {
+            let result = match self.policy {
+                QueueFullPolicy::Drop => match sink.tx.try_send(packet.clone()) {
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        sink.dropped.fetch_add(1, Ordering::SeqCst);
+                        Ok(())
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => Err(()),
+                    Ok(()) => Ok(()),
+                },
+                QueueFullPolicy::Block => sink.tx.send(packet.clone()).await.map_err(|_| ()),
+            };
+            closed |= result.is_err();
+        }
+
+        if closed {
+            Err(TransportError::Closed)
+        } else {
+            Ok(())
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{SystemHealth, TelemetryPacket};
+    use crate::{SystemHealth, TelemetryPacket, SCHEMA_VERSION};
 
     #[tokio::test]
     async fn test_mqtt_transport_send() {
@@ -178,15 +975,270 @@ mod tests {
             .unwrap();
         let packet = TelemetryPacket {
             sequence: 1,
+            schema_version: SCHEMA_VERSION,
             timestamp: chrono::Utc::now(),
             health: SystemHealth::new(),
             sensor_readings: vec![],
             diagnostics: Default::default(),
+            metadata: Default::default(),
         };
 
         transport.send(&packet).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_sanitize_non_finite_sensor_value() {
+        let out = PathBuf::from("target/test_output/sanitize_non_finite.log");
+        let _ = fs::remove_file(&out).await;
+        let transport = SerialTransport::new(Some(out.clone()))
+            .await
+            .unwrap()
+            .with_sanitize_non_finite(true);
+
+        let mut packet = TelemetryPacket::new(3);
+        packet.sensor_readings.push(crate::SensorReading::new(
+            "temp-01".to_string(),
+            "Temperature".to_string(),
+            crate::SensorData::Temperature {
+                value: f32::NAN,
+                unit: "°C".to_string(),
+            },
+            1,
+        ));
+
+        transport.send(&packet).await.unwrap();
+        drop(transport);
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let content = fs::read_to_string(&out).await.unwrap();
+        let decoded = TelemetryPacket::from_json(content.lines().next().unwrap()).unwrap();
+        match &decoded.sensor_readings[0].data {
+            crate::SensorData::Temperature { value, .. } => assert_eq!(*value, 0.0),
+            other => panic!("unexpected sensor data: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_fails_after_writer_exits() {
+        // Pointing the transport at a directory makes the writer fail to open and exit
+        let dir = PathBuf::from("target/test_output/writer_exit_dir");
+        fs::create_dir_all(&dir).await.unwrap();
+        let transport = MqttTransport::new(Some(dir)).await.unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(1);
+        while transport.is_writer_alive() && std::time::Instant::now() < deadline {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        assert!(!transport.is_writer_alive());
+
+        let result = transport.send(&TelemetryPacket::new(1)).await;
+        assert!(matches!(result, Err(TransportError::Closed)));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_drop_flushes_queued_lines() {
+        let out = PathBuf::from("target/test_output/drop_flush.log");
+        let _ = fs::remove_file(&out).await;
+        let transport = SerialTransport::new(Some(out.clone())).await.unwrap();
+
+        for seq in 0..200 {
+            transport.send(&TelemetryPacket::new(seq)).await.unwrap();
+        }
+        drop(transport);
+
+        // No sleep: dropping must have waited for the writer to drain
+        let content = std::fs::read_to_string(&out).unwrap();
+        assert_eq!(content.lines().count(), 200);
+        let last = TelemetryPacket::from_json(content.lines().last().unwrap()).unwrap();
+        assert_eq!(last.sequence, 199);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_drop_on_worker_lets_writer_drain() {
+        let out = PathBuf::from("target/test_output/drop_flush_worker.log");
+        let _ = fs::remove_file(&out).await;
+
+        // Dropped on the only worker thread: the writer task can only make
+        // progress if the drop hands the worker off while it waits
+        tokio::spawn({
+            let out = out.clone();
+            async move {
+                let transport = SerialTransport::new(Some(out)).await.unwrap();
+                for seq in 0..200 {
+                    transport.send(&TelemetryPacket::new(seq)).await.unwrap();
+                }
+                drop(transport);
+            }
+        })
+        .await
+        .unwrap();
+
+        let content = std::fs::read_to_string(&out).unwrap();
+        assert_eq!(content.lines().count(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_close_drains_and_rejects_later_sends() {
+        let out = PathBuf::from("target/test_output/close_drain.log");
+        let _ = fs::remove_file(&out).await;
+        let mut transport = MqttTransport::new(Some(out.clone())).await.unwrap();
+
+        transport.send(&TelemetryPacket::new(1)).await.unwrap();
+        transport.close().await;
+
+        let content = fs::read_to_string(&out).await.unwrap();
+        assert!(content.contains("\"sequence\":1"));
+        let result = transport.send(&TelemetryPacket::new(2)).await;
+        assert!(matches!(result, Err(TransportError::Closed)));
+    }
+
+    #[tokio::test]
+    async fn test_rotating_file_rolls_over_and_keeps_max_files() {
+        let out = PathBuf::from("target/test_output/rotating/telemetry.log");
+        for index in 0..=3 {
+            let _ = fs::remove_file(rotated_path(&out, index)).await;
+        }
+        let _ = fs::remove_file(&out).await;
+
+        // Room for two lines per file
+        let line_len = serde_json::to_vec(&TelemetryPacket::new(0)).unwrap().len() as u64 + 1;
+        let mut transport = RotatingFileTransport::new(&out, line_len * 2 + 10, 2)
+            .await
+            .unwrap();
+        for seq in 0..10 {
+            transport.send(&TelemetryPacket::new(seq)).await.unwrap();
+        }
+        transport.close().await;
+
+        assert!(fs::metadata(rotated_path(&out, 3)).await.is_err());
+        let mut sequences = Vec::new();
+        for path in [rotated_path(&out, 2), rotated_path(&out, 1), out.clone()] {
+            let content = fs::read_to_string(&path).await.unwrap();
+            assert!(content.lines().count() <= 2, "{} too large", path.display());
+            for line in content.lines() {
+                sequences.push(TelemetryPacket::from_json(line).unwrap().sequence);
+            }
+        }
+        assert_eq!(sequences, vec![4, 5, 6, 7, 8, 9]);
+    }
+
+    #[tokio::test]
+    async fn test_udp_sends_datagram_and_rejects_oversized() {
+        let receiver = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let transport = UdpTransport::new(receiver.local_addr().unwrap())
+            .await
+            .unwrap();
+
+        transport.send(&TelemetryPacket::new(7)).await.unwrap();
+        let mut buf = vec![0u8; DEFAULT_UDP_MAX_PAYLOAD];
+        let n = receiver.recv(&mut buf).await.unwrap();
+        let received: TelemetryPacket = serde_json::from_slice(&buf[..n]).unwrap();
+        assert_eq!(received.sequence, 7);
+
+        let transport = transport.with_max_payload(64);
+        match transport.send(&TelemetryPacket::new(8)).await {
+            Err(TransportError::PayloadTooLarge { size, max }) => {
+                assert!(size > 64);
+                assert_eq!(max, 64);
+            }
+            other => panic!("expected PayloadTooLarge, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_udp_batch_skips_oversized_packets() {
+        let receiver = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let transport = UdpTransport::new(receiver.local_addr().unwrap())
+            .await
+            .unwrap()
+            .with_max_payload(512);
+
+        let mut oversized = TelemetryPacket::new(2);
+        oversized
+            .metadata
+            .insert("padding".to_string(), "x".repeat(1024));
+        let packets = vec![TelemetryPacket::new(1), oversized, TelemetryPacket::new(3)];
+        let encoded: Vec<Vec<u8>> = packets
+            .iter()
+            .map(|p| serde_json::to_vec(p).unwrap())
+            .collect();
+        transport
+            .send_batch_bytes(&packets, &encoded)
+            .await
+            .unwrap();
+        assert_eq!(transport.oversized_count(), 1);
+
+        let mut buf = vec![0u8; DEFAULT_UDP_MAX_PAYLOAD];
+        let mut sequences = Vec::new();
+        for _ in 0..2 {
+            let n = receiver.recv(&mut buf).await.unwrap();
+            let received: TelemetryPacket = serde_json::from_slice(&buf[..n]).unwrap();
+            sequences.push(received.sequence);
+        }
+        assert_eq!(sequences, vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_downsample_every_nth() {
+        let downsampled = DownsampleTransport::every_nth(MemoryTransport::new(), 5);
+        for i in 0..5 {
+            downsampled.send(&TelemetryPacket::new(i)).await.unwrap();
+        }
+        assert_eq!(downsampled.inner().len().await, 1);
+
+        let windowed =
+            DownsampleTransport::per_window(MemoryTransport::new(), Duration::from_secs(60));
+        for i in 0..5 {
+            windowed.send(&TelemetryPacket::new(i)).await.unwrap();
+        }
+        assert_eq!(windowed.inner().len().await, 1);
+    }
+
+    /// Transport that never finishes a send
+    struct StalledTransport;
+
+    #[async_trait]
+    impl Transport for StalledTransport {
+        async fn send(&self, _packet: &TelemetryPacket) -> Result<(), TransportError> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_slow_sink_does_not_block_fast_sink() {
+        let fast = MemoryTransport::new();
+        let fan_out = FanOutTransport::new(
+            vec![Arc::new(fast.clone()), Arc::new(StalledTransport)],
+            2,
+            QueueFullPolicy::Drop,
+        );
+
+        for i in 0..10 {
+            fan_out.send(&TelemetryPacket::new(i)).await.unwrap();
+            tokio::task::yield_now().await;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(fast.len().await, 10);
+        let dropped = fan_out.dropped_counts();
+        assert_eq!(dropped[0], 0);
+        assert!(dropped[1] >= 7, "stalled sink dropped {}", dropped[1]);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_drop_aborts_sink_tasks() {
+        let stalled = Arc::new(StalledTransport);
+        let fan_out = FanOutTransport::new(vec![stalled.clone()], 2, QueueFullPolicy::Drop);
+        fan_out.send(&TelemetryPacket::new(1)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(Arc::strong_count(&stalled), 2);
+
+        // The sink task is stuck in `send`; only aborting it releases the sink
+        drop(fan_out);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(Arc::strong_count(&stalled), 1);
+    }
+
     #[tokio::test]
     async fn test_serial_transport_send() {
         let transport =
@@ -195,10 +1247,12 @@ mod tests {
                 .unwrap();
         let packet = TelemetryPacket {
             sequence: 2,
+            schema_version: SCHEMA_VERSION,
             timestamp: chrono::Utc::now(),
             health: SystemHealth::new(),
             sensor_readings: vec![],
             diagnostics: Default::default(),
+            metadata: Default::default(),
         };
 
         transport.send(&packet).await.unwrap();
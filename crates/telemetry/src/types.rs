@@ -2,8 +2,9 @@
 //!
 //! All types implement Serde for JSON and binary serialization.
 
+use crate::units::{Dimension, Quantity, Unit};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 /// Timestamp type for telemetry events
 pub type Timestamp = chrono::DateTime<chrono::Utc>;
@@ -60,6 +61,9 @@ pub struct SystemHealth {
     /// Optional error message if status is not Healthy
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// Number of components expected to report, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_components: Option<u32>,
 }
 
 impl SystemHealth {
@@ -76,16 +80,34 @@ impl SystemHealth {
             memory_usage_bytes: 0,
             temperature_celsius: 25.0,
             error_message: None,
+            expected_components: None,
         }
     }
 
+    /// Declare how many components are expected to report
+    ///
+    /// With an expectation of zero, a report with no components is `Healthy`
+    /// (there is nothing to be wrong). With a non-zero expectation, or none
+    /// at all, a report with no components stays `Unknown`. A report with
+    /// some but fewer components than expected is at best `Degraded`.
+    pub fn with_expectation(mut self, expected_components: u32) -> Self {
+        self.expected_components = Some(expected_components);
+        self
+    }
+
     /// Calculate overall health status based on component counts
     pub fn recalculate_status(&mut self) {
+        let reported = self.healthy_components + self.degraded_components + self.failed_components;
+        let missing = self
+            .expected_components
+            .is_some_and(|expected| reported < expected);
         self.status = if self.failed_components > 0 {
             HealthStatus::Critical
-        } else if self.degraded_components > 0 {
+        } else if self.degraded_components > 0 || (missing && reported > 0) {
             HealthStatus::Degraded
-        } else if self.healthy_components > 0 {
+        } else if missing {
+            HealthStatus::Unknown
+        } else if self.healthy_components > 0 || self.expected_components == Some(0) {
             HealthStatus::Healthy
         } else {
             HealthStatus::Unknown
@@ -137,6 +159,320 @@ pub enum SensorData {
 }
 
 impl SensorData {
+    /// Build sensor data from a unit-checked quantity
+    ///
+    /// Temperatures are normalized to Celsius and pressures to hPa, matching
+    /// the documented units of those variants. Percentages become humidity
+    /// readings; any other dimension is carried as an analog value.
+    pub fn from_quantity(quantity: Quantity) -> Self {
+        let normalized = |unit: Unit| {
+            quantity
+                .convert_to(unit)
+                .expect("unit shares the quantity's dimension")
+        };
+        match quantity.unit.dimension() {
+            Dimension::Temperature => {
+                let q = normalized(Unit::Celsius);
+                Self::Temperature {
+                    value: q.value as f32,
+                    unit: q.unit.symbol().to_string(),
+                }
+            }
+            Dimension::Pressure => {
+                let q = normalized(Unit::Hectopascal);
+                Self::Pressure {
+                    value: q.value as f32,
+                    unit: q.unit.symbol().to_string(),
+                }
+            }
+            Dimension::Ratio => Self::Humidity {
+                value: quantity.value as f32,
+                unit: quantity.unit.symbol().to_string(),
+            },
+            _ => Self::Analog {
+                value: quantity.value as f32,
+                unit: quantity.unit.symbol().to_string(),
+            },
+        }
+    }
+
+    /// Single scalar value of this reading, if it has one
+    ///
+    /// Temperature, Pressure, Humidity and Analog carry one value; GPS,
+    /// three-axis and digital readings return `None`.
+    pub fn scalar_value(&self) -> Option<f32> {
+        match self {
+            Self::Temperature { value, .. }
+            | Self::Pressure { value, .. }
+            | Self::Humidity { value, .. }
+            | Self::Analog { value, .. } => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Replace NaN/infinite values with 0.0, returning how many were replaced
+    pub fn sanitize_non_finite(&mut self) -> usize {
+        let mut replaced = 0;
+        match self {
+            Self::Temperature { value, .. }
+            | Self::Pressure { value, .. }
+            | Self::Humidity { value, .. }
+            | Self::Analog { value, .. } => replaced += zero_non_finite(value),
+            Self::Gps {
+                latitude,
+                longitude,
+                altitude,
+                accuracy,
+            } => {
+                for v in [latitude, longitude] {
+                    if !v.is_finite() {
+                        *v = 0.0;
+                        replaced += 1;
+                    }
+                }
+                replaced += zero_non_finite(altitude) + zero_non_finite(accuracy);
+            }
+            Self::Accelerometer { x, y, z, .. } | Self::Gyroscope { x, y, z, .. } => {
+                replaced += zero_non_finite(x) + zero_non_finite(y) + zero_non_finite(z);
+            }
+            Self::Digital { .. } => {}
+        }
+        replaced
+    }
+
+    /// Compare with `other` allowing each numeric field to differ by `epsilon`
+    ///
+    /// Variants, units and labels must match exactly; different variants are
+    /// never equal.
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        let close = |a: f64, b: f64| (a - b).abs() <= epsilon;
+        let close3 =
+            |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(&a, b)| close(a as f64, b as f64));
+        match (self, other) {
+            (
+                Self::Temperature { value: a, unit: ua },
+                Self::Temperature { value: b, unit: ub },
+            )
+            | (Self::Pressure { value: a, unit: ua }, Self::Pressure { value: b, unit: ub })
+            | (Self::Humidity { value: a, unit: ua }, Self::Humidity { value: b, unit: ub })
+            | (Self::Analog { value: a, unit: ua }, Self::Analog { value: b, unit: ub }) => {
+                ua == ub && close(*a as f64, *b as f64)
+            }
+            (
+                Self::Gps {
+                    latitude: lat_a,
+                    longitude: lon_a,
+                    altitude: alt_a,
+                    accuracy: acc_a,
+                },
+                Self::Gps {
+                    latitude: lat_b,
+                    longitude: lon_b,
+                    altitude: alt_b,
+                    accuracy: acc_b,
+                },
+            ) => {
+                close(*lat_a, *lat_b)
+                    && close(*lon_a, *lon_b)
+                    && close(*alt_a as f64, *alt_b as f64)
+                    && close(*acc_a as f64, *acc_b as f64)
+            }
+            (
+                Self::Accelerometer {
+                    x: xa,
+                    y: ya,
+                    z: za,
+                    unit: ua,
+                },
+                Self::Accelerometer {
+                    x: xb,
+                    y: yb,
+                    z: zb,
+                    unit: ub,
+                },
+            )
+            | (
+                Self::Gyroscope {
+                    x: xa,
+                    y: ya,
+                    z: za,
+                    unit: ua,
+                },
+                Self::Gyroscope {
+                    x: xb,
+                    y: yb,
+                    z: zb,
+                    unit: ub,
+                },
+            ) => ua == ub && close3([*xa, *ya, *za], [*xb, *yb, *zb]),
+            (
+                Self::Digital {
+                    state: sa,
+                    label: la,
+                },
+                Self::Digital {
+                    state: sb,
+                    label: lb,
+                },
+            ) => sa == sb && la == lb,
+            _ => false,
+        }
+    }
+
+    /// Interpolate each numeric field towards `other` by `fraction` (0.0..=1.0)
+    fn lerp(&self, other: &Self, fraction: f64) -> Option<Self> {
+        let f32_lerp = |a: f32, b: f32| lerp(a as f64, b as f64, fraction) as f32;
+        let data = match (self, other) {
+            (Self::Temperature { value: a, unit }, Self::Temperature { value: b, unit: ub })
+                if unit == ub =>
+            {
+                Self::Temperature {
+                    value: f32_lerp(*a, *b),
+                    unit: unit.clone(),
+                }
+            }
+            (Self::Pressure { value: a, unit }, Self::Pressure { value: b, unit: ub })
+                if unit == ub =>
+            {
+                Self::Pressure {
+                    value: f32_lerp(*a, *b),
+                    unit: unit.clone(),
+                }
+            }
+            (Self::Humidity { value: a, unit }, Self::Humidity { value: b, unit: ub })
+                if unit == ub =>
+            {
+                Self::Humidity {
+                    value: f32_lerp(*a, *b),
+                    unit: unit.clone(),
+                }
+            }
+            (Self::Analog { value: a, unit }, Self::Analog { value: b, unit: ub })
+                if unit == ub =>
+            {
+                Self::Analog {
+                    value: f32_lerp(*a, *b),
+                    unit: unit.clone(),
+                }
+            }
+            (
+                Self::Gps {
+                    latitude,
+                    longitude,
+                    altitude,
+                    accuracy,
+                },
+                Self::Gps {
+                    latitude: lat_b,
+                    longitude: lon_b,
+                    altitude: alt_b,
+                    accuracy: acc_b,
+                },
+            ) => Self::Gps {
+                latitude: lerp(*latitude, *lat_b, fraction),
+                longitude: lerp(*longitude, *lon_b, fraction),
+                altitude: f32_lerp(*altitude, *alt_b),
+                accuracy: f32_lerp(*accuracy, *acc_b),
+            },
+            (
+                Self::Accelerometer { x, y, z, unit },
+                Self::Accelerometer {
+                    x: xb,
+                    y: yb,
+                    z: zb,
+                    unit: ub,
+                },
+            ) if unit == ub => Self::Accelerometer {
+                x: f32_lerp(*x, *xb),
+                y: f32_lerp(*y, *yb),
+                z: f32_lerp(*z, *zb),
+                unit: unit.clone(),
+            },
+            (
+                Self::Gyroscope { x, y, z, unit },
+                Self::Gyroscope {
+                    x: xb,
+                    y: yb,
+                    z: zb,
+                    unit: ub,
+                },
+            ) if unit == ub => Self::Gyroscope {
+                x: f32_lerp(*x, *xb),
+                y: f32_lerp(*y, *yb),
+                z: f32_lerp(*z, *zb),
+                unit: unit.clone(),
+            },
+            _ => return None,
+        };
+        Some(data)
+    }
+
+    /// Short snake_case name of the variant (e.g. "temperature", "gps")
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Temperature { .. } => "temperature",
+            Self::Pressure { .. } => "pressure",
+            Self::Humidity { .. } => "humidity",
+            Self::Gps { .. } => "gps",
+            Self::Accelerometer { .. } => "accelerometer",
+            Self::Gyroscope { .. } => "gyroscope",
+            Self::Analog { .. } => "analog",
+            Self::Digital { .. } => "digital",
+        }
+    }
+
+    /// Round every numeric field to `decimals` decimal places
+    ///
+    /// Fewer significant digits serialize shorter and compress better.
+    /// `Digital` data is returned unchanged.
+    pub fn round_to(&self, decimals: u8) -> SensorData {
+        let factor = 10f64.powi(decimals as i32);
+        let round = |v: f64| (v * factor).round() / factor;
+        let round32 = |v: f32| round(v as f64) as f32;
+        match self {
+            Self::Temperature { value, unit } => Self::Temperature {
+                value: round32(*value),
+                unit: unit.clone(),
+            },
+            Self::Pressure { value, unit } => Self::Pressure {
+                value: round32(*value),
+                unit: unit.clone(),
+            },
+            Self::Humidity { value, unit } => Self::Humidity {
+                value: round32(*value),
+                unit: unit.clone(),
+            },
+            Self::Gps {
+                latitude,
+                longitude,
+                altitude,
+                accuracy,
+            } => Self::Gps {
+                latitude: round(*latitude),
+                longitude: round(*longitude),
+                altitude: round32(*altitude),
+                accuracy: round32(*accuracy),
+            },
+            Self::Accelerometer { x, y, z, unit } => Self::Accelerometer {
+                x: round32(*x),
+                y: round32(*y),
+                z: round32(*z),
+                unit: unit.clone(),
+            },
+            Self::Gyroscope { x, y, z, unit } => Self::Gyroscope {
+                x: round32(*x),
+                y: round32(*y),
+                z: round32(*z),
+                unit: unit.clone(),
+            },
+            Self::Analog { value, unit } => Self::Analog {
+                value: round32(*value),
+                unit: unit.clone(),
+            },
+            Self::Digital { .. } => self.clone(),
+        }
+    }
+
     /// Get a human-readable description of the sensor reading
     pub fn description(&self) -> String {
         match self {
@@ -166,6 +502,69 @@ impl SensorData {
     }
 }
 
+/// Linear interpolation between `a` and `b`
+fn lerp(a: f64, b: f64, fraction: f64) -> f64 {
+    a + (b - a) * fraction
+}
+
+/// Zero out a non-finite value, returning 1 if it was replaced
+fn zero_non_finite(value: &mut f32) -> usize {
+    if value.is_finite() {
+        0
+    } else {
+        *value = 0.0;
+        1
+    }
+}
+
+/// Per-variant decimal precision applied to sensor data before sending
+///
+/// `overrides` is keyed by `SensorData::kind` (e.g. `gps: 6`), so GPS can
+/// keep more decimals than temperature. Variants without an override use
+/// `default_decimals`, or are left untouched when that is `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrecisionConfig {
+    /// Decimal places for variants without an override
+    #[serde(default)]
+    pub default_decimals: Option<u8>,
+    /// Decimal places per variant kind
+    #[serde(default)]
+    pub overrides: HashMap<String, u8>,
+}
+
+impl PrecisionConfig {
+    /// Round every variant to `decimals` unless overridden
+    pub fn new(default_decimals: u8) -> Self {
+        Self {
+            default_decimals: Some(default_decimals),
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Use `decimals` for the variant named `kind`
+    pub fn with_kind(mut self, kind: impl Into<String>, decimals: u8) -> Self {
+        self.overrides.insert(kind.into(), decimals);
+        self
+    }
+
+    /// Decimal places to keep for `data`, if it should be rounded
+    pub fn decimals_for(&self, data: &SensorData) -> Option<u8> {
+        self.overrides
+            .get(data.kind())
+            .copied()
+            .or(self.default_decimals)
+    }
+
+    /// Round the readings of `packet` in place
+    pub fn apply(&self, packet: &mut TelemetryPacket) {
+        for reading in &mut packet.sensor_readings {
+            if let Some(decimals) = self.decimals_for(&reading.data) {
+                reading.data = reading.data.round_to(decimals);
+            }
+        }
+    }
+}
+
 /// A single sensor reading with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SensorReading {
@@ -200,6 +599,52 @@ impl SensorReading {
             confidence: 95.0,
         }
     }
+
+    /// Linearly interpolate two readings of the same kind to time `t`
+    ///
+    /// The result keeps `a`'s identity and sequence number with the
+    /// timestamp set to `t`. Returns `None` if the variants or units differ,
+    /// the data is not numeric (`Digital`), or `t` lies outside the span of
+    /// the two timestamps.
+    pub fn interpolate_at(a: &SensorReading, b: &SensorReading, t: Timestamp) -> Option<Self> {
+        if a.timestamp > b.timestamp {
+            return Self::interpolate_at(b, a, t);
+        }
+        if t < a.timestamp || t > b.timestamp {
+            return None;
+        }
+
+        let span = (b.timestamp - a.timestamp).num_nanoseconds()?;
+        let fraction = if span == 0 {
+            0.0
+        } else {
+            (t - a.timestamp).num_nanoseconds()? as f64 / span as f64
+        };
+
+        Some(Self {
+            component_id: a.component_id.clone(),
+            component_name: a.component_name.clone(),
+            timestamp: t,
+            data: a.data.lerp(&b.data, fraction)?,
+            sequence: a.sequence,
+            confidence: lerp(a.confidence as f64, b.confidence as f64, fraction) as f32,
+        })
+    }
+
+    /// Create a new sensor reading from a unit-checked quantity
+    pub fn from_quantity(
+        component_id: ComponentId,
+        component_name: String,
+        quantity: Quantity,
+        sequence: u64,
+    ) -> Self {
+        Self::new(
+            component_id,
+            component_name,
+            SensorData::from_quantity(quantity),
+            sequence,
+        )
+    }
 }
 
 /// Diagnostic event types
@@ -263,24 +708,216 @@ impl DiagnosticEntry {
         }
     }
 
+    /// Create an entry for a catalogued code, taking its level and message from the catalog
+    ///
+    /// Returns `None` if `code` is not registered in `catalog`.
+    pub fn from_code(
+        catalog: &DiagnosticCatalog,
+        code: &str,
+        component_id: ComponentId,
+    ) -> Option<Self> {
+        let definition = catalog.get(code)?;
+        Some(
+            Self::new(
+                definition.level,
+                component_id,
+                definition.description.clone(),
+            )
+            .with_code(code),
+        )
+    }
+
     /// Add a diagnostic code
     pub fn with_code(mut self, code: impl Into<String>) -> Self {
         self.code = Some(code.into());
         self
     }
 
-    /// Add context data
-    pub fn with_context(mut self, key: String, value: String) -> Self {
-        if self.context.is_none() {
-            self.context = Some(HashMap::new());
+    /// Add context data, within the default `ContextLimits`
+    pub fn with_context(self, key: String, value: String) -> Self {
+        self.with_context_limited(key, value, &ContextLimits::default())
+    }
+
+    /// Add context data, dropping or truncating it to stay within `limits`
+    ///
+    /// A new key is dropped once `max_keys` is reached, and the value is
+    /// truncated (at a character boundary) to the remaining value budget.
+    pub fn with_context_limited(
+        mut self,
+        key: String,
+        mut value: String,
+        limits: &ContextLimits,
+    ) -> Self {
+        let ctx = self.context.get_or_insert_with(HashMap::new);
+        if !ctx.contains_key(&key) && ctx.len() >= limits.max_keys {
+            tracing::warn!(
+                "Dropping diagnostic context key {:?} from {}: limit of {} keys reached",
+                key,
+                self.component_id,
+                limits.max_keys
+            );
+            return self;
         }
-        if let Some(ref mut ctx) = self.context {
-            ctx.insert(key, value);
+
+        let used: usize = ctx
+            .iter()
+            .filter(|(k, _)| **k != key)
+            .map(|(_, v)| v.len())
+            .sum();
+        let budget = limits.max_value_bytes.saturating_sub(used);
+        if value.len() > budget {
+            tracing::warn!(
+                "Truncating diagnostic context {:?} from {} to {} bytes",
+                key,
+                self.component_id,
+                budget
+            );
+            truncate_at_char_boundary(&mut value, budget);
         }
+        ctx.insert(key, value);
         self
     }
 }
 
+/// Bounds on the context attached to a diagnostic entry
+///
+/// Protects packet size against components attaching unbounded context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextLimits {
+    /// Maximum number of context keys
+    pub max_keys: usize,
+    /// Maximum total bytes across all context values
+    pub max_value_bytes: usize,
+}
+
+impl ContextLimits {
+    /// Trim `entry`'s context to these limits, returning whether anything was cut
+    ///
+    /// Keys are kept in sorted order until `max_keys`, and values are
+    /// truncated in that order once the byte budget runs out.
+    pub fn enforce(&self, entry: &mut DiagnosticEntry) -> bool {
+        let Some(ref mut ctx) = entry.context else {
+            return false;
+        };
+        let mut keys: Vec<String> = ctx.keys().cloned().collect();
+        keys.sort();
+
+        let mut trimmed = false;
+        let mut budget = self.max_value_bytes;
+        for (index, key) in keys.iter().enumerate() {
+            if index >= self.max_keys {
+                ctx.remove(key);
+                trimmed = true;
+                continue;
+            }
+            if let Some(value) = ctx.get_mut(key) {
+                if value.len() > budget {
+                    truncate_at_char_boundary(value, budget);
+                    trimmed = true;
+                }
+                budget -= value.len();
+            }
+        }
+
+        if trimmed {
+            tracing::warn!(
+                "Trimmed oversized diagnostic context from {} to {} keys / {} bytes",
+                entry.component_id,
+                self.max_keys,
+                self.max_value_bytes
+            );
+        }
+        trimmed
+    }
+}
+
+impl Default for ContextLimits {
+    fn default() -> Self {
+        Self {
+            max_keys: 16,
+            max_value_bytes: 4096,
+        }
+    }
+}
+
+/// Truncate `value` to at most `max_bytes`, backing off to a character boundary
+fn truncate_at_char_boundary(value: &mut String, max_bytes: usize) {
+    if value.len() <= max_bytes {
+        return;
+    }
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    value.truncate(end);
+}
+
+/// Meaning and default severity of a diagnostic code
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticDefinition {
+    /// Level used for entries created from this code
+    pub level: DiagnosticLevel,
+    /// Human-readable description, used as the entry message
+    pub description: String,
+}
+
+/// Registry mapping diagnostic codes to their severity and description
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiagnosticCatalog {
+    codes: HashMap<String, DiagnosticDefinition>,
+}
+
+impl DiagnosticCatalog {
+    /// Create an empty catalog
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or replace) a code
+    pub fn register(
+        &mut self,
+        code: impl Into<String>,
+        level: DiagnosticLevel,
+        description: impl Into<String>,
+    ) -> &mut Self {
+        self.codes.insert(
+            code.into(),
+            DiagnosticDefinition {
+                level,
+                description: description.into(),
+            },
+        );
+        self
+    }
+
+    /// Look up a code
+    pub fn get(&self, code: &str) -> Option<&DiagnosticDefinition> {
+        self.codes.get(code)
+    }
+
+    /// Check whether a code is registered
+    pub fn contains(&self, code: &str) -> bool {
+        self.codes.contains_key(code)
+    }
+
+    /// Get the number of registered codes
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Check whether the catalog has no codes
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+}
+
+/// Number of recent entries a `DiagnosticsReport` keeps by default
+pub const DEFAULT_DIAGNOSTICS_CAPACITY: usize = 100;
+
+fn default_diagnostics_capacity() -> usize {
+    DEFAULT_DIAGNOSTICS_CAPACITY
+}
+
 /// Complete diagnostics report
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiagnosticsReport {
@@ -290,31 +927,47 @@ pub struct DiagnosticsReport {
     pub total_entries: u32,
     /// Entries by level
     pub entries_by_level: HashMap<String, u32>,
-    /// Recent diagnostic entries
-    pub recent_entries: Vec<DiagnosticEntry>,
+    /// Recent diagnostic entries, oldest first
+    pub recent_entries: VecDeque<DiagnosticEntry>,
+    /// Maximum number of recent entries kept (not sent on the wire)
+    #[serde(skip, default = "default_diagnostics_capacity")]
+    capacity: usize,
 }
 
 impl DiagnosticsReport {
     /// Create a new empty diagnostics report
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_DIAGNOSTICS_CAPACITY)
+    }
+
+    /// Create an empty report keeping at most `capacity` recent entries
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
             timestamp: chrono::Utc::now(),
             total_entries: 0,
             entries_by_level: HashMap::new(),
-            recent_entries: Vec::new(),
+            recent_entries: VecDeque::with_capacity(capacity),
+            capacity,
         }
     }
 
+    /// Maximum number of recent entries kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     /// Add a diagnostic entry and update statistics
+    ///
+    /// Once `capacity` entries are held the oldest is evicted; the totals
+    /// keep counting every entry ever added.
     pub fn add_entry(&mut self, entry: DiagnosticEntry) {
         let level_str = format!("{:?}", entry.level);
         *self.entries_by_level.entry(level_str).or_insert(0) += 1;
         self.total_entries += 1;
-        self.recent_entries.push(entry);
+        self.recent_entries.push_back(entry);
 
-        // Keep only last 100 entries
-        if self.recent_entries.len() > 100 {
-            self.recent_entries.remove(0);
+        while self.recent_entries.len() > self.capacity {
+            self.recent_entries.pop_front();
         }
     }
 }
@@ -325,11 +978,24 @@ impl Default for DiagnosticsReport {
     }
 }
 
+/// Wire-format version written by this build
+///
+/// Version 0 is the original format, which had no `schema_version` field.
+pub const SCHEMA_VERSION: u16 = 1;
+
+/// Schema version a packet cannot be migrated from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("cannot migrate schema version {0} to {}", SCHEMA_VERSION)]
+pub struct UnsupportedSchemaVersion(pub u16);
+
 /// Complete telemetry packet combining health, sensor readings, and diagnostics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TelemetryPacket {
     /// Packet sequence number
     pub sequence: u64,
+    /// Wire-format version (0 when absent, i.e. written before versioning)
+    #[serde(default)]
+    pub schema_version: u16,
     /// Timestamp of packet generation
     pub timestamp: Timestamp,
     /// System health snapshot
@@ -338,6 +1004,9 @@ pub struct TelemetryPacket {
     pub sensor_readings: Vec<SensorReading>,
     /// Diagnostics snapshot
     pub diagnostics: DiagnosticsReport,
+    /// Free-form key/value annotations (source, firmware version, ...)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
 impl TelemetryPacket {
@@ -345,13 +1014,85 @@ impl TelemetryPacket {
     pub fn new(sequence: u64) -> Self {
         Self {
             sequence,
+            schema_version: SCHEMA_VERSION,
             timestamp: chrono::Utc::now(),
             health: SystemHealth::new(),
             sensor_readings: Vec::new(),
             diagnostics: DiagnosticsReport::new(),
+            metadata: HashMap::new(),
         }
     }
 
+    /// Start building a packet
+    pub fn builder() -> TelemetryPacketBuilder {
+        TelemetryPacketBuilder::new()
+    }
+
+    /// Create a minimal "alive but idle" packet for `device_id`
+    ///
+    /// Carries only a default health snapshot plus metadata marking it as a
+    /// heartbeat; readings and diagnostics are empty.
+    pub fn heartbeat(device_id: &str, sequence: u64) -> Self {
+        let mut packet = Self::new(sequence);
+        packet.metadata.insert(
+            crate::heartbeat::HEARTBEAT_KEY.to_string(),
+            "true".to_string(),
+        );
+        packet.metadata.insert(
+            crate::heartbeat::DEVICE_ID_KEY.to_string(),
+            device_id.to_string(),
+        );
+        packet
+    }
+
+    /// Check whether this packet is a heartbeat
+    pub fn is_heartbeat(&self) -> bool {
+        self.metadata
+            .get(crate::heartbeat::HEARTBEAT_KEY)
+            .is_some_and(|v| v == "true")
+    }
+
+    /// Check whether the packet was written by a newer build than this one
+    ///
+    /// Such packets may carry fields this build silently drops, so receivers
+    /// should reject them rather than act on a partial view.
+    pub fn is_newer_schema(&self) -> bool {
+        self.schema_version > SCHEMA_VERSION
+    }
+
+    /// Upgrade a packet written in an older wire format to `SCHEMA_VERSION`
+    ///
+    /// Packets already at the current version are left untouched. A packet
+    /// from a newer build, or from an older version this build has no
+    /// migration for, is rejected and keeps the version it reached.
+    pub fn migrate_to_current(&mut self) -> Result<(), UnsupportedSchemaVersion> {
+        if self.is_newer_schema() {
+            return Err(UnsupportedSchemaVersion(self.schema_version));
+        }
+        while self.schema_version < SCHEMA_VERSION {
+            match self.schema_version {
+                // v0 differs from v1 only by the missing version field
+                0 => self.schema_version = 1,
+                version => return Err(UnsupportedSchemaVersion(version)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace NaN/infinite floats with 0.0, returning how many were replaced
+    ///
+    /// serde_json writes non-finite floats as `null`, which receivers cannot
+    /// deserialize back into the numeric fields, so the packet would be lost.
+    pub fn sanitize_non_finite(&mut self) -> usize {
+        let mut replaced = zero_non_finite(&mut self.health.cpu_usage_percent)
+            + zero_non_finite(&mut self.health.temperature_celsius);
+        for reading in &mut self.sensor_readings {
+            replaced += zero_non_finite(&mut reading.confidence);
+            replaced += reading.data.sanitize_non_finite();
+        }
+        replaced
+    }
+
     /// Serialize to JSON string
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
@@ -372,16 +1113,256 @@ impl TelemetryPacket {
         serde_json::from_slice(bytes)
     }
 
+    /// Serialize to CBOR bytes
+    ///
+    /// CBOR is self-describing, so optional and defaulted fields round-trip
+    /// exactly as they do through JSON, at a fraction of the size.
+    #[cfg(feature = "binary_serde")]
+    pub fn to_cbor(&self) -> Result<Vec<u8>, ciborium::ser::Error<std::io::Error>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Deserialize from CBOR bytes
+    #[cfg(feature = "binary_serde")]
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ciborium::de::Error<std::io::Error>> {
+        ciborium::from_reader(bytes)
+    }
+
     /// Get total size in bytes (approximate)
     pub fn size_bytes(&self) -> usize {
         self.to_json_bytes().unwrap_or_default().len()
     }
 }
 
+/// Builder assembling a `TelemetryPacket` piece by piece
+///
+/// Diagnostics are added through `DiagnosticsReport::add_entry`, so the
+/// report's totals and per-level counts are always consistent.
+///
+/// # Example
+/// ```ignore
+/// let packet = TelemetryPacket::builder()
+///     .sequence(7)
+///     .add_reading(reading)
+///     .add_diagnostic(entry)
+///     .metadata("source", "simulator")
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TelemetryPacketBuilder {
+    sequence: u64,
+    health: SystemHealth,
+    sensor_readings: Vec<SensorReading>,
+    diagnostics: DiagnosticsReport,
+    metadata: HashMap<String, String>,
+}
+
+impl TelemetryPacketBuilder {
+    /// Create a builder for an empty packet with sequence 0
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the packet sequence number
+    pub fn sequence(mut self, sequence: u64) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Set the health snapshot
+    pub fn health(mut self, health: SystemHealth) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// Append a sensor reading
+    pub fn add_reading(mut self, reading: SensorReading) -> Self {
+        self.sensor_readings.push(reading);
+        self
+    }
+
+    /// Append a diagnostic entry, updating the report totals
+    pub fn add_diagnostic(mut self, entry: DiagnosticEntry) -> Self {
+        self.diagnostics.add_entry(entry);
+        self
+    }
+
+    /// Attach a metadata key/value pair
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Assemble the packet, timestamped now
+    pub fn build(self) -> TelemetryPacket {
+        TelemetryPacket {
+            sequence: self.sequence,
+            schema_version: SCHEMA_VERSION,
+            timestamp: chrono::Utc::now(),
+            health: self.health,
+            sensor_readings: self.sensor_readings,
+            diagnostics: self.diagnostics,
+            metadata: self.metadata,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn temperature(value: f32) -> SensorData {
+        SensorData::Temperature {
+            value,
+            unit: "°C".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_approx_eq_temperature_within_epsilon() {
+        assert!(temperature(21.50).approx_eq(&temperature(21.52), 0.05));
+    }
+
+    #[test]
+    fn test_approx_eq_temperature_beyond_epsilon() {
+        assert!(!temperature(21.5).approx_eq(&temperature(21.7), 0.05));
+    }
+
+    fn reading_at(data: SensorData, timestamp: Timestamp) -> SensorReading {
+        let mut reading =
+            SensorReading::new("temp-01".to_string(), "Temperature".to_string(), data, 1);
+        reading.timestamp = timestamp;
+        reading
+    }
+
+    #[test]
+    fn test_interpolate_temperature_midpoint_and_endpoints() {
+        let start = chrono::Utc::now();
+        let end = start + chrono::Duration::seconds(10);
+        let a = reading_at(temperature(20.0), start);
+        let b = reading_at(temperature(30.0), end);
+
+        let mid =
+            SensorReading::interpolate_at(&a, &b, start + chrono::Duration::seconds(5)).unwrap();
+        assert!(mid.data.approx_eq(&temperature(25.0), 1e-4));
+
+        let at_start = SensorReading::interpolate_at(&a, &b, start).unwrap();
+        assert!(at_start.data.approx_eq(&temperature(20.0), 1e-4));
+        let at_end = SensorReading::interpolate_at(&a, &b, end).unwrap();
+        assert!(at_end.data.approx_eq(&temperature(30.0), 1e-4));
+
+        let outside = end + chrono::Duration::seconds(1);
+        assert!(SensorReading::interpolate_at(&a, &b, outside).is_none());
+    }
+
+    #[test]
+    fn test_interpolate_mismatched_variants() {
+        let start = chrono::Utc::now();
+        let end = start + chrono::Duration::seconds(10);
+        let a = reading_at(temperature(20.0), start);
+        let b = reading_at(
+            SensorData::Pressure {
+                value: 1013.0,
+                unit: "hPa".to_string(),
+            },
+            end,
+        );
+        let mid = start + chrono::Duration::seconds(5);
+        assert!(SensorReading::interpolate_at(&a, &b, mid).is_none());
+    }
+
+    #[test]
+    fn test_round_to_two_decimals() {
+        let rounded = temperature(21.5678).round_to(2);
+        assert!(rounded.approx_eq(&temperature(21.57), 1e-6));
+        let json = serde_json::to_string(&rounded).unwrap();
+        assert!(json.contains("21.57"), "{}", json);
+    }
+
+    #[test]
+    fn test_precision_config_per_variant() {
+        let config = PrecisionConfig::new(1).with_kind("gps", 6);
+        let mut packet = TelemetryPacket::new(1);
+        packet.sensor_readings.push(SensorReading::new(
+            "gps-01".to_string(),
+            "GPS".to_string(),
+            SensorData::Gps {
+                latitude: 37.774_912_3,
+                longitude: -122.419_415_5,
+                altitude: 12.345,
+                accuracy: 1.25,
+            },
+            1,
+        ));
+        packet.sensor_readings.push(SensorReading::new(
+            "temp-01".to_string(),
+            "Temperature".to_string(),
+            temperature(21.5678),
+            1,
+        ));
+
+        config.apply(&mut packet);
+        match &packet.sensor_readings[0].data {
+            SensorData::Gps {
+                latitude,
+                longitude,
+                altitude,
+                ..
+            } => {
+                assert_eq!(*latitude, 37.774_912);
+                assert_eq!(*longitude, -122.419_416);
+                assert!((*altitude - 12.345).abs() < 1e-4);
+            }
+            other => panic!("unexpected sensor data: {:?}", other),
+        }
+        assert!(packet.sensor_readings[1]
+            .data
+            .approx_eq(&temperature(21.6), 1e-6));
+    }
+
+    #[test]
+    fn test_oversized_context_is_truncated() {
+        let limits = ContextLimits {
+            max_keys: 2,
+            max_value_bytes: 10,
+        };
+        let entry = DiagnosticEntry::new(DiagnosticLevel::Warning, "comp-1".to_string(), "msg")
+            .with_context_limited("a".to_string(), "123456".to_string(), &limits)
+            .with_context_limited("b".to_string(), "x".repeat(1_000_000), &limits)
+            .with_context_limited("c".to_string(), "dropped".to_string(), &limits);
+
+        let ctx = entry.context.as_ref().unwrap();
+        assert_eq!(ctx.len(), 2);
+        assert_eq!(ctx["a"], "123456");
+        assert_eq!(ctx["b"], "xxxx");
+        assert!(!ctx.contains_key("c"));
+
+        // Entries built without the limits are trimmed when enforced
+        let mut raw = DiagnosticEntry::new(DiagnosticLevel::Info, "comp-2".to_string(), "msg");
+        raw.context = Some((0..5).map(|i| (format!("k{}", i), "é".repeat(4))).collect());
+        assert!(limits.enforce(&mut raw));
+        let ctx = raw.context.unwrap();
+        assert_eq!(ctx.len(), 2);
+        assert_eq!(ctx["k0"], "é".repeat(4));
+        assert_eq!(ctx["k1"], "é");
+        assert!(!limits.enforce(&mut DiagnosticEntry::new(
+            DiagnosticLevel::Info,
+            "comp-3".to_string(),
+            "msg"
+        )));
+    }
+
+    #[test]
+    fn test_approx_eq_different_variants() {
+        let humidity = SensorData::Humidity {
+            value: 21.5,
+            unit: "°C".to_string(),
+        };
+        assert!(!temperature(21.5).approx_eq(&humidity, 1.0));
+    }
+
     #[test]
     fn test_system_health_serialization() {
         let mut health = SystemHealth::new();
@@ -399,6 +1380,34 @@ mod tests {
         assert_eq!(deserialized.status, HealthStatus::Degraded);
     }
 
+    #[test]
+    fn test_health_with_zero_expected_components() {
+        let mut health = SystemHealth::new().with_expectation(0);
+        health.recalculate_status();
+        assert_eq!(health.status, HealthStatus::Healthy);
+
+        // Without an expectation the legacy behaviour is kept
+        let mut health = SystemHealth::new();
+        health.recalculate_status();
+        assert_eq!(health.status, HealthStatus::Unknown);
+    }
+
+    #[test]
+    fn test_health_with_missing_expected_components() {
+        let mut health = SystemHealth::new().with_expectation(3);
+        health.recalculate_status();
+        assert_eq!(health.status, HealthStatus::Unknown);
+
+        // Only some components reported: not all of the system is known good
+        health.healthy_components = 1;
+        health.recalculate_status();
+        assert_eq!(health.status, HealthStatus::Degraded);
+
+        health.healthy_components = 3;
+        health.recalculate_status();
+        assert_eq!(health.status, HealthStatus::Healthy);
+    }
+
     #[test]
     fn test_sensor_data_variants() {
         let temp = SensorData::Temperature {
@@ -433,6 +1442,24 @@ mod tests {
         assert_eq!(deserialized.component_id, "sensor-001");
     }
 
+    #[test]
+    fn test_sensor_reading_from_quantity() {
+        let reading = SensorReading::from_quantity(
+            "temp-001".to_string(),
+            "Temperature Sensor".to_string(),
+            Quantity::new(212.0, Unit::Fahrenheit),
+            1,
+        );
+
+        match reading.data {
+            SensorData::Temperature { value, unit } => {
+                assert!((value - 100.0).abs() < 1e-4);
+                assert_eq!(unit, "°C");
+            }
+            other => panic!("unexpected sensor data: {:?}", other),
+        }
+    }
+
     #[test]
     fn test_diagnostic_entry_builder() {
         let entry = DiagnosticEntry::new(
@@ -466,6 +1493,24 @@ mod tests {
         assert_eq!(report.recent_entries.len(), 2);
     }
 
+    #[test]
+    fn test_diagnostics_report_evicts_beyond_capacity() {
+        let mut report = DiagnosticsReport::with_capacity(50);
+        for i in 0..500 {
+            report.add_entry(DiagnosticEntry::new(
+                DiagnosticLevel::Info,
+                "sys".to_string(),
+                format!("event {}", i),
+            ));
+        }
+
+        assert_eq!(report.recent_entries.len(), 50);
+        assert_eq!(report.total_entries, 500);
+        assert_eq!(report.entries_by_level["Info"], 500);
+        assert_eq!(report.recent_entries.front().unwrap().message, "event 450");
+        assert_eq!(report.recent_entries.back().unwrap().message, "event 499");
+    }
+
     #[test]
     fn test_telemetry_packet_roundtrip() {
         let mut packet = TelemetryPacket::new(1);
@@ -490,10 +1535,154 @@ mod tests {
         assert_eq!(restored.sensor_readings.len(), 1);
     }
 
+    #[cfg(feature = "binary_serde")]
+    fn packet_with_readings(count: u64) -> TelemetryPacket {
+        let mut builder = TelemetryPacket::builder()
+            .sequence(7)
+            .metadata("source", "cbor-test");
+        for i in 0..count {
+            builder = builder.add_reading(SensorReading::new(
+                format!("accel-{:03}", i),
+                "Accelerometer".to_string(),
+                SensorData::Accelerometer {
+                    x: 0.01 * i as f32,
+                    y: -0.02,
+                    z: 9.81,
+                    unit: "m/s²".to_string(),
+                },
+                i,
+            ));
+        }
+        builder.build()
+    }
+
+    #[cfg(feature = "binary_serde")]
+    #[test]
+    fn test_telemetry_packet_cbor_roundtrip() {
+        let packet = packet_with_readings(50);
+        let bytes = packet.to_cbor().unwrap();
+        let restored = TelemetryPacket::from_cbor(&bytes).unwrap();
+
+        assert_eq!(restored.timestamp, packet.timestamp);
+        assert_eq!(
+            serde_json::to_value(&restored).unwrap(),
+            serde_json::to_value(&packet).unwrap()
+        );
+    }
+
+    #[cfg(feature = "binary_serde")]
+    #[test]
+    fn test_cbor_smaller_than_json() {
+        let packet = packet_with_readings(50);
+        let cbor = packet.to_cbor().unwrap().len();
+        let json = packet.to_json_bytes().unwrap().len();
+        // Field names are still carried, so expect at least a 15% saving
+        assert!(cbor * 100 < json * 85, "cbor {} vs json {}", cbor, json);
+    }
+
+    #[test]
+    fn test_v0_packet_deserializes_and_migrates() {
+        let v0 = r#"{
+            "sequence": 12,
+            "timestamp": "2024-05-01T12:00:00Z",
+            "health": {
+                "status": "HEALTHY",
+                "timestamp": "2024-05-01T12:00:00Z",
+                "healthy_components": 3,
+                "degraded_components": 0,
+                "failed_components": 0,
+                "uptime_seconds": 60,
+                "cpu_usage_percent": 5.0,
+                "memory_usage_bytes": 1024,
+                "temperature_celsius": 40.0
+            },
+            "sensor_readings": [],
+            "diagnostics": {
+                "timestamp": "2024-05-01T12:00:00Z",
+                "total_entries": 0,
+                "entries_by_level": {},
+                "recent_entries": []
+            }
+        }"#;
+
+        let mut packet = TelemetryPacket::from_json(v0).unwrap();
+        assert_eq!(packet.schema_version, 0);
+        assert_eq!(packet.sequence, 12);
+        assert!(!packet.is_newer_schema());
+
+        packet.migrate_to_current().unwrap();
+        assert_eq!(packet.schema_version, SCHEMA_VERSION);
+        assert_eq!(packet.health.healthy_components, 3);
+    }
+
+    #[test]
+    fn test_newer_schema_rejected_and_left_untouched() {
+        let mut packet = TelemetryPacket::new(1);
+        packet.schema_version = SCHEMA_VERSION + 1;
+        assert!(packet.is_newer_schema());
+        assert_eq!(
+            packet.migrate_to_current(),
+            Err(UnsupportedSchemaVersion(SCHEMA_VERSION + 1))
+        );
+        assert_eq!(packet.schema_version, SCHEMA_VERSION + 1);
+    }
+
     #[test]
     fn test_telemetry_packet_size() {
         let packet = TelemetryPacket::new(1);
         let size = packet.size_bytes();
         assert!(size > 0);
     }
+
+    #[test]
+    fn test_packet_builder() {
+        let reading = |id: &str, value: f32| {
+            SensorReading::new(
+                id.to_string(),
+                "Temperature".to_string(),
+                SensorData::Temperature {
+                    value,
+                    unit: "°C".to_string(),
+                },
+                1,
+            )
+        };
+
+        let packet = TelemetryPacket::builder()
+            .sequence(42)
+            .add_reading(reading("temp-01", 21.0))
+            .add_reading(reading("temp-02", 22.5))
+            .add_diagnostic(DiagnosticEntry::new(
+                DiagnosticLevel::Warning,
+                "temp-02".to_string(),
+                "Reading drifting",
+            ))
+            .metadata("source", "test")
+            .build();
+
+        assert_eq!(packet.sequence, 42);
+        assert_eq!(packet.sensor_readings.len(), 2);
+        assert_eq!(packet.diagnostics.total_entries, 1);
+        assert_eq!(packet.diagnostics.entries_by_level["Warning"], 1);
+        assert_eq!(packet.metadata["source"], "test");
+    }
+
+    #[test]
+    fn test_diagnostic_entry_from_catalog_code() {
+        let mut catalog = DiagnosticCatalog::new();
+        catalog.register(
+            "TEMP_OVER_LIMIT",
+            DiagnosticLevel::Error,
+            "Temperature above operating limit",
+        );
+
+        let entry =
+            DiagnosticEntry::from_code(&catalog, "TEMP_OVER_LIMIT", "temp-01".to_string()).unwrap();
+        assert_eq!(entry.level, DiagnosticLevel::Error);
+        assert_eq!(entry.message, "Temperature above operating limit");
+        assert_eq!(entry.code.as_deref(), Some("TEMP_OVER_LIMIT"));
+        assert_eq!(entry.component_id, "temp-01");
+
+        assert!(DiagnosticEntry::from_code(&catalog, "UNKNOWN", "temp-01".to_string()).is_none());
+    }
 }
@@ -0,0 +1,238 @@
+//! Physical quantities with explicit units
+//!
+//! `Quantity` pairs a value with a `Unit` so that mixing, for example,
+//! Celsius and Fahrenheit is caught instead of silently producing garbage.
+//! Arithmetic requires matching units, and conversion is only allowed
+//! within the same physical dimension.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Standard gravity in m/s²
+const STANDARD_GRAVITY: f64 = 9.80665;
+
+/// Error type for unit operations
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum UnitError {
+    #[error("Unit mismatch: {left} vs {right}")]
+    Mismatch { left: Unit, right: Unit },
+    #[error("Cannot convert {from} to {to}")]
+    IncompatibleDimension { from: Unit, to: Unit },
+}
+
+/// Physical dimension a unit measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Dimension {
+    Temperature,
+    Pressure,
+    Ratio,
+    Acceleration,
+    AngularVelocity,
+    Length,
+}
+
+/// Supported measurement units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Unit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Hectopascal,
+    Pascal,
+    Percent,
+    MetersPerSecondSquared,
+    StandardGravity,
+    DegreesPerSecond,
+    RadiansPerSecond,
+    Meters,
+    Feet,
+}
+
+impl Unit {
+    /// Get the dimension this unit measures
+    pub fn dimension(&self) -> Dimension {
+        match self {
+            Self::Celsius | Self::Fahrenheit | Self::Kelvin => Dimension::Temperature,
+            Self::Hectopascal | Self::Pascal => Dimension::Pressure,
+            Self::Percent => Dimension::Ratio,
+            Self::MetersPerSecondSquared | Self::StandardGravity => Dimension::Acceleration,
+            Self::DegreesPerSecond | Self::RadiansPerSecond => Dimension::AngularVelocity,
+            Self::Meters | Self::Feet => Dimension::Length,
+        }
+    }
+
+    /// Get the conventional symbol for this unit
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+            Self::Kelvin => "K",
+            Self::Hectopascal => "hPa",
+            Self::Pascal => "Pa",
+            Self::Percent => "%",
+            Self::MetersPerSecondSquared => "m/s²",
+            Self::StandardGravity => "g",
+            Self::DegreesPerSecond => "°/s",
+            Self::RadiansPerSecond => "rad/s",
+            Self::Meters => "m",
+            Self::Feet => "ft",
+        }
+    }
+
+    /// Convert a value in this unit to the base unit of its dimension
+    fn value_to_base(self, value: f64) -> f64 {
+        match self {
+            Self::Celsius => value + 273.15,
+            Self::Fahrenheit => (value - 32.0) * 5.0 / 9.0 + 273.15,
+            Self::Hectopascal => value * 100.0,
+            Self::StandardGravity => value * STANDARD_GRAVITY,
+            Self::DegreesPerSecond => value.to_radians(),
+            Self::Feet => value * 0.3048,
+            Self::Kelvin
+            | Self::Pascal
+            | Self::Percent
+            | Self::MetersPerSecondSquared
+            | Self::RadiansPerSecond
+            | Self::Meters => value,
+        }
+    }
+
+    /// Convert a value in the base unit of this dimension to this unit
+    fn value_from_base(self, value: f64) -> f64 {
+        match self {
+            Self::Celsius => value - 273.15,
+            Self::Fahrenheit => (value - 273.15) * 9.0 / 5.0 + 32.0,
+            Self::Hectopascal => value / 100.0,
+            Self::StandardGravity => value / STANDARD_GRAVITY,
+            Self::DegreesPerSecond => value.to_degrees(),
+            Self::Feet => value / 0.3048,
+            Self::Kelvin
+            | Self::Pascal
+            | Self::Percent
+            | Self::MetersPerSecondSquared
+            | Self::RadiansPerSecond
+            | Self::Meters => value,
+        }
+    }
+}
+
+impl std::fmt::Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// A value tagged with its unit
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Quantity {
+    /// Create a new quantity
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Add another quantity, rejecting mismatched units
+    pub fn checked_add(self, other: Quantity) -> Result<Quantity, UnitError> {
+        self.ensure_same_unit(&other)?;
+        Ok(Self::new(self.value + other.value, self.unit))
+    }
+
+    /// Subtract another quantity, rejecting mismatched units
+    pub fn checked_sub(self, other: Quantity) -> Result<Quantity, UnitError> {
+        self.ensure_same_unit(&other)?;
+        Ok(Self::new(self.value - other.value, self.unit))
+    }
+
+    /// Scale the quantity by a dimensionless factor
+    pub fn scale(self, factor: f64) -> Quantity {
+        Self::new(self.value * factor, self.unit)
+    }
+
+    /// Convert to another unit of the same dimension
+    pub fn convert_to(self, unit: Unit) -> Result<Quantity, UnitError> {
+        if self.unit.dimension() != unit.dimension() {
+            return Err(UnitError::IncompatibleDimension {
+                from: self.unit,
+                to: unit,
+            });
+        }
+        Ok(Self::new(
+            unit.value_from_base(self.unit.value_to_base(self.value)),
+            unit,
+        ))
+    }
+
+    fn ensure_same_unit(&self, other: &Quantity) -> Result<(), UnitError> {
+        if self.unit == other.unit {
+            Ok(())
+        } else {
+            Err(UnitError::Mismatch {
+                left: self.unit,
+                right: other.unit,
+            })
+        }
+    }
+}
+
+impl std::fmt::Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_mismatched_units_rejected() {
+        let celsius = Quantity::new(20.0, Unit::Celsius);
+        let fahrenheit = Quantity::new(68.0, Unit::Fahrenheit);
+
+        let err = celsius.checked_add(fahrenheit).unwrap_err();
+        assert_eq!(
+            err,
+            UnitError::Mismatch {
+                left: Unit::Celsius,
+                right: Unit::Fahrenheit
+            }
+        );
+
+        let sum = celsius
+            .checked_add(Quantity::new(1.5, Unit::Celsius))
+            .unwrap();
+        assert_eq!(sum, Quantity::new(21.5, Unit::Celsius));
+    }
+
+    #[test]
+    fn test_conversion() {
+        let boiling = Quantity::new(100.0, Unit::Celsius)
+            .convert_to(Unit::Fahrenheit)
+            .unwrap();
+        assert!((boiling.value - 212.0).abs() < 1e-9);
+        assert_eq!(boiling.unit, Unit::Fahrenheit);
+
+        let pressure = Quantity::new(1013.25, Unit::Hectopascal)
+            .convert_to(Unit::Pascal)
+            .unwrap();
+        assert!((pressure.value - 101_325.0).abs() < 1e-6);
+
+        let one_g = Quantity::new(1.0, Unit::StandardGravity)
+            .convert_to(Unit::MetersPerSecondSquared)
+            .unwrap();
+        assert!((one_g.value - 9.80665).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_conversion_across_dimensions_rejected() {
+        let result = Quantity::new(20.0, Unit::Celsius).convert_to(Unit::Pascal);
+        assert!(matches!(
+            result,
+            Err(UnitError::IncompatibleDimension { .. })
+        ));
+    }
+}
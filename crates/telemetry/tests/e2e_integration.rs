@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use telemetry::{PipelineConfig, StreamingPipeline, SystemHealth, TelemetryPacket};
+use telemetry::{
+    PipelineConfig, ProducerHandle, StreamingPipeline, SystemHealth, TelemetryPacket,
+    SCHEMA_VERSION,
+};
 
 #[tokio::test]
 async fn e2e_pipeline_runs_and_sends() {
@@ -13,6 +16,7 @@ async fn e2e_pipeline_runs_and_sends() {
         enable_compression: false,
         enable_resilience: true,
         channel_capacity: 1024,
+        ..Default::default()
     };
 
     // Use a temporary file under target/test_output
@@ -29,28 +33,26 @@ async fn e2e_pipeline_runs_and_sends() {
         .expect("pipeline");
     let sender = pipeline.get_sender();
 
-    // Spawn a producer that generates telemetry for `run_duration`.
-    let producer = tokio::spawn(async move {
-        let start = Instant::now();
-        let mut seq: u64 = 0;
-        while Instant::now() - start < run_duration {
-            let packet = TelemetryPacket {
-                sequence: seq,
-                timestamp: chrono::Utc::now(),
-                health: SystemHealth::new(),
-                sensor_readings: vec![],
-                diagnostics: Default::default(),
-            };
-            if let Err(_) = sender.send(packet).await {
-                break;
-            }
-            seq += 1;
-            tokio::time::sleep(Duration::from_millis(100)).await;
-        }
+    // Spawn a producer that generates telemetry until stopped.
+    let producer = ProducerHandle::spawn(sender, Duration::from_millis(100), |seq| {
+        Some(TelemetryPacket {
+            sequence: seq,
+            schema_version: SCHEMA_VERSION,
+            timestamp: chrono::Utc::now(),
+            health: SystemHealth::new(),
+            sensor_readings: vec![],
+            diagnostics: Default::default(),
+            metadata: Default::default(),
+        })
     });
 
-    // Wait for producer to finish
-    producer.await.expect("producer panicked");
+    // Let it run for `run_duration`, bailing out early if the pipeline dies
+    let deadline = Instant::now() + run_duration;
+    while Instant::now() < deadline && !producer.is_finished() {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let produced = producer.stop().await;
+    assert!(produced > 0, "Expected the producer to send packets");
 
     // Allow pipeline to flush
     tokio::time::sleep(Duration::from_secs(1)).await;